@@ -0,0 +1,204 @@
+use crate::probe::{probe_audio_file, probe_clip};
+use crate::theme::Themed;
+use crate::unified_clip::{ClipLookup, find_unified_clip};
+use crate::validate::{FilenameRules, fit_filename_stem, sanitize_and_validate_filename_stem};
+use anyhow::{Context, Result, bail};
+use inquire::{Confirm, Select, Text};
+use std::path::Path;
+use tokio::process::Command;
+
+fn validate_volume(volume: f32, flag: &str) -> Result<f32> {
+    if !volume.is_finite() || !(0.0..=10.0).contains(&volume) {
+        bail!("{flag} must be between 0.0 and 10.0, got {volume}.");
+    }
+    Ok(volume)
+}
+
+fn audio_filter(mix_original: bool, original_volume: f32, audio_volume: f32) -> String {
+    if mix_original {
+        format!(
+            "[0:a]volume={original_volume}[orig];[1:a]volume={audio_volume}[ext];[orig][ext]amix=inputs=2:duration=first:dropout_transition=0:normalize=0[aout]"
+        )
+    } else {
+        format!("[1:a]volume={audio_volume},apad[aout]")
+    }
+}
+
+async fn mux_audio(
+    clip_path: &Path,
+    audio_path: &Path,
+    output: &Path,
+    mix_original: bool,
+    original_volume: f32,
+    audio_volume: f32,
+) -> Result<()> {
+    let output_result = Command::new("ffmpeg")
+        .arg("-y")
+        .arg("-i")
+        .arg(clip_path)
+        .arg("-i")
+        .arg(audio_path)
+        .arg("-filter_complex")
+        .arg(audio_filter(mix_original, original_volume, audio_volume))
+        .arg("-map")
+        .arg("0:v")
+        .arg("-map")
+        .arg("[aout]")
+        .arg("-c:v")
+        .arg("copy")
+        .arg("-c:a")
+        .arg("aac")
+        .arg("-shortest")
+        .arg(output)
+        .output()
+        .await
+        .context("Failed to execute ffmpeg. Is it installed and in your PATH?")?;
+
+    if !output_result.status.success() {
+        let stderr = String::from_utf8_lossy(&output_result.stderr);
+        bail!(
+            "ffmpeg failed with status: {}\n{}",
+            output_result.status,
+            stderr
+        );
+    }
+    Ok(())
+}
+
+pub async fn handle_add_audio(
+    name: &str,
+    audio_path: &Path,
+    keep_original: bool,
+    original_volume: f32,
+    audio_volume: f32,
+    filename_rules: &FilenameRules,
+    lookup: ClipLookup,
+) -> Result<()> {
+    let original_volume = validate_volume(original_volume, "--original-volume")?;
+    let audio_volume = validate_volume(audio_volume, "--audio-volume")?;
+
+    let clip = find_unified_clip(name, lookup).await?;
+    let clip_path_str = clip
+        .local_path
+        .context(format!("Clip '{}' not found locally.", clip.name))?;
+    let clip_path = Path::new(&clip_path_str);
+
+    println!("{}", "◌ Checking clip and audio file...".warning());
+    let clip_info = probe_clip(clip_path)
+        .await
+        .context(format!("Clip '{}' is not playable", clip.name))?;
+    let audio_duration = probe_audio_file(audio_path).await.context(format!(
+        "'{}' is not a usable audio file",
+        audio_path.display()
+    ))?;
+
+    let mix_original = keep_original && clip_info.has_audio;
+    if keep_original && !clip_info.has_audio {
+        println!(
+            "{}",
+            "⚠ The clip has no audio track to keep; the new audio will replace it.".warning()
+        );
+    }
+    if audio_duration + 0.05 < clip_info.duration {
+        println!(
+            "{}",
+            format!(
+                "○ Audio is shorter than the clip ({audio_duration:.1}s vs {:.1}s); the rest will be silent.",
+                clip_info.duration
+            )
+            .warning()
+        );
+    } else if audio_duration > clip_info.duration + 0.05 {
+        println!(
+            "{}",
+            format!(
+                "○ Audio is longer than the clip ({audio_duration:.1}s vs {:.1}s); it will be cut to fit.",
+                clip_info.duration
+            )
+            .warning()
+        );
+    }
+
+    let ext = clip_path
+        .extension()
+        .and_then(|e| e.to_str())
+        .unwrap_or("mp4")
+        .to_string();
+
+    let options = vec![
+        "Create a new copy with the audio",
+        "Modify the original file",
+    ];
+    let choice = Select::new("What would you like to do?", options).prompt()?;
+
+    let (output_path, is_overwrite) = if choice == "Create a new copy with the audio" {
+        let new_name_suggestion = format!("{}_audio", clip.name);
+        let new_name_input = Text::new("› Enter name for the new clip (without extension):")
+            .with_initial_value(&new_name_suggestion)
+            .prompt()?;
+        let new_name_stem = fit_filename_stem(
+            &sanitize_and_validate_filename_stem(&new_name_input, filename_rules)?,
+            &ext,
+            filename_rules,
+        )?;
+        (
+            clip_path.with_file_name(format!("{new_name_stem}.{ext}")),
+            false,
+        )
+    } else {
+        let confirmed = Confirm::new("Modifying the original file cannot be undone. Are you sure?")
+            .with_default(false)
+            .prompt()?;
+        if !confirmed {
+            println!("{}", "○ Operation cancelled.".warning());
+            return Ok(());
+        }
+        (clip_path.to_path_buf(), true)
+    };
+
+    let temp_output_path = output_path.with_extension(format!("tmp.{ext}"));
+
+    println!(
+        "{}",
+        if mix_original {
+            "◌ Mixing audio into clip..."
+        } else {
+            "◌ Replacing clip audio..."
+        }
+        .warning()
+    );
+
+    if let Err(e) = mux_audio(
+        clip_path,
+        audio_path,
+        &temp_output_path,
+        mix_original,
+        original_volume,
+        audio_volume,
+    )
+    .await
+    {
+        let _ = tokio::fs::remove_file(&temp_output_path).await;
+        return Err(e);
+    }
+
+    if is_overwrite {
+        tokio::fs::rename(&temp_output_path, clip_path)
+            .await
+            .context("Failed to replace original file")?;
+        println!("{}", "✔ Original clip successfully modified.".success());
+    } else {
+        tokio::fs::rename(&temp_output_path, &output_path)
+            .await
+            .context("Failed to save new clip")?;
+        println!(
+            "{}",
+            format!(
+                "✔ New clip saved as '{}'",
+                output_path.file_name().unwrap().to_str().unwrap()
+            )
+            .success()
+        );
+    }
+    Ok(())
+}
@@ -0,0 +1,83 @@
+use crate::cli_settings::CliSettings;
+use crate::theme::Themed;
+use crate::unified_clip::{ClipLookup, find_unified_clip, gather_clips};
+use anyhow::{Result, bail};
+use colored::*;
+
+fn validate_alias(alias: &str) -> Result<String> {
+    let trimmed = alias.trim();
+    if trimmed.is_empty() {
+        bail!("Alias cannot be empty.");
+    }
+    if trimmed
+        .chars()
+        .any(|c| c.is_whitespace() || matches!(c, '*' | '?'))
+    {
+        bail!("Alias cannot contain whitespace or glob characters (* ?).");
+    }
+    Ok(trimmed.to_lowercase())
+}
+
+pub fn resolve_alias<'a>(settings: &'a CliSettings, name: &str) -> Option<&'a str> {
+    settings
+        .aliases
+        .get(&name.to_lowercase())
+        .map(String::as_str)
+}
+
+pub async fn handle_alias_add(alias: &str, target: &str, lookup: ClipLookup) -> Result<()> {
+    let alias = validate_alias(alias)?;
+    let clip = find_unified_clip(target, lookup).await?;
+
+    if gather_clips(lookup)
+        .await?
+        .iter()
+        .any(|c| c.name.eq_ignore_ascii_case(&alias))
+    {
+        println!(
+            "{}",
+            format!("⚠ A clip named '{alias}' exists; the alias will take precedence over it.")
+                .warning()
+        );
+    }
+
+    let mut settings = CliSettings::load().await?;
+    if let Some(previous) = settings.aliases.insert(alias.clone(), clip.name.clone()) {
+        println!(
+            "{}",
+            format!("○ Alias '{alias}' previously pointed to '{previous}'.").warning()
+        );
+    }
+    settings.save().await?;
+    println!(
+        "{}",
+        format!("✔ '{alias}' now refers to '{}'.", clip.name).success()
+    );
+    Ok(())
+}
+
+pub async fn handle_alias_list() -> Result<()> {
+    let settings = CliSettings::load().await?;
+    if settings.aliases.is_empty() {
+        println!("{}", "○ No aliases defined.".warning());
+        return Ok(());
+    }
+    for (alias, target) in &settings.aliases {
+        println!("  {} → {}", alias.accent().bold(), target);
+    }
+    Ok(())
+}
+
+pub async fn handle_alias_remove(alias: &str) -> Result<()> {
+    let alias = validate_alias(alias)?;
+    let mut settings = CliSettings::load().await?;
+    let Some(target) = settings.aliases.remove(&alias) else {
+        bail!("No alias named '{alias}'.");
+    };
+    settings.save().await?;
+    println!(
+        "{}",
+        format!("✔ Removed alias '{alias}' (was '{target}').").success()
+    );
+    Ok(())
+}
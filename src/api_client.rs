@@ -0,0 +1,102 @@
+use crate::cli_settings::CliSettings;
+use crate::token_store::session_token;
+use reqwest::header::{AUTHORIZATION, HeaderMap, HeaderValue};
+use reqwest::{Client, Response, StatusCode};
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+use std::time::Duration;
+use uuid::Uuid;
+use wayclip_core::api::ApiClientError;
+use wayclip_core::models::{HostedClipInfo, UserProfile};
+use wayclip_core::settings::Settings;
+
+const REQUEST_TIMEOUT_SECS: u64 = 300;
+
+fn request_error(e: reqwest::Error) -> ApiClientError {
+    ApiClientError::Config(e.into())
+}
+
+fn build_client(headers: HeaderMap) -> Result<Client, ApiClientError> {
+    Client::builder()
+        .default_headers(headers)
+        .timeout(Duration::from_secs(REQUEST_TIMEOUT_SECS))
+        .build()
+        .map_err(request_error)
+}
+
+pub async fn anonymous_client() -> Result<Client, ApiClientError> {
+    build_client(CliSettings::load().await?.header_map()?)
+}
+
+pub async fn api_client() -> Result<Client, ApiClientError> {
+    let mut headers = CliSettings::load().await?.header_map()?;
+    if let Some(token) = session_token().await? {
+        let value = HeaderValue::from_str(&format!("Bearer {token}"))
+            .map_err(|e| ApiClientError::Config(e.into()))?;
+        headers.insert(AUTHORIZATION, value);
+    }
+    build_client(headers)
+}
+
+async fn read_response<T: DeserializeOwned>(response: Response) -> Result<T, ApiClientError> {
+    match response.status() {
+        StatusCode::OK | StatusCode::CREATED => response.json::<T>().await.map_err(request_error),
+        StatusCode::UNAUTHORIZED => Err(ApiClientError::Unauthorized),
+        StatusCode::NOT_FOUND => Err(ApiClientError::NotFound),
+        status => Err(ApiClientError::ApiError {
+            status: status.as_u16(),
+            message: response
+                .text()
+                .await
+                .unwrap_or_else(|_| "Could not retrieve error message.".to_string()),
+        }),
+    }
+}
+
+pub async fn current_user_json() -> Result<Value, ApiClientError> {
+    let settings = Settings::load().await?;
+    let response = api_client()
+        .await?
+        .get(format!("{}/api/me", settings.api_url))
+        .send()
+        .await
+        .map_err(request_error)?;
+    read_response(response).await
+}
+
+pub async fn current_user() -> Result<UserProfile, ApiClientError> {
+    Ok(serde_json::from_value(current_user_json().await?)?)
+}
+
+pub async fn hosted_clips_index() -> Result<Vec<HostedClipInfo>, ApiClientError> {
+    let settings = Settings::load().await?;
+    let response = api_client()
+        .await?
+        .get(format!("{}/api/clips/index", settings.api_url))
+        .send()
+        .await
+        .map_err(request_error)?;
+    read_response(response).await
+}
+
+pub async fn delete_hosted_clip(clip_id: Uuid) -> Result<(), ApiClientError> {
+    let settings = Settings::load().await?;
+    let response = api_client()
+        .await?
+        .delete(format!("{}/api/clip/{clip_id}", settings.api_url))
+        .send()
+        .await
+        .map_err(request_error)?;
+    match response.status() {
+        StatusCode::NO_CONTENT => Ok(()),
+        StatusCode::UNAUTHORIZED => Err(ApiClientError::Unauthorized),
+        StatusCode::NOT_FOUND => Err(ApiClientError::NotFound),
+        status => Err(ApiClientError::ApiError {
+            status: status.as_u16(),
+            message: response
+                .text()
+                .await
+                .unwrap_or_else(|_| "Could not retrieve error message.".to_string()),
+        }),
+    }
+}
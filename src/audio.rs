@@ -1,29 +1,50 @@
 use crate::model::{AudioDevice, PwNode};
-use anyhow::{Context, Result, bail};
+use crate::theme::Themed;
+use anyhow::{Context, Result, anyhow, bail};
 use colored::*;
 use inquire::Select;
 use regex::Regex;
+use std::process::Output;
+use std::time::Duration;
 use tokio::process::Command;
 use wayclip_core::settings::Settings;
 
-pub async fn handle_audio() -> Result<()> {
-    println!("○ Gathering audio device information...");
+const TOOL_TIMEOUT: Duration = Duration::from_secs(5);
 
-    let pw_dump_output = Command::new("pw-dump")
-        .arg("Node")
-        .output()
-        .await
-        .context("Failed to execute 'pw-dump'. Is PipeWire installed and running?")?;
+struct DeviceList {
+    backend: &'static str,
+    sources: Vec<AudioDevice>,
+    sinks: Vec<AudioDevice>,
+    default_source_desc: Option<String>,
+    default_sink_desc: Option<String>,
+}
 
-    if !pw_dump_output.status.success() {
-        let stderr = String::from_utf8_lossy(&pw_dump_output.stderr);
+async fn run_tool(program: &str, args: &[&str]) -> Result<Output> {
+    let output = tokio::time::timeout(
+        TOOL_TIMEOUT,
+        Command::new(program).args(args).kill_on_drop(true).output(),
+    )
+    .await
+    .map_err(|_| {
+        anyhow!(
+            "'{program}' did not respond within {}s",
+            TOOL_TIMEOUT.as_secs()
+        )
+    })?
+    .context(format!("Failed to execute '{program}'"))?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
         bail!(
-            "'pw-dump' failed with status: {}\n{}",
-            pw_dump_output.status,
-            stderr
+            "'{program}' failed with status: {}\n{}",
+            output.status,
+            stderr.trim()
         );
     }
+    Ok(output)
+}
 
+async fn pipewire_devices() -> Result<DeviceList> {
+    let pw_dump_output = run_tool("pw-dump", &["Node"]).await?;
     let all_nodes: Vec<PwNode> = serde_json::from_slice(&pw_dump_output.stdout)
         .context("Failed to parse JSON from 'pw-dump'.")?;
 
@@ -53,13 +74,16 @@ pub async fn handle_audio() -> Result<()> {
         }
     }
 
-    let wpctl_output = Command::new("wpctl")
-        .arg("status")
-        .output()
-        .await
-        .context("Failed to execute 'wpctl'.")?;
-
-    let wpctl_stdout = String::from_utf8_lossy(&wpctl_output.stdout);
+    let wpctl_stdout = match run_tool("wpctl", &["status"]).await {
+        Ok(output) => String::from_utf8_lossy(&output.stdout).into_owned(),
+        Err(e) => {
+            println!(
+                "{}",
+                format!("⚠ Could not read default devices: {e:#}").warning()
+            );
+            String::new()
+        }
+    };
     let default_re = Regex::new(r"│\s+\*\s+\d+\.\s+(.*?)\s+\[vol:").unwrap();
     let mut default_sink_desc = None;
     let mut default_source_desc = None;
@@ -94,6 +118,130 @@ pub async fn handle_audio() -> Result<()> {
         }
     }
 
+    Ok(DeviceList {
+        backend: "PipeWire",
+        sources,
+        sinks,
+        default_source_desc,
+        default_sink_desc,
+    })
+}
+
+fn parse_pactl_devices(output: &str) -> Vec<AudioDevice> {
+    let mut devices = Vec::new();
+    let mut name: Option<String> = None;
+    for line in output.lines().map(str::trim) {
+        if let Some(value) = line.strip_prefix("Name: ") {
+            name = Some(value.to_string());
+        } else if let Some(value) = line.strip_prefix("Description: ")
+            && let Some(name) = name.take()
+        {
+            devices.push(AudioDevice {
+                name,
+                description: value.to_string(),
+            });
+        }
+    }
+    devices
+}
+
+async fn pulseaudio_devices() -> Result<DeviceList> {
+    let sources: Vec<AudioDevice> = parse_pactl_devices(&String::from_utf8_lossy(
+        &run_tool("pactl", &["list", "sources"]).await?.stdout,
+    ))
+    .into_iter()
+    .filter(|d| !d.name.ends_with(".monitor"))
+    .collect();
+    let sinks = parse_pactl_devices(&String::from_utf8_lossy(
+        &run_tool("pactl", &["list", "sinks"]).await?.stdout,
+    ));
+
+    let default_desc = |devices: &[AudioDevice], output: Option<Output>| {
+        let name = String::from_utf8_lossy(&output?.stdout).trim().to_string();
+        devices
+            .iter()
+            .find(|d| d.name == name)
+            .map(|d| d.description.clone())
+    };
+    let default_source_desc = default_desc(
+        &sources,
+        run_tool("pactl", &["get-default-source"]).await.ok(),
+    );
+    let default_sink_desc =
+        default_desc(&sinks, run_tool("pactl", &["get-default-sink"]).await.ok());
+
+    Ok(DeviceList {
+        backend: "PulseAudio",
+        sources,
+        sinks,
+        default_source_desc,
+        default_sink_desc,
+    })
+}
+
+fn parse_alsa_devices(output: &str) -> Vec<AudioDevice> {
+    let card_re = Regex::new(r"^card (\d+): .*?\[(.*?)\], device (\d+): .*?\[(.*?)\]").unwrap();
+    output
+        .lines()
+        .filter_map(|line| card_re.captures(line))
+        .map(|caps| AudioDevice {
+            name: format!("hw:{},{}", &caps[1], &caps[3]),
+            description: format!("{}: {}", &caps[2], &caps[4]),
+        })
+        .collect()
+}
+
+async fn alsa_devices() -> Result<DeviceList> {
+    let sources = parse_alsa_devices(&String::from_utf8_lossy(
+        &run_tool("arecord", &["-l"]).await?.stdout,
+    ));
+    let sinks = parse_alsa_devices(&String::from_utf8_lossy(
+        &run_tool("aplay", &["-l"]).await?.stdout,
+    ));
+    Ok(DeviceList {
+        backend: "ALSA",
+        sources,
+        sinks,
+        default_source_desc: None,
+        default_sink_desc: None,
+    })
+}
+
+async fn list_devices() -> Result<DeviceList> {
+    let pipewire_error = match pipewire_devices().await {
+        Ok(devices) => return Ok(devices),
+        Err(e) => e,
+    };
+    println!(
+        "{}",
+        format!("⚠ PipeWire tools unavailable ({pipewire_error:#}); trying fallbacks...").warning()
+    );
+    if let Ok(devices) = pulseaudio_devices().await {
+        return Ok(devices);
+    }
+    alsa_devices().await.context(format!(
+        "Could not list audio devices with PipeWire, PulseAudio or ALSA tools. PipeWire error: {pipewire_error:#}"
+    ))
+}
+
+pub async fn handle_audio() -> Result<()> {
+    println!("○ Gathering audio device information...");
+
+    let DeviceList {
+        backend,
+        sources,
+        sinks,
+        default_source_desc,
+        default_sink_desc,
+    } = list_devices().await?;
+    println!("○ Listing devices via {}.", backend.accent());
+    if backend != "PipeWire" {
+        println!(
+            "{}",
+            "⚠ The daemon records through PipeWire; these device names may not match.".warning()
+        );
+    }
+
     let mut settings = Settings::load().await?;
 
     if !sources.is_empty() {
@@ -122,7 +270,7 @@ pub async fn handle_audio() -> Result<()> {
             settings.mic_node_name = selected_source.name.clone();
         }
     } else {
-        println!("{}", "⚠ No audio sources found.".yellow());
+        println!("{}", "⚠ No audio sources found.".warning());
     }
 
     if !sinks.is_empty() {
@@ -151,16 +299,16 @@ pub async fn handle_audio() -> Result<()> {
             settings.bg_node_name = selected_sink.name.clone();
         }
     } else {
-        println!("{}", "⚠ No audio sinks found.".yellow());
+        println!("{}", "⚠ No audio sinks found.".warning());
     }
 
     settings.save().await?;
     println!(
         "\n{}",
-        "✔ Audio settings updated successfully!".green().bold()
+        "✔ Audio settings updated successfully!".success().bold()
     );
-    println!("  Mic set to: {}", settings.mic_node_name.cyan());
-    println!("  Audio set to: {}", settings.bg_node_name.cyan());
+    println!("  Mic set to: {}", settings.mic_node_name.accent());
+    println!("  Audio set to: {}", settings.bg_node_name.accent());
 
     Ok(())
 }
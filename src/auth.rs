@@ -1,15 +1,28 @@
+use crate::api_client::{anonymous_client, api_client, current_user};
+use crate::exit::SilentExit;
 use crate::model::{AuthCallbackResult, LOCAL_PORT};
+use crate::profile_cache;
+use crate::theme::Themed;
+use crate::token_store;
+use crate::url::open_url_or_print;
 use anyhow::{Context, Result, bail};
 use colored::*;
+use comfy_table::Table;
 use inquire::{Confirm, Password, PasswordDisplayMode, Select, Text};
+use qrcode::QrCode;
+use qrcode::render::unicode::Dense1x2;
 use serde_json::Value;
+use std::path::Path;
 use std::time::Duration;
 use tokio::io::{AsyncReadExt, AsyncWriteExt};
 use tokio::net::TcpListener;
 use tokio::sync::oneshot;
+use tokio::time::Instant;
 use wayclip_core::api;
 use wayclip_core::settings::Settings;
 
+const MAX_2FA_VERIFY_ATTEMPTS: u32 = 3;
+
 fn parse_token_from_header(response: &reqwest::Response) -> Option<String> {
     response
         .headers()
@@ -27,7 +40,61 @@ fn parse_token_from_header(response: &reqwest::Response) -> Option<String> {
         })
 }
 
-async fn handle_oauth_login(provider: &str, browser: &Option<String>) -> Result<()> {
+pub async fn handle_login_check(verbose: bool) -> Result<()> {
+    match current_user().await {
+        Ok(profile) => {
+            if verbose {
+                println!("✔ Logged in as {}", profile.user.username);
+            }
+            Ok(())
+        }
+        Err(api::ApiClientError::Unauthorized) => {
+            if verbose {
+                println!("✗ Not logged in");
+            }
+            Err(SilentExit(1).into())
+        }
+        Err(e) => {
+            if verbose {
+                println!("✗ Could not verify login: {e}");
+            }
+            Err(SilentExit(2).into())
+        }
+    }
+}
+
+async fn wait_for_callback(
+    mut rx: oneshot::Receiver<AuthCallbackResult>,
+    timeout_secs: u64,
+) -> Result<AuthCallbackResult> {
+    let deadline = Instant::now() + Duration::from_secs(timeout_secs);
+    let mut ticker = tokio::time::interval(Duration::from_secs(30));
+    ticker.tick().await;
+
+    loop {
+        tokio::select! {
+            result = &mut rx => {
+                return result.context("Login callback server stopped unexpectedly.");
+            }
+            _ = tokio::time::sleep_until(deadline) => {
+                bail!("Login timed out after {timeout_secs}s. Please try again, or pass a longer --timeout.");
+            }
+            _ = ticker.tick() => {
+                let remaining = deadline.saturating_duration_since(Instant::now());
+                println!(
+                    "{}",
+                    format!("◌ Still waiting... {}s remaining", remaining.as_secs()).warning()
+                );
+            }
+        }
+    }
+}
+
+async fn handle_oauth_login(
+    provider: &str,
+    browser: &Option<String>,
+    timeout_secs: u64,
+) -> Result<()> {
     let settings = Settings::load().await?;
     let (tx, rx) = oneshot::channel::<AuthCallbackResult>();
 
@@ -69,7 +136,7 @@ async fn handle_oauth_login(provider: &str, browser: &Option<String>) -> Result<
         urlencoding::encode(&redirect_uri)
     );
 
-    println!("{}", "○ Opening your browser to complete login...".cyan());
+    println!("{}", "○ Opening your browser to complete login...".accent());
     if let Some(browser_cmd) = browser {
         let status = tokio::process::Command::new(browser_cmd)
             .arg(&login_url)
@@ -77,21 +144,16 @@ async fn handle_oauth_login(provider: &str, browser: &Option<String>) -> Result<
 
         if status.is_err() {
             eprintln!("Failed to open with '{browser_cmd}'. Falling back to system opener.",);
-            if opener::open(&login_url).is_err() {
-                println!("Could not open browser automatically.");
-                println!("Please visit this URL to log in:\n{login_url}");
-            }
+            open_url_or_print(&login_url).await;
         }
-    } else if opener::open(&login_url).is_err() {
-        println!("Could not open browser automatically.");
-        println!("Please visit this URL to log in:\n{login_url}");
+    } else {
+        open_url_or_print(&login_url).await;
     }
 
-    println!("{}", "◌ Waiting for authentication...".yellow());
-    let result = tokio::time::timeout(Duration::from_secs(120), rx)
-        .await
-        .context("Login timed out. Please try again.")??;
+    println!("{}", "◌ Waiting for authentication...".warning());
+    let result = wait_for_callback(rx, timeout_secs).await;
     server_handle.abort();
+    let result = result?;
 
     match result {
         AuthCallbackResult::Error(reason) if reason == "port" => {
@@ -103,8 +165,8 @@ async fn handle_oauth_login(provider: &str, browser: &Option<String>) -> Result<
             bail!("Login failed: {e}");
         }
         AuthCallbackResult::Success(token) => {
-            api::login(token).await?;
-            println!("{}", "✔ Login successful!".green().bold());
+            token_store::store_token(token).await?;
+            println!("{}", "✔ Login successful!".success().bold());
         }
         AuthCallbackResult::TwoFactor(two_fa_token) => {
             return handle_2fa_authentication(&two_fa_token).await;
@@ -114,21 +176,27 @@ async fn handle_oauth_login(provider: &str, browser: &Option<String>) -> Result<
     Ok(())
 }
 
-async fn handle_password_login() -> Result<()> {
+async fn handle_password_login(email: Option<&str>, password: Option<&str>) -> Result<()> {
     let settings = Settings::load().await?;
-    let email = Text::new("› Enter your email:")
-        .prompt()?
-        .trim()
-        .to_string();
+    let email = match email {
+        Some(email) => email.trim().to_string(),
+        None => Text::new("› Enter your email:")
+            .prompt()?
+            .trim()
+            .to_string(),
+    };
     if email.is_empty() {
         bail!("Email cannot be empty.");
     }
 
-    let password = Password::new("› Enter your password:")
-        .with_display_mode(PasswordDisplayMode::Masked)
-        .prompt()?;
+    let password = match password {
+        Some(password) => password.to_string(),
+        None => Password::new("› Enter your password:")
+            .with_display_mode(PasswordDisplayMode::Masked)
+            .prompt()?,
+    };
 
-    let client = reqwest::Client::new();
+    let client = anonymous_client().await?;
     let response = client
         .post(format!("{}/auth/login", settings.api_url))
         .json(&serde_json::json!({
@@ -161,8 +229,8 @@ async fn handle_password_login() -> Result<()> {
         let token = parse_token_from_header(&temp_response)
             .context("Login token not found in server response.")?;
 
-        api::login(token).await?;
-        println!("{}", "✔ Login successful!".green().bold());
+        token_store::store_token(token).await?;
+        println!("{}", "✔ Login successful!".success().bold());
         Ok(())
     } else {
         let error_body: serde_json::Value =
@@ -191,7 +259,10 @@ async fn handle_password_login() -> Result<()> {
 async fn handle_2fa_authentication(two_fa_token: &str) -> Result<()> {
     let settings = Settings::load().await?;
 
-    println!("{}", "○ Two-Factor Authentication Required".yellow().bold());
+    println!(
+        "{}",
+        "○ Two-Factor Authentication Required".warning().bold()
+    );
     let code = Text::new("› Enter your 2FA code or a recovery code:")
         .prompt()?
         .trim()
@@ -200,7 +271,7 @@ async fn handle_2fa_authentication(two_fa_token: &str) -> Result<()> {
         bail!("2FA code cannot be empty.");
     }
 
-    let client = reqwest::Client::new();
+    let client = anonymous_client().await?;
     let response = client
         .post(format!("{}/auth/2fa/authenticate", settings.api_url))
         .json(&serde_json::json!({
@@ -213,8 +284,8 @@ async fn handle_2fa_authentication(two_fa_token: &str) -> Result<()> {
     if response.status().is_success() {
         let token =
             parse_token_from_header(&response).context("2FA token not found in response.")?;
-        api::login(token).await?;
-        println!("{}", "✔ 2FA authentication successful!".green().bold());
+        token_store::store_token(token).await?;
+        println!("{}", "✔ 2FA authentication successful!".success().bold());
         Ok(())
     } else {
         let error_body: serde_json::Value = response.json().await.unwrap_or_default();
@@ -228,7 +299,7 @@ async fn handle_2fa_authentication(two_fa_token: &str) -> Result<()> {
 async fn handle_register() -> Result<()> {
     let settings = Settings::load().await?;
 
-    println!("{}", "○ Create a new account".cyan().bold());
+    println!("{}", "○ Create a new account".accent().bold());
     let username = Text::new("› Enter your username:")
         .prompt()?
         .trim()
@@ -249,7 +320,7 @@ async fn handle_register() -> Result<()> {
         .with_display_mode(PasswordDisplayMode::Masked)
         .prompt()?;
 
-    let client = reqwest::Client::new();
+    let client = anonymous_client().await?;
     let response = client
         .post(format!("{}/auth/register", settings.api_url))
         .json(&serde_json::json!({
@@ -261,10 +332,10 @@ async fn handle_register() -> Result<()> {
         .await?;
 
     if response.status().is_success() {
-        println!("{}", "✔ Registration successful!".green().bold());
+        println!("{}", "✔ Registration successful!".success().bold());
         println!(
             "{}",
-            "◌ Please check your email to verify your account before logging in.".yellow()
+            "◌ Please check your email to verify your account before logging in.".warning()
         );
         return Ok(());
     }
@@ -279,7 +350,7 @@ async fn handle_register() -> Result<()> {
 async fn handle_resend_verification(email: &str) -> Result<()> {
     let settings = Settings::load().await?;
 
-    let client = reqwest::Client::new();
+    let client = anonymous_client().await?;
     let response = client
         .post(format!("{}/auth/resend-verification", settings.api_url))
         .json(&serde_json::json!({
@@ -291,16 +362,38 @@ async fn handle_resend_verification(email: &str) -> Result<()> {
     if response.status().is_success() {
         println!(
             "{}",
-            "✔ Verification email sent (if account exists).".green()
+            "✔ Verification email sent (if account exists).".success()
         );
     } else {
-        println!("{}", "✗ Could not send verification email.".yellow());
+        println!("{}", "✗ Could not send verification email.".warning());
     }
 
     Ok(())
 }
 
-pub async fn handle_login(browser: &Option<String>) -> Result<()> {
+pub async fn handle_login(
+    browser: &Option<String>,
+    timeout_secs: u64,
+    token: Option<&str>,
+    email: Option<&str>,
+    password: Option<&str>,
+) -> Result<()> {
+    if let Some(token) = token {
+        let token = token.trim();
+        if token.is_empty() {
+            bail!("Token cannot be empty.");
+        }
+        token_store::store_token(token.to_string()).await?;
+        profile_cache::invalidate().await;
+        println!("{}", "✔ Token saved.".success().bold());
+        return Ok(());
+    }
+    if email.is_some() {
+        handle_password_login(email, password).await?;
+        profile_cache::invalidate().await;
+        return Ok(());
+    }
+
     let options = vec![
         "GitHub",
         "Google",
@@ -312,31 +405,33 @@ pub async fn handle_login(browser: &Option<String>) -> Result<()> {
 
     match choice {
         "GitHub" | "Google" | "Discord" => {
-            handle_oauth_login(&choice.to_lowercase(), browser).await?;
+            handle_oauth_login(&choice.to_lowercase(), browser, timeout_secs).await?;
         }
         "Email/Password" => {
-            handle_password_login().await?;
+            handle_password_login(None, None).await?;
         }
         "Register new account" => {
             handle_register().await?;
         }
         _ => unreachable!(),
     }
+    profile_cache::invalidate().await;
 
     Ok(())
 }
 
 pub async fn handle_logout() -> Result<()> {
-    api::logout().await?;
-    println!("{}", "✔ You have been logged out.".green());
+    token_store::clear_tokens().await?;
+    profile_cache::invalidate().await;
+    println!("{}", "✔ You have been logged out.".success());
     Ok(())
 }
 
 pub async fn handle_2fa_setup() -> Result<()> {
     let settings = Settings::load().await?;
 
-    println!("{}", "◌ Contacting the server to set up 2FA...".yellow());
-    let client = api::get_api_client().await?;
+    println!("{}", "◌ Contacting the server to set up 2FA...".warning());
+    let client = api_client().await?;
     let response = client
         .post(format!("{}/api/2fa/setup", settings.api_url))
         .send()
@@ -352,58 +447,187 @@ pub async fn handle_2fa_setup() -> Result<()> {
         .as_str()
         .context("No secret key received from the server. The API response may have changed.")?;
 
-    println!("{}", "\n○ Two-Factor Authentication Setup".cyan().bold());
+    let username = profile_cache::current_user(false)
+        .await
+        .map(|profile| profile.user.username)
+        .ok();
+
+    println!("{}", "\n○ Two-Factor Authentication Setup".accent().bold());
     println!("1. Open an authenticator app (like Google Authenticator, Authy, or 1Password).");
-    println!("2. Choose to add a new account via manual entry or secret key.");
-    println!("3. Enter the following secret key when prompted:");
-    println!("\n   {}", secret.green().bold());
+    match render_otpauth_qr(&otpauth_uri(secret, username.as_deref())) {
+        Some(qr) => {
+            println!(
+                "2. Scan this QR code, or choose manual entry and enter the secret key below:"
+            );
+            println!("\n{qr}");
+        }
+        None => {
+            println!("2. Choose to add a new account via manual entry or secret key.");
+            println!("3. Enter the following secret key when prompted:");
+        }
+    }
+    println!("\n   {}", secret.success().bold());
     println!("\n4. After adding the account, your app will generate a 6-digit code.");
 
-    let code = Text::new("› Enter the code from your app to verify and complete the setup:")
-        .prompt()?
-        .trim()
-        .to_string();
+    let mut last_error = String::new();
+    for attempt in 1..=MAX_2FA_VERIFY_ATTEMPTS {
+        let code = Text::new("› Enter the code from your app to verify and complete the setup:")
+            .prompt()?
+            .trim()
+            .to_string();
 
-    if code.is_empty() {
-        bail!("Verification code cannot be empty. Setup cancelled.");
+        if code.is_empty() {
+            bail!("Verification code cannot be empty. Setup cancelled.");
+        }
+
+        println!("{}", "◌ Verifying code with the server...".warning());
+        let verify_response = client
+            .post(format!("{}/api/2fa/verify", settings.api_url))
+            .json(&serde_json::json!({
+                "secret": secret,
+                "code": code,
+            }))
+            .send()
+            .await?;
+
+        if verify_response.status().is_success() {
+            let verify_data: Value = verify_response.json().await?;
+            profile_cache::invalidate().await;
+            println!("{}", "✔ 2FA enabled successfully!".success().bold());
+
+            if let Some(recovery_codes) = verify_data["recovery_codes"].as_array() {
+                print_recovery_codes(recovery_codes);
+            }
+            return Ok(());
+        }
+
+        let error_body: Value = verify_response.json().await.unwrap_or_default();
+        last_error = error_body["message"]
+            .as_str()
+            .unwrap_or("Verification failed.")
+            .to_string();
+        if attempt == MAX_2FA_VERIFY_ATTEMPTS {
+            break;
+        }
+        println!(
+            "{}",
+            format!(
+                "✗ {last_error} Attempt {attempt} of {MAX_2FA_VERIFY_ATTEMPTS}. Your secret key is unchanged, so wait for a fresh code and try again."
+            )
+            .warning()
+        );
     }
 
-    println!("{}", "◌ Verifying code with the server...".yellow());
-    let verify_response = client
-        .post(format!("{}/api/2fa/verify", settings.api_url))
-        .json(&serde_json::json!({
-            "secret": secret,
-            "code": code,
-        }))
-        .send()
-        .await?;
+    bail!("2FA setup failed after {MAX_2FA_VERIFY_ATTEMPTS} attempts: {last_error}");
+}
 
-    if verify_response.status().is_success() {
-        let verify_data: Value = verify_response.json().await?;
-        println!("{}", "✔ 2FA enabled successfully!".green().bold());
+fn otpauth_uri(secret: &str, username: Option<&str>) -> String {
+    let label = match username {
+        Some(username) => format!("Wayclip:{username}"),
+        None => "Wayclip".to_string(),
+    };
+    format!(
+        "otpauth://totp/{}?secret={secret}&issuer=Wayclip",
+        urlencoding::encode(&label)
+    )
+}
 
-        if let Some(recovery_codes) = verify_data["recovery_codes"].as_array() {
-            println!(
-                "\n{}",
-                "IMPORTANT: Save these recovery codes in a safe place:"
-                    .yellow()
-                    .bold()
-            );
-            println!("These can be used to access your account if you lose your 2FA device.");
-            for (i, code) in recovery_codes.iter().enumerate() {
-                if let Some(code_str) = code.as_str() {
-                    println!("  {}. {}", i + 1, code_str.cyan());
-                }
-            }
+fn render_otpauth_qr(uri: &str) -> Option<String> {
+    let code = QrCode::new(uri.as_bytes()).ok()?;
+    let qr_width = code.width() + 8;
+    let terminal_width = Table::new().width()?;
+    if qr_width > terminal_width as usize {
+        return None;
+    }
+    Some(
+        code.render::<Dense1x2>()
+            .dark_color(Dense1x2::Light)
+            .light_color(Dense1x2::Dark)
+            .quiet_zone(true)
+            .build(),
+    )
+}
+
+fn print_recovery_codes(recovery_codes: &[Value]) {
+    println!(
+        "\n{}",
+        "IMPORTANT: Save these recovery codes in a safe place:"
+            .warning()
+            .bold()
+    );
+    println!("These can be used to access your account if you lose your 2FA device.");
+    for (i, code) in recovery_codes.iter().enumerate() {
+        if let Some(code_str) = code.as_str() {
+            println!("  {}. {}", i + 1, code_str.accent());
         }
-    } else {
-        let error_body: Value = verify_response.json().await.unwrap_or_default();
+    }
+}
+
+async fn write_recovery_codes(path: &Path, recovery_codes: &[Value]) -> Result<()> {
+    let contents: String = recovery_codes
+        .iter()
+        .filter_map(|code| code.as_str())
+        .map(|code| format!("{code}\n"))
+        .collect();
+    tokio::fs::write(path, contents)
+        .await
+        .context(format!("Failed to write {}", path.display()))?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        tokio::fs::set_permissions(path, std::fs::Permissions::from_mode(0o600)).await?;
+    }
+    Ok(())
+}
+
+pub async fn handle_2fa_recovery_codes(output: Option<&Path>) -> Result<()> {
+    let confirmed = Confirm::new(
+        "Generate new recovery codes? Your existing recovery codes will stop working.",
+    )
+    .with_default(false)
+    .prompt()?;
+    if !confirmed {
+        println!("{}", "○ Operation cancelled.".warning());
+        return Ok(());
+    }
+
+    let settings = Settings::load().await?;
+    println!("{}", "◌ Regenerating recovery codes...".warning());
+    let client = api_client().await?;
+    let response = client
+        .post(format!(
+            "{}/auth/2fa/recovery-codes/regenerate",
+            settings.api_url
+        ))
+        .send()
+        .await?;
+
+    let status = response.status();
+    if status == reqwest::StatusCode::UNAUTHORIZED {
+        bail!("You are not logged in. Please run `wayclip login` first.");
+    }
+    if !status.is_success() {
+        let error_body: Value = response.json().await.unwrap_or_default();
         let error_msg = error_body["message"]
             .as_str()
-            .unwrap_or("Verification failed.");
-        bail!("2FA setup failed: {error_msg}");
+            .unwrap_or("Unexpected server response.");
+        bail!("Failed to regenerate recovery codes: {error_msg}");
     }
 
+    let body: Value = response.json().await?;
+    let recovery_codes = body["recovery_codes"].as_array().context(
+        "No recovery codes received from the server. The API response may have changed.",
+    )?;
+    println!("{}", "✔ New recovery codes generated.".success().bold());
+    print_recovery_codes(recovery_codes);
+
+    if let Some(path) = output {
+        write_recovery_codes(path, recovery_codes).await?;
+        println!(
+            "{}",
+            format!("✔ Recovery codes saved to {}", path.display()).success()
+        );
+    }
     Ok(())
 }
 
@@ -427,18 +651,18 @@ fn parse_token_from_request(request: &str) -> Option<AuthCallbackResult> {
 }
 
 pub async fn handle_2fa_status() -> Result<()> {
-    match api::get_current_user().await {
+    match current_user().await {
         Ok(profile) => {
             if profile.user.two_factor_enabled {
                 println!(
                     "{}",
-                    "✔ Two-Factor Authentication is ENABLED".green().bold()
+                    "✔ Two-Factor Authentication is ENABLED".success().bold()
                 );
                 println!("Your account is protected with 2FA.");
             } else {
                 println!(
                     "{}",
-                    "⚠ Two-Factor Authentication is DISABLED".yellow().bold()
+                    "⚠ Two-Factor Authentication is DISABLED".warning().bold()
                 );
                 println!("Run `wayclip 2fa setup` to enable 2FA for better security.");
             }
@@ -1,15 +1,353 @@
+use crate::model::InitSystem;
+use crate::theme::Themed;
 use anyhow::{Context, Result, bail};
 use colored::*;
 use inquire::Confirm;
+use std::env;
+use std::os::unix::fs::PermissionsExt;
+use std::path::{Path, PathBuf};
 use tokio::process::Command;
 use wayclip_core::settings::Settings;
 use which::which;
 
-pub async fn handle_autostart_on() -> Result<()> {
-    println!("○ Enabling autostart using systemd user service...");
+const DESKTOP_FILE: &str = "wayclip-daemon.desktop";
+const SERVICE_NAME: &str = "wayclip-daemon";
+
+fn detect_init() -> InitSystem {
+    if Path::new("/run/systemd/system").exists() {
+        InitSystem::Systemd
+    } else {
+        InitSystem::Xdg
+    }
+}
+
+fn resolve_init(init: Option<InitSystem>) -> InitSystem {
+    init.unwrap_or_else(|| {
+        let detected = detect_init();
+        if detected != InitSystem::Systemd {
+            println!(
+                "{}",
+                "○ systemd was not detected; using an XDG autostart entry. Pass --init openrc or --init runit to create a user service instead."
+                    .warning()
+            );
+        }
+        detected
+    })
+}
+
+fn home_dir() -> Result<PathBuf> {
+    env::var_os("HOME")
+        .map(PathBuf::from)
+        .context("HOME is not set")
+}
+
+fn desktop_file_path() -> PathBuf {
+    Settings::config_path().join("autostart").join(DESKTOP_FILE)
+}
+
+fn openrc_script_path() -> PathBuf {
+    Settings::config_path()
+        .join("rc")
+        .join("init.d")
+        .join(SERVICE_NAME)
+}
+
+fn runit_service_dir() -> Result<PathBuf> {
+    Ok(home_dir()?.join("service").join(SERVICE_NAME))
+}
+
+async fn confirm_overwrite(path: &Path) -> Result<bool> {
+    if !path.exists() {
+        return Ok(true);
+    }
+    let overwrite = Confirm::new(&format!("{} already exists. Overwrite?", path.display()))
+        .with_default(false)
+        .prompt()?;
+    if !overwrite {
+        println!("{}", "○ Autostart setup cancelled.".warning());
+    }
+    Ok(overwrite)
+}
+
+async fn write_autostart_file(path: &Path, contents: &str, executable: bool) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        tokio::fs::create_dir_all(parent)
+            .await
+            .context(format!("Failed to create {}", parent.display()))?;
+    }
+    tokio::fs::write(path, contents)
+        .await
+        .context(format!("Failed to write {}", path.display()))?;
+    if executable {
+        tokio::fs::set_permissions(path, std::fs::Permissions::from_mode(0o755)).await?;
+    }
+    println!("✔ Created {}", path.display());
+    Ok(())
+}
+
+async fn run_init_command(program: &str, args: &[&str]) -> Result<()> {
+    let command_line = format!("{program} {}", args.join(" "));
+    let output = Command::new(program)
+        .args(args)
+        .output()
+        .await
+        .context(format!("Failed to execute '{command_line}'."))?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        bail!("'{command_line}' failed. Please run it manually.\nError: {stderr}");
+    }
+    Ok(())
+}
+
+fn print_enabled() {
+    println!("{}", "✔ Autostart enabled successfully!".success().bold());
+    println!("  The Wayclip daemon will now start automatically when you log in.");
+    println!(
+        "  To disable it, run: {}",
+        "wayclip daemon autostart off".italic()
+    );
+}
 
+pub async fn handle_autostart_on(init: Option<InitSystem>) -> Result<()> {
+    let init = resolve_init(init);
     let daemon_path = which("wayclip-daemon")
         .context("Could not find 'wayclip-daemon' executable in your PATH. Please ensure it is installed correctly.")?;
+
+    match init {
+        InitSystem::Systemd => systemd_on(&daemon_path).await,
+        InitSystem::Openrc => openrc_on(&daemon_path).await,
+        InitSystem::Runit => runit_on(&daemon_path).await,
+        InitSystem::Xdg => xdg_on(&daemon_path).await,
+    }
+}
+
+pub async fn handle_autostart_off(init: Option<InitSystem>) -> Result<()> {
+    match resolve_init(init) {
+        InitSystem::Systemd => systemd_off().await,
+        InitSystem::Openrc => openrc_off().await,
+        InitSystem::Runit => runit_off().await,
+        InitSystem::Xdg => xdg_off().await,
+    }
+}
+
+enum AutostartState {
+    Enabled,
+    Disabled(String),
+    NotInstalled,
+}
+
+async fn command_stdout(program: &str, args: &[&str]) -> Option<String> {
+    let output = Command::new(program).args(args).output().await.ok()?;
+    Some(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+async fn autostart_state(init: InitSystem) -> Result<AutostartState> {
+    let state = match init {
+        InitSystem::Systemd => {
+            let service_name = format!("{SERVICE_NAME}.service");
+            if !Settings::config_path().join(&service_name).exists() {
+                return Ok(AutostartState::NotInstalled);
+            }
+            match command_stdout("systemctl", &["--user", "is-enabled", &service_name]).await {
+                Some(state) if state == "enabled" => AutostartState::Enabled,
+                Some(state) if !state.is_empty() => AutostartState::Disabled(state),
+                _ => AutostartState::Disabled("unknown".to_string()),
+            }
+        }
+        InitSystem::Openrc => {
+            if !openrc_script_path().exists() {
+                return Ok(AutostartState::NotInstalled);
+            }
+            let runlevel = command_stdout("rc-update", &["--user", "show", "default"]).await;
+            if runlevel.is_some_and(|out| out.lines().any(|l| l.trim().starts_with(SERVICE_NAME))) {
+                AutostartState::Enabled
+            } else {
+                AutostartState::Disabled("not in the default runlevel".to_string())
+            }
+        }
+        InitSystem::Runit => {
+            let service_dir = runit_service_dir()?;
+            if !service_dir.join("run").exists() {
+                return Ok(AutostartState::NotInstalled);
+            }
+            if service_dir.join("down").exists() {
+                AutostartState::Disabled("down".to_string())
+            } else {
+                AutostartState::Enabled
+            }
+        }
+        InitSystem::Xdg => {
+            let desktop_path = desktop_file_path();
+            if !desktop_path.exists() {
+                return Ok(AutostartState::NotInstalled);
+            }
+            let contents = tokio::fs::read_to_string(&desktop_path).await?;
+            if contents.lines().any(|l| l.trim() == "Hidden=true") {
+                AutostartState::Disabled("hidden".to_string())
+            } else {
+                AutostartState::Enabled
+            }
+        }
+    };
+    Ok(state)
+}
+
+pub async fn handle_autostart_status(init: Option<InitSystem>) -> Result<()> {
+    let init = init.unwrap_or_else(detect_init);
+    let label = format!("Autostart ({init}):");
+    match autostart_state(init).await? {
+        AutostartState::Enabled => println!("{label} {}", "enabled".success()),
+        AutostartState::Disabled(state) => {
+            println!("{label} {}", format!("disabled ({state})").warning())
+        }
+        AutostartState::NotInstalled => println!(
+            "{label} {}\n  To enable it, run: {}",
+            "not installed".error(),
+            "wayclip daemon autostart on".italic()
+        ),
+    }
+    Ok(())
+}
+
+async fn xdg_on(daemon_path: &Path) -> Result<()> {
+    println!("○ Enabling autostart using an XDG autostart entry...");
+    println!("  Daemon found at: {}", daemon_path.display());
+    let desktop_path = desktop_file_path();
+    if !confirm_overwrite(&desktop_path).await? {
+        return Ok(());
+    }
+    let desktop_content = format!(
+        r#"[Desktop Entry]
+Type=Application
+Name=Wayclip Daemon
+Comment=Background replay buffer for Wayclip
+Exec={}
+Terminal=false
+NoDisplay=true
+X-GNOME-Autostart-enabled=true
+"#,
+        daemon_path.display()
+    );
+    write_autostart_file(&desktop_path, &desktop_content, false).await?;
+    print_enabled();
+    println!(
+        "  {}",
+        "The daemon starts with your next graphical session; run `wayclip daemon start` to start it now."
+            .dimmed()
+    );
+    Ok(())
+}
+
+async fn xdg_off() -> Result<()> {
+    let desktop_path = desktop_file_path();
+    if !desktop_path.exists() {
+        println!(
+            "{}",
+            "○ Autostart is already disabled (autostart entry not found).".warning()
+        );
+        return Ok(());
+    }
+    tokio::fs::remove_file(&desktop_path)
+        .await
+        .context(format!("Failed to remove {}", desktop_path.display()))?;
+    println!("{}", "✔ Autostart disabled successfully!".success().bold());
+    Ok(())
+}
+
+async fn openrc_on(daemon_path: &Path) -> Result<()> {
+    println!("○ Enabling autostart using an OpenRC user service...");
+    println!("  Daemon found at: {}", daemon_path.display());
+    let script_path = openrc_script_path();
+    if !confirm_overwrite(&script_path).await? {
+        return Ok(());
+    }
+    let script_content = format!(
+        r#"#!/sbin/openrc-run
+description="Wayclip Daemon"
+supervisor=supervise-daemon
+command="{}"
+respawn_delay=5
+respawn_max=5
+
+depend() {{
+    need pipewire
+}}
+"#,
+        daemon_path.display()
+    );
+    write_autostart_file(&script_path, &script_content, true).await?;
+
+    println!("○ Enabling and starting the service...");
+    run_init_command("rc-update", &["--user", "add", SERVICE_NAME, "default"]).await?;
+    run_init_command("rc-service", &["--user", SERVICE_NAME, "start"]).await?;
+    print_enabled();
+    Ok(())
+}
+
+async fn openrc_off() -> Result<()> {
+    let script_path = openrc_script_path();
+    if !script_path.exists() {
+        println!(
+            "{}",
+            "○ Autostart is already disabled (service script not found).".warning()
+        );
+        return Ok(());
+    }
+    println!("○ Disabling and stopping the service...");
+    let _ = run_init_command("rc-service", &["--user", SERVICE_NAME, "stop"]).await;
+    run_init_command("rc-update", &["--user", "del", SERVICE_NAME, "default"]).await?;
+    tokio::fs::remove_file(&script_path)
+        .await
+        .context(format!("Failed to remove {}", script_path.display()))?;
+    println!("{}", "✔ Autostart disabled successfully!".success().bold());
+    Ok(())
+}
+
+async fn runit_on(daemon_path: &Path) -> Result<()> {
+    println!("○ Enabling autostart using a runit user service...");
+    println!("  Daemon found at: {}", daemon_path.display());
+    let service_dir = runit_service_dir()?;
+    let run_path = service_dir.join("run");
+    if !confirm_overwrite(&run_path).await? {
+        return Ok(());
+    }
+    let run_content = format!("#!/bin/sh\nexec {} 2>&1\n", daemon_path.display());
+    write_autostart_file(&run_path, &run_content, true).await?;
+    print_enabled();
+    println!(
+        "  {}",
+        format!(
+            "This requires a per-user runsvdir watching {}.",
+            service_dir.parent().unwrap_or(&service_dir).display()
+        )
+        .dimmed()
+    );
+    Ok(())
+}
+
+async fn runit_off() -> Result<()> {
+    let service_dir = runit_service_dir()?;
+    if !service_dir.exists() {
+        println!(
+            "{}",
+            "○ Autostart is already disabled (service directory not found).".warning()
+        );
+        return Ok(());
+    }
+    let _ = Command::new("sv")
+        .arg("down")
+        .arg(&service_dir)
+        .output()
+        .await;
+    tokio::fs::remove_dir_all(&service_dir)
+        .await
+        .context(format!("Failed to remove {}", service_dir.display()))?;
+    println!("{}", "✔ Autostart disabled successfully!".success().bold());
+    Ok(())
+}
+
+async fn systemd_on(daemon_path: &Path) -> Result<()> {
+    println!("○ Enabling autostart using systemd user service...");
     println!("  Daemon found at: {}", daemon_path.display());
 
     let service_content = format!(
@@ -51,7 +389,7 @@ WantedBy=default.target
             .with_default(false)
             .prompt()?;
         if !overwrite {
-            println!("{}", "○ Autostart setup cancelled.".yellow());
+            println!("{}", "○ Autostart setup cancelled.".warning());
             return Ok(());
         }
     }
@@ -91,17 +429,12 @@ WantedBy=default.target
         bail!("'systemctl --user enable --now' failed. Please run it manually.\nError: {stderr}",);
     }
 
-    println!("{}", "✔ Autostart enabled successfully!".green().bold());
-    println!("  The Wayclip daemon will now start automatically when you log in.");
-    println!(
-        "  To disable it, run: {}",
-        "wayclip daemon autostart off".italic()
-    );
+    print_enabled();
 
     Ok(())
 }
 
-pub async fn handle_autostart_off() -> Result<()> {
+async fn systemd_off() -> Result<()> {
     println!("○ Disabling autostart using systemd user service...");
 
     let service_name = "wayclip-daemon.service";
@@ -112,7 +445,7 @@ pub async fn handle_autostart_off() -> Result<()> {
     if !service_path.exists() {
         println!(
             "{}",
-            "○ Autostart is already disabled (service file not found).".yellow()
+            "○ Autostart is already disabled (service file not found).".warning()
         );
         return Ok(());
     }
@@ -136,7 +469,7 @@ pub async fn handle_autostart_off() -> Result<()> {
         }
     }
 
-    println!("{}", "✔ Autostart disabled successfully!".green().bold());
+    println!("{}", "✔ Autostart disabled successfully!".success().bold());
 
     let delete_file = Confirm::new("Do you want to remove the systemd service file?")
         .with_default(false)
@@ -0,0 +1,270 @@
+use crate::data_file::write_atomic;
+use crate::encrypt::private_temp_path;
+use crate::profile_cache;
+use crate::theme::Themed;
+use anyhow::{Context, Result, bail};
+use chrono::Utc;
+use colored::*;
+use inquire::Confirm;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use tokio::process::Command;
+use wayclip_core::settings::Settings;
+
+const MANIFEST_NAME: &str = "wayclip-backup.json";
+const BACKUP_VERSION: u32 = 1;
+const CONFIG_FILES: [&str; 4] = [
+    "settings.json",
+    "cli-settings.json",
+    "cli-state.json",
+    "data.json",
+];
+
+#[derive(Serialize, Deserialize, Debug)]
+struct BackupManifest {
+    version: u32,
+    created_at: String,
+    files: Vec<String>,
+    includes_token: bool,
+}
+
+fn config_dir() -> PathBuf {
+    Settings::config_path().join("wayclip")
+}
+
+fn is_session_file(name: &str) -> bool {
+    !CONFIG_FILES.contains(&name)
+        && name != MANIFEST_NAME
+        && !name.ends_with(".tmp")
+        && !name.ends_with(".bak")
+}
+
+async fn session_files(dir: &Path) -> Result<Vec<String>> {
+    let mut files = Vec::new();
+    let mut entries = tokio::fs::read_dir(dir)
+        .await
+        .context(format!("Failed to read {}", dir.display()))?;
+    while let Some(entry) = entries.next_entry().await? {
+        if !entry.file_type().await?.is_file() {
+            continue;
+        }
+        if let Some(name) = entry.file_name().to_str().filter(|n| is_session_file(n)) {
+            files.push(name.to_string());
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+async fn run_tar(args: &[&std::ffi::OsStr]) -> Result<Vec<u8>> {
+    let output = Command::new("tar")
+        .args(args)
+        .output()
+        .await
+        .context("Failed to execute tar. Is it installed and in your PATH?")?;
+    if !output.status.success() {
+        bail!(
+            "tar failed with status: {}\n{}",
+            output.status,
+            String::from_utf8_lossy(&output.stderr)
+        );
+    }
+    Ok(output.stdout)
+}
+
+pub async fn handle_config_backup(file: &Path, include_token: bool) -> Result<()> {
+    let source_dir = config_dir();
+    let mut files: Vec<String> = CONFIG_FILES
+        .iter()
+        .filter(|name| source_dir.join(name).is_file())
+        .map(|name| name.to_string())
+        .collect();
+    if files.is_empty() {
+        bail!(
+            "No Wayclip configuration found in {}.",
+            source_dir.display()
+        );
+    }
+    if include_token {
+        println!(
+            "{}",
+            "⚠ The backup will contain your login token. Anyone with the file can act as you; store it securely."
+                .warning()
+        );
+        files.extend(session_files(&source_dir).await?);
+    }
+
+    let staging_dir = private_temp_path("backup");
+    tokio::fs::create_dir_all(&staging_dir)
+        .await
+        .context(format!("Failed to create {}", staging_dir.display()))?;
+    let result = async {
+        for name in &files {
+            tokio::fs::copy(source_dir.join(name), staging_dir.join(name))
+                .await
+                .context(format!("Failed to copy {name}"))?;
+        }
+        let manifest = BackupManifest {
+            version: BACKUP_VERSION,
+            created_at: Utc::now().to_rfc3339(),
+            files: files.clone(),
+            includes_token: include_token,
+        };
+        tokio::fs::write(
+            staging_dir.join(MANIFEST_NAME),
+            serde_json::to_vec_pretty(&manifest)?,
+        )
+        .await?;
+        run_tar(&[
+            "-czf".as_ref(),
+            file.as_os_str(),
+            "-C".as_ref(),
+            staging_dir.as_os_str(),
+            ".".as_ref(),
+        ])
+        .await
+    }
+    .await;
+    let _ = tokio::fs::remove_dir_all(&staging_dir).await;
+    result?;
+
+    #[cfg(unix)]
+    if include_token {
+        use std::os::unix::fs::PermissionsExt;
+        tokio::fs::set_permissions(file, std::fs::Permissions::from_mode(0o600)).await?;
+    }
+
+    println!(
+        "{}",
+        format!("✔ Backed up {} file(s) to {}", files.len(), file.display()).success()
+    );
+    for name in &files {
+        println!("  {name}");
+    }
+    Ok(())
+}
+
+async fn validate_backup(file: &Path, staging_dir: &Path) -> Result<BackupManifest> {
+    let listing = run_tar(&["-tzf".as_ref(), file.as_os_str()])
+        .await
+        .context(format!(
+            "'{}' is not a Wayclip backup archive",
+            file.display()
+        ))?;
+    for entry in String::from_utf8_lossy(&listing).lines() {
+        let entry = entry.trim_start_matches("./");
+        if entry.is_empty() {
+            continue;
+        }
+        if entry.contains('/') || entry == ".." {
+            bail!("Refusing to restore: unexpected entry '{entry}' in the archive.");
+        }
+    }
+
+    run_tar(&[
+        "-xzf".as_ref(),
+        file.as_os_str(),
+        "-C".as_ref(),
+        staging_dir.as_os_str(),
+    ])
+    .await?;
+
+    let manifest_path = staging_dir.join(MANIFEST_NAME);
+    let contents = tokio::fs::read(&manifest_path).await.context(
+        "The archive has no backup manifest; it was not created by `wayclip config backup`.",
+    )?;
+    let manifest: BackupManifest =
+        serde_json::from_slice(&contents).context("The backup manifest is corrupt")?;
+    if manifest.version != BACKUP_VERSION {
+        bail!(
+            "Unsupported backup version {} (expected {BACKUP_VERSION}).",
+            manifest.version
+        );
+    }
+    for name in &manifest.files {
+        if name.contains('/') || name == MANIFEST_NAME {
+            bail!("Refusing to restore: invalid file name '{name}' in the manifest.");
+        }
+        let contents = tokio::fs::read(staging_dir.join(name))
+            .await
+            .context(format!("The archive is missing '{name}'"))?;
+        if CONFIG_FILES.contains(&name.as_str()) {
+            serde_json::from_slice::<serde_json::Value>(&contents)
+                .context(format!("'{name}' in the archive is not valid JSON"))?;
+        }
+    }
+    Ok(manifest)
+}
+
+pub async fn handle_config_restore(file: &Path, yes: bool) -> Result<()> {
+    let staging_dir = private_temp_path("restore");
+    tokio::fs::create_dir_all(&staging_dir)
+        .await
+        .context(format!("Failed to create {}", staging_dir.display()))?;
+    let result = restore_from(file, &staging_dir, yes).await;
+    let _ = tokio::fs::remove_dir_all(&staging_dir).await;
+    result
+}
+
+async fn restore_from(file: &Path, staging_dir: &Path, yes: bool) -> Result<()> {
+    println!("{}", "◌ Validating backup...".warning());
+    let manifest = validate_backup(file, staging_dir).await?;
+
+    let target_dir = config_dir();
+    println!(
+        "○ Backup from {} with {} file(s):",
+        manifest.created_at,
+        manifest.files.len()
+    );
+    for name in &manifest.files {
+        let note = if target_dir.join(name).exists() {
+            " (will be overwritten)".warning().to_string()
+        } else {
+            String::new()
+        };
+        println!("  {name}{note}");
+    }
+    if manifest.includes_token {
+        println!(
+            "{}",
+            "○ This backup includes a login token; you will be signed in as its owner.".warning()
+        );
+    }
+
+    if !yes {
+        let confirmed = Confirm::new("Restore this configuration?")
+            .with_default(false)
+            .prompt()?;
+        if !confirmed {
+            println!("{}", "○ Restore cancelled.".warning());
+            return Ok(());
+        }
+    }
+
+    tokio::fs::create_dir_all(&target_dir)
+        .await
+        .context(format!("Failed to create {}", target_dir.display()))?;
+    for name in &manifest.files {
+        let contents = tokio::fs::read(staging_dir.join(name)).await?;
+        write_atomic(&target_dir.join(name), &contents).await?;
+    }
+    if manifest.includes_token {
+        profile_cache::invalidate().await;
+    }
+
+    println!(
+        "{}",
+        format!(
+            "✔ Restored {} file(s) to {}",
+            manifest.files.len(),
+            target_dir.display()
+        )
+        .success()
+        .bold()
+    );
+    println!(
+        "{}",
+        "○ Run `wayclip daemon restart` so the daemon picks up the restored settings.".warning()
+    );
+    Ok(())
+}
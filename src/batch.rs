@@ -0,0 +1,240 @@
+use crate::api_client::delete_hosted_clip;
+use crate::data_file::{add_clip_tags, remove_clip_tags};
+use crate::import::normalize_tags;
+use crate::rename::rename_clip;
+use crate::theme::Themed;
+use crate::trash::move_to_trash;
+use crate::unified_clip::{ClipLookup, find_unified_clip};
+use crate::validate::{FilenameRules, sanitize_and_validate_filename_stem};
+use anyhow::{Context, Result, anyhow, bail};
+use colored::*;
+use serde::Deserialize;
+use std::fmt;
+use std::path::Path;
+use wayclip_core::{delete_file, update_liked};
+
+#[derive(Deserialize, Debug)]
+#[serde(tag = "action", rename_all = "snake_case", deny_unknown_fields)]
+enum BatchOperation {
+    Rename {
+        name: String,
+        new_name: String,
+    },
+    Delete {
+        name: String,
+        #[serde(default = "default_true")]
+        local: bool,
+        #[serde(default)]
+        server: bool,
+        #[serde(default)]
+        permanent: bool,
+    },
+    Like {
+        name: String,
+        #[serde(default = "default_true")]
+        liked: bool,
+    },
+    Tag {
+        name: String,
+        #[serde(default)]
+        add: Vec<String>,
+        #[serde(default)]
+        remove: Vec<String>,
+    },
+}
+
+fn default_true() -> bool {
+    true
+}
+
+impl fmt::Display for BatchOperation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BatchOperation::Rename { name, new_name } => {
+                write!(f, "rename '{name}' → '{new_name}'")
+            }
+            BatchOperation::Delete {
+                name,
+                local,
+                server,
+                permanent,
+            } => {
+                let scope = match (local, server) {
+                    (true, true) => "local + server",
+                    (false, true) => "server",
+                    _ => "local",
+                };
+                let mode = if *permanent || !local {
+                    ""
+                } else {
+                    ", to trash"
+                };
+                write!(f, "delete '{name}' ({scope}{mode})")
+            }
+            BatchOperation::Like { name, liked } => {
+                write!(f, "{} '{name}'", if *liked { "like" } else { "unlike" })
+            }
+            BatchOperation::Tag { name, add, remove } => {
+                let mut changes = Vec::new();
+                if !add.is_empty() {
+                    changes.push(format!("+{}", add.join(", +")));
+                }
+                if !remove.is_empty() {
+                    changes.push(format!("-{}", remove.join(", -")));
+                }
+                write!(f, "tag '{name}' ({})", changes.join(", "))
+            }
+        }
+    }
+}
+
+async fn run_operation(
+    operation: &BatchOperation,
+    filename_rules: &FilenameRules,
+    lookup: ClipLookup,
+) -> Result<()> {
+    match operation {
+        BatchOperation::Rename { name, new_name } => {
+            let clip = find_unified_clip(name, lookup).await?;
+            let new_stem = sanitize_and_validate_filename_stem(new_name, filename_rules)?;
+            if new_stem == clip.name {
+                return Ok(());
+            }
+            rename_clip(&clip, &new_stem, filename_rules).await?;
+        }
+        BatchOperation::Delete {
+            name,
+            local,
+            server,
+            permanent,
+        } => {
+            if !local && !server {
+                bail!("Nothing to delete: both 'local' and 'server' are false.");
+            }
+            let clip = find_unified_clip(name, lookup).await?;
+            if *server {
+                let hosted_id = clip
+                    .hosted_id
+                    .context(format!("'{}' is not hosted on the server.", clip.name))?;
+                delete_hosted_clip(hosted_id)
+                    .await
+                    .map_err(|e| anyhow!(e))?;
+            }
+            if *local {
+                let local_path = clip
+                    .local_path
+                    .context(format!("'{}' does not exist locally.", clip.name))?;
+                if *permanent {
+                    delete_file(&local_path).await.map_err(|e| anyhow!(e))?;
+                } else {
+                    move_to_trash(Path::new(&local_path)).await?;
+                }
+            }
+        }
+        BatchOperation::Like { name, liked } => {
+            let clip = find_unified_clip(name, lookup).await?;
+            if clip.local_path.is_none() {
+                bail!("'{}' does not exist locally.", clip.name);
+            }
+            update_liked(&clip.full_filename, *liked)
+                .await
+                .map_err(|e| anyhow!("Failed to update liked status: {e}"))?;
+        }
+        BatchOperation::Tag { name, add, remove } => {
+            if add.is_empty() && remove.is_empty() {
+                bail!("Nothing to tag: both 'add' and 'remove' are empty.");
+            }
+            let add = normalize_tags(add)?;
+            let remove = normalize_tags(remove)?;
+            let clip = find_unified_clip(name, lookup).await?;
+            if clip.local_path.is_none() {
+                bail!("'{}' does not exist locally.", clip.name);
+            }
+            if (!add.is_empty() && !add_clip_tags(&clip.full_filename, &add).await?)
+                || (!remove.is_empty() && !remove_clip_tags(&clip.full_filename, &remove).await?)
+            {
+                bail!("'{}' has no local data entry.", clip.name);
+            }
+        }
+    }
+    Ok(())
+}
+
+pub async fn handle_batch(
+    file: &Path,
+    continue_on_error: bool,
+    filename_rules: &FilenameRules,
+    lookup: ClipLookup,
+) -> Result<()> {
+    let contents = tokio::fs::read(file)
+        .await
+        .context(format!("Failed to read {}", file.display()))?;
+    let operations: Vec<BatchOperation> =
+        serde_json::from_slice(&contents).context(format!("Failed to parse {}", file.display()))?;
+
+    if operations.is_empty() {
+        println!("{}", "○ No operations to run.".warning());
+        return Ok(());
+    }
+
+    println!("○ Running {} operations...", operations.len());
+    let mut succeeded = 0;
+    let mut failed = 0;
+    for (index, operation) in operations.iter().enumerate() {
+        let label = format!("[{}/{}] {operation}", index + 1, operations.len());
+        match run_operation(operation, filename_rules, lookup).await {
+            Ok(_) => {
+                succeeded += 1;
+                println!("{} {label}", "✔".success());
+            }
+            Err(e) => {
+                failed += 1;
+                println!("{} {label}: {e:#}", "✗".error());
+                if !continue_on_error {
+                    let skipped = operations.len() - index - 1;
+                    bail!(
+                        "Stopped after a failed operation ({succeeded} succeeded, {skipped} not run). Use --continue-on-error to keep going."
+                    );
+                }
+            }
+        }
+    }
+
+    if failed > 0 {
+        bail!("{failed} of {} operations failed.", operations.len());
+    }
+    println!(
+        "{}",
+        format!("✔ All {succeeded} operations completed.")
+            .success()
+            .bold()
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_tag_operation() {
+        let manifest = r#"[
+            {"action": "tag", "name": "clip", "add": ["funny"], "remove": ["old"]},
+            {"action": "tag", "name": "other", "add": ["best"]}
+        ]"#;
+        let operations: Vec<BatchOperation> = serde_json::from_str(manifest).unwrap();
+        match &operations[0] {
+            BatchOperation::Tag { name, add, remove } => {
+                assert_eq!(name, "clip");
+                assert_eq!(add, &["funny"]);
+                assert_eq!(remove, &["old"]);
+            }
+            other => panic!("unexpected operation: {other}"),
+        }
+        assert!(matches!(
+            &operations[1],
+            BatchOperation::Tag { remove, .. } if remove.is_empty()
+        ));
+        assert_eq!(operations[0].to_string(), "tag 'clip' (+funny, -old)");
+    }
+}
@@ -0,0 +1,175 @@
+use crate::cli_settings::CliSettings;
+use crate::probe::probe_clip;
+use crate::theme::Themed;
+use anyhow::{Context, Result, bail};
+use std::path::{Path, PathBuf};
+use tokio::process::Command;
+
+#[derive(Clone, Debug, Default)]
+pub struct Bumpers {
+    intro: Option<PathBuf>,
+    outro: Option<PathBuf>,
+}
+
+async fn resolve_bumper(
+    kind: &str,
+    flag: Option<&Option<PathBuf>>,
+    default: Option<&PathBuf>,
+) -> Result<Option<PathBuf>> {
+    let path = match flag {
+        None => return Ok(None),
+        Some(Some(path)) => path.clone(),
+        Some(None) => default.cloned().context(format!(
+            "--concat-{kind} was given without a path and no {kind}_path is set in cli-settings.json."
+        ))?,
+    };
+    if !path.is_file() {
+        bail!("The {kind} bumper '{}' does not exist.", path.display());
+    }
+    probe_clip(&path).await.context(format!(
+        "The {kind} bumper '{}' is not a playable video",
+        path.display()
+    ))?;
+    Ok(Some(path))
+}
+
+impl Bumpers {
+    pub async fn resolve(
+        intro: Option<&Option<PathBuf>>,
+        outro: Option<&Option<PathBuf>>,
+        settings: &CliSettings,
+    ) -> Result<Self> {
+        Ok(Self {
+            intro: resolve_bumper("intro", intro, settings.intro_path.as_ref()).await?,
+            outro: resolve_bumper("outro", outro, settings.outro_path.as_ref()).await?,
+        })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.intro.is_none() && self.outro.is_none()
+    }
+}
+
+pub async fn apply_bumpers(
+    bumpers: &Bumpers,
+    clip_path: &Path,
+    output: &Path,
+    keep_audio: bool,
+) -> Result<()> {
+    let clip_info = probe_clip(clip_path).await?;
+    let (Some(width), Some(height)) = (clip_info.width, clip_info.height) else {
+        bail!("Could not read the clip's resolution to scale the bumpers to.");
+    };
+    let fps = clip_info.frame_rate.unwrap_or(60.0);
+    let with_audio = keep_audio && clip_info.has_audio;
+    if clip_info.hdr {
+        println!(
+            "{}",
+            "⚠ Adding bumpers re-encodes the clip in SDR; HDR metadata will be lost.".warning()
+        );
+    }
+    if with_audio && clip_info.audio_streams > 1 {
+        println!(
+            "{}",
+            "⚠ Only the first audio track is kept when adding bumpers.".warning()
+        );
+    }
+
+    let inputs: Vec<&Path> = bumpers
+        .intro
+        .as_deref()
+        .into_iter()
+        .chain(std::iter::once(clip_path))
+        .chain(bumpers.outro.as_deref())
+        .collect();
+
+    concat_reencode(&inputs, output, width, height, fps, with_audio).await
+}
+
+pub async fn concat_reencode(
+    inputs: &[&Path],
+    output: &Path,
+    width: u32,
+    height: u32,
+    fps: f64,
+    with_audio: bool,
+) -> Result<()> {
+    let mut filter = String::new();
+    let mut segments = String::new();
+    for (i, input) in inputs.iter().enumerate() {
+        filter.push_str(&format!(
+            "[{i}:v:0]scale={width}:{height}:force_original_aspect_ratio=decrease,pad={width}:{height}:(ow-iw)/2:(oh-ih)/2,setsar=1,fps={fps},format=yuv420p[v{i}];"
+        ));
+        segments.push_str(&format!("[v{i}]"));
+        if with_audio {
+            let info = probe_clip(input).await?;
+            if info.has_audio {
+                filter.push_str(&format!(
+                    "[{i}:a:0]aresample=48000,aformat=sample_fmts=fltp:channel_layouts=stereo[a{i}];"
+                ));
+            } else {
+                filter.push_str(&format!(
+                    "anullsrc=r=48000:cl=stereo,atrim=duration={:.3}[a{i}];",
+                    info.duration
+                ));
+            }
+            segments.push_str(&format!("[a{i}]"));
+        }
+    }
+    if with_audio {
+        filter.push_str(&format!(
+            "{segments}concat=n={}:v=1:a=1[vout][aout]",
+            inputs.len()
+        ));
+    } else {
+        filter.push_str(&format!(
+            "{segments}concat=n={}:v=1:a=0[vout]",
+            inputs.len()
+        ));
+    }
+
+    let mut command = Command::new("ffmpeg");
+    command.arg("-y");
+    for input in inputs {
+        command.arg("-i").arg(input);
+    }
+    command
+        .arg("-filter_complex")
+        .arg(filter)
+        .arg("-map")
+        .arg("[vout]");
+    let webm = output
+        .extension()
+        .is_some_and(|e| e.eq_ignore_ascii_case("webm"));
+    if webm {
+        command
+            .arg("-c:v")
+            .arg("libvpx-vp9")
+            .arg("-b:v")
+            .arg("0")
+            .arg("-crf")
+            .arg("31");
+    } else {
+        command.arg("-c:v").arg("libx264").arg("-crf").arg("18");
+    }
+    if with_audio {
+        let audio_codec = if webm { "libopus" } else { "aac" };
+        command
+            .arg("-map")
+            .arg("[aout]")
+            .arg("-c:a")
+            .arg(audio_codec);
+    }
+    command.arg(output);
+
+    let output = command
+        .output()
+        .await
+        .context("Failed to execute ffmpeg. Is it installed and in your PATH?")?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        bail!("ffmpeg failed with status: {}\n{}", output.status, stderr);
+    }
+    Ok(())
+}
@@ -0,0 +1,65 @@
+use crate::data_file::write_atomic;
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use std::path::PathBuf;
+use wayclip_core::settings::Settings;
+
+#[derive(Serialize, Deserialize, Default, Debug)]
+#[serde(default)]
+struct CheckpointData {
+    completed: BTreeSet<String>,
+}
+
+pub struct Checkpoint {
+    path: PathBuf,
+    data: CheckpointData,
+}
+
+impl Checkpoint {
+    fn path_for(operation: &str) -> PathBuf {
+        Settings::config_path()
+            .join("wayclip")
+            .join("checkpoints")
+            .join(format!("{operation}.json"))
+    }
+
+    pub async fn open(operation: &str, resume: bool) -> Result<Self> {
+        let path = Self::path_for(operation);
+        let data = if resume && path.exists() {
+            let contents = tokio::fs::read(&path)
+                .await
+                .context(format!("Failed to read {}", path.display()))?;
+            serde_json::from_slice(&contents)
+                .context(format!("Failed to parse {}", path.display()))?
+        } else {
+            CheckpointData::default()
+        };
+        Ok(Self { path, data })
+    }
+
+    pub fn completed_count(&self) -> usize {
+        self.data.completed.len()
+    }
+
+    pub fn is_done(&self, item: &str) -> bool {
+        self.data.completed.contains(item)
+    }
+
+    pub async fn mark_done(&mut self, item: &str) -> Result<()> {
+        self.data.completed.insert(item.to_string());
+        if let Some(parent) = self.path.parent() {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+        write_atomic(&self.path, &serde_json::to_vec_pretty(&self.data)?).await
+    }
+
+    pub async fn finish(self) -> Result<()> {
+        if self.path.exists() {
+            tokio::fs::remove_file(&self.path)
+                .await
+                .context(format!("Failed to remove {}", self.path.display()))?;
+        }
+        Ok(())
+    }
+}
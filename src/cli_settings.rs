@@ -0,0 +1,75 @@
+use crate::data_file::write_atomic;
+use crate::model::ManageSort;
+use crate::theme::ThemeName;
+use crate::token_store::TokenStorage;
+use crate::uploader::UploaderConfig;
+use crate::validate::SanitizeRules;
+use anyhow::{Context, Result};
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use wayclip_core::settings::Settings;
+
+#[derive(Serialize, Deserialize, Default, Debug, Clone)]
+#[serde(default)]
+pub struct CliSettings {
+    pub manage_sort: ManageSort,
+    pub api_headers: BTreeMap<String, String>,
+    pub web_url: Option<String>,
+    pub server_timeout_secs: Option<u64>,
+    pub server_retries: u32,
+    pub theme: ThemeName,
+    pub theme_colors: BTreeMap<String, String>,
+    pub uploaders: BTreeMap<String, UploaderConfig>,
+    pub max_filename_length: Option<usize>,
+    pub aliases: BTreeMap<String, String>,
+    pub sanitize: SanitizeRules,
+    pub intro_path: Option<PathBuf>,
+    pub outro_path: Option<PathBuf>,
+    pub token_storage: TokenStorage,
+    pub default_player: String,
+}
+
+impl CliSettings {
+    pub fn path() -> PathBuf {
+        Settings::config_path()
+            .join("wayclip")
+            .join("cli-settings.json")
+    }
+
+    pub async fn load() -> Result<Self> {
+        let path = Self::path();
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let contents = tokio::fs::read(&path)
+            .await
+            .context(format!("Failed to read {}", path.display()))?;
+        let settings: Self = serde_json::from_slice(&contents)
+            .context(format!("Failed to parse {}", path.display()))?;
+        settings.header_map()?;
+        Ok(settings)
+    }
+
+    pub fn header_map(&self) -> Result<HeaderMap> {
+        let mut headers = HeaderMap::new();
+        for (name, value) in &self.api_headers {
+            let header_name = HeaderName::from_bytes(name.as_bytes())
+                .context(format!("Invalid header name '{name}' in api_headers"))?;
+            let header_value = HeaderValue::from_str(value)
+                .context(format!("Invalid value for header '{name}' in api_headers"))?;
+            headers.insert(header_name, header_value);
+        }
+        Ok(headers)
+    }
+
+    pub async fn save(&self) -> Result<()> {
+        let path = Self::path();
+        if let Some(parent) = path.parent() {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+        let contents = serde_json::to_vec_pretty(self)?;
+        write_atomic(&path, &contents).await
+    }
+}
@@ -0,0 +1,72 @@
+use crate::api_client::api_client;
+use crate::theme::Themed;
+use crate::unified_clip::{ClipLookup, find_unified_clip};
+use anyhow::{Context, Result, bail};
+use serde_json::{Value, json};
+use uuid::Uuid;
+use wayclip_core::settings::Settings;
+
+pub fn validate_clip_password(password: &str) -> Result<&str> {
+    if password.trim().is_empty() {
+        bail!("The clip password cannot be empty.");
+    }
+    Ok(password)
+}
+
+pub async fn set_clip_password(hosted_id: Uuid, password: Option<&str>) -> Result<()> {
+    let settings = Settings::load().await?;
+    let client = api_client().await?;
+    let url = format!("{}/api/clips/{}/password", settings.api_url, hosted_id);
+    let request = match password {
+        Some(password) => client.put(url).json(&json!({ "password": password })),
+        None => client.delete(url),
+    };
+    let response = request.send().await?;
+
+    let status = response.status();
+    if status == reqwest::StatusCode::UNAUTHORIZED {
+        bail!("You are not logged in. Please run `wayclip login` first.");
+    }
+    if !status.is_success() {
+        let error_body: Value = response.json().await.unwrap_or_default();
+        let error_msg = error_body["message"]
+            .as_str()
+            .unwrap_or("Unexpected server response.");
+        bail!("Failed to update the clip password: {error_msg}");
+    }
+    Ok(())
+}
+
+pub async fn handle_set_password(
+    name: &str,
+    password: Option<&str>,
+    lookup: ClipLookup,
+) -> Result<()> {
+    let password = password.map(validate_clip_password).transpose()?;
+    let clip = find_unified_clip(name, lookup).await?;
+    let hosted_id = clip
+        .hosted_id
+        .context(format!("'{}' is not a hosted clip.", clip.name))?;
+
+    println!("{}", "◌ Updating clip access...".warning());
+    set_clip_password(hosted_id, password).await?;
+    match password {
+        Some(_) => println!(
+            "{}",
+            format!(
+                "✔ '{}' is now password-protected; viewers must enter the password to watch.",
+                clip.name
+            )
+            .success()
+        ),
+        None => println!(
+            "{}",
+            format!(
+                "✔ Password removed; '{}' is viewable by anyone with the link.",
+                clip.name
+            )
+            .success()
+        ),
+    }
+    Ok(())
+}
@@ -0,0 +1,51 @@
+use crate::data_file::write_atomic;
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::PathBuf;
+use wayclip_core::settings::Settings;
+
+#[derive(Serialize, Deserialize, Default, Debug, Clone)]
+#[serde(default)]
+pub struct ClipState {
+    pub encrypted: BTreeMap<String, PathBuf>,
+    pub unplayable: BTreeSet<String>,
+}
+
+impl ClipState {
+    pub fn path() -> PathBuf {
+        Settings::config_path()
+            .join("wayclip")
+            .join("cli-state.json")
+    }
+
+    pub async fn load() -> Result<Self> {
+        let path = Self::path();
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let contents = tokio::fs::read(&path)
+            .await
+            .context(format!("Failed to read {}", path.display()))?;
+        serde_json::from_slice(&contents).context(format!("Failed to parse {}", path.display()))
+    }
+
+    pub async fn save(&self) -> Result<()> {
+        let path = Self::path();
+        if let Some(parent) = path.parent() {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+        let contents = serde_json::to_vec_pretty(self)?;
+        write_atomic(&path, &contents).await
+    }
+
+    pub fn find_encrypted(&self, name: &str) -> Option<(String, PathBuf)> {
+        let trimmed = name.trim();
+        let without_enc = trimmed.strip_suffix(".enc").unwrap_or(trimmed);
+        let stem = without_enc.strip_suffix(".mp4").unwrap_or(without_enc);
+        self.encrypted
+            .iter()
+            .find(|(clip_name, _)| clip_name.eq_ignore_ascii_case(stem))
+            .map(|(clip_name, path)| (clip_name.clone(), path.clone()))
+    }
+}
@@ -5,37 +5,31 @@ use tokio::io::AsyncWriteExt;
 use tokio::process::Command;
 
 pub async fn copy_to_clipboard(text: &str) -> Result<()> {
-    if env::var("WAYLAND_DISPLAY").is_ok() {
-        if let Ok(mut process) = Command::new("wl-copy")
+    if env::var("WAYLAND_DISPLAY").is_ok()
+        && let Ok(mut process) = Command::new("wl-copy")
             .stdin(std::process::Stdio::piped())
             .spawn()
-        {
-            if let Some(mut stdin) = process.stdin.take() {
-                if stdin.write_all(text.as_bytes()).await.is_ok() {
-                    drop(stdin);
-                    if process.wait().await.is_ok() {
-                        return Ok(());
-                    }
-                }
-            }
+        && let Some(mut stdin) = process.stdin.take()
+        && stdin.write_all(text.as_bytes()).await.is_ok()
+    {
+        drop(stdin);
+        if process.wait().await.is_ok() {
+            return Ok(());
         }
     }
 
-    if env::var("DISPLAY").is_ok() {
-        if let Ok(mut process) = Command::new("xclip")
+    if env::var("DISPLAY").is_ok()
+        && let Ok(mut process) = Command::new("xclip")
             .arg("-selection")
             .arg("clipboard")
             .stdin(std::process::Stdio::piped())
             .spawn()
-        {
-            if let Some(mut stdin) = process.stdin.take() {
-                if stdin.write_all(text.as_bytes()).await.is_ok() {
-                    drop(stdin);
-                    if process.wait().await.is_ok() {
-                        return Ok(());
-                    }
-                }
-            }
+        && let Some(mut stdin) = process.stdin.take()
+        && stdin.write_all(text.as_bytes()).await.is_ok()
+    {
+        drop(stdin);
+        if process.wait().await.is_ok() {
+            return Ok(());
         }
     }
 
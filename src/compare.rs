@@ -0,0 +1,98 @@
+use crate::probe::probe_clip;
+use crate::theme::Themed;
+use crate::unified_clip::{ClipLookup, find_unified_clip};
+use anyhow::{Context, Result, bail};
+use std::path::{Path, PathBuf};
+use tokio::process::Command;
+
+async fn local_clip_path(name: &str, lookup: ClipLookup) -> Result<PathBuf> {
+    let clip = find_unified_clip(name, lookup).await?;
+    let local_path = clip
+        .local_path
+        .context(format!("Clip '{}' not found locally.", clip.name))?;
+    Ok(PathBuf::from(local_path))
+}
+
+fn stack_filter(vertical: bool, width: u32, height: u32) -> String {
+    if vertical {
+        format!(
+            "[0:v]scale={width}:-2,setsar=1[a];[1:v]scale={width}:-2,setsar=1[b];[a][b]vstack=inputs=2:shortest=1[v]"
+        )
+    } else {
+        format!(
+            "[0:v]scale=-2:{height},setsar=1[a];[1:v]scale=-2:{height},setsar=1[b];[a][b]hstack=inputs=2:shortest=1[v]"
+        )
+    }
+}
+
+pub async fn handle_compare_render(
+    a: &str,
+    b: &str,
+    output: &Path,
+    vertical: bool,
+    lookup: ClipLookup,
+) -> Result<()> {
+    if output.exists() {
+        bail!("'{}' already exists.", output.display());
+    }
+    let path_a = local_clip_path(a, lookup).await?;
+    let path_b = local_clip_path(b, lookup).await?;
+
+    let info = probe_clip(&path_a)
+        .await
+        .context(format!("Clip '{a}' is not playable"))?;
+    probe_clip(&path_b)
+        .await
+        .context(format!("Clip '{b}' is not playable"))?;
+    let (Some(width), Some(height)) = (info.width, info.height) else {
+        bail!("Could not determine the dimensions of '{a}'.");
+    };
+    let width = width - width % 2;
+    let height = height - height % 2;
+
+    println!(
+        "{}",
+        format!(
+            "◌ Rendering '{a}' and '{b}' {}...",
+            if vertical { "stacked" } else { "side by side" }
+        )
+        .warning()
+    );
+    let output_result = Command::new("ffmpeg")
+        .arg("-n")
+        .arg("-i")
+        .arg(&path_a)
+        .arg("-i")
+        .arg(&path_b)
+        .arg("-filter_complex")
+        .arg(stack_filter(vertical, width, height))
+        .arg("-map")
+        .arg("[v]")
+        .arg("-map")
+        .arg("0:a?")
+        .arg("-c:v")
+        .arg("libx264")
+        .arg("-crf")
+        .arg("18")
+        .arg("-c:a")
+        .arg("aac")
+        .arg("-shortest")
+        .arg(output)
+        .output()
+        .await
+        .context("Failed to execute ffmpeg. Is it installed and in your PATH?")?;
+
+    if !output_result.status.success() {
+        let stderr = String::from_utf8_lossy(&output_result.stderr);
+        bail!(
+            "ffmpeg failed with status: {}\n{}",
+            output_result.status,
+            stderr
+        );
+    }
+    println!(
+        "{}",
+        format!("✔ Comparison saved to {}", output.display()).success()
+    );
+    Ok(())
+}
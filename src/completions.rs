@@ -0,0 +1,9 @@
+use crate::model::Cli;
+use clap::CommandFactory;
+use clap_complete::Shell;
+
+pub fn handle_completions(shell: Shell) {
+    let mut command = Cli::command();
+    let bin_name = command.get_name().to_string();
+    clap_complete::generate(shell, &mut command, bin_name, &mut std::io::stdout());
+}
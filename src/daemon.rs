@@ -0,0 +1,209 @@
+use crate::theme::Themed;
+use anyhow::{Context, Result, anyhow, bail};
+use colored::*;
+use inquire::Confirm;
+use std::env;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::net::UnixStream;
+use tokio::process::Command;
+use wayclip_core::control::DaemonManager;
+use wayclip_core::settings::Settings;
+
+const SOCKET_TIMEOUT: Duration = Duration::from_secs(2);
+
+enum SocketReply {
+    Answered,
+    Closed,
+    TimedOut,
+}
+
+enum DaemonHealth {
+    Responsive,
+    Unresponsive(String),
+    Unknown(String),
+}
+
+pub fn runtime_dir() -> PathBuf {
+    env::var_os("XDG_RUNTIME_DIR")
+        .map(PathBuf::from)
+        .unwrap_or_else(env::temp_dir)
+}
+
+async fn control_socket_path() -> Result<PathBuf> {
+    Ok(PathBuf::from(Settings::load().await?.daemon_socket_path))
+}
+
+fn start_lock_path() -> PathBuf {
+    runtime_dir().join("wayclip-start.lock")
+}
+
+async fn daemon_pids() -> Vec<u32> {
+    let Ok(output) = Command::new("pgrep")
+        .arg("-x")
+        .arg("wayclip-daemon")
+        .output()
+        .await
+    else {
+        return Vec::new();
+    };
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| line.trim().parse().ok())
+        .collect()
+}
+
+async fn is_zombie(pid: u32) -> bool {
+    let Ok(status) = tokio::fs::read_to_string(format!("/proc/{pid}/status")).await else {
+        return true;
+    };
+    status
+        .lines()
+        .find_map(|line| line.strip_prefix("State:"))
+        .map(|state| matches!(state.trim().chars().next(), Some('Z') | Some('X')))
+        .unwrap_or(false)
+}
+
+async fn probe_daemon() -> DaemonHealth {
+    let pids = daemon_pids().await;
+    if pids.is_empty() {
+        return DaemonHealth::Unresponsive(
+            "no wayclip-daemon process exists (stale pidfile)".to_string(),
+        );
+    }
+
+    let mut all_zombies = true;
+    for pid in &pids {
+        if !is_zombie(*pid).await {
+            all_zombies = false;
+        }
+    }
+    if all_zombies {
+        return DaemonHealth::Unresponsive("the daemon process is a zombie".to_string());
+    }
+
+    let Ok(socket_path) = control_socket_path().await else {
+        return DaemonHealth::Unknown("the daemon socket path could not be read".to_string());
+    };
+    if !socket_path.exists() {
+        return DaemonHealth::Unknown(format!(
+            "the control socket {} was not found",
+            socket_path.display()
+        ));
+    }
+
+    match probe_control_socket(&socket_path).await {
+        Ok(SocketReply::Answered | SocketReply::Closed) => DaemonHealth::Responsive,
+        Ok(SocketReply::TimedOut) => DaemonHealth::Unresponsive(format!(
+            "the daemon did not close the control connection within {}s",
+            SOCKET_TIMEOUT.as_secs()
+        )),
+        Err(e) => DaemonHealth::Unresponsive(format!("{e:#}")),
+    }
+}
+
+pub async fn handle_daemon_status(manager: &DaemonManager) -> Result<()> {
+    manager.status().await?;
+    if !manager.is_running().await {
+        return Ok(());
+    }
+
+    match probe_daemon().await {
+        DaemonHealth::Responsive => {
+            println!("{}", "✔ Daemon is responsive.".success());
+        }
+        DaemonHealth::Unresponsive(reason) => {
+            println!(
+                "{}",
+                "⚠ Daemon is running but unresponsive (possibly hung)."
+                    .warning()
+                    .bold()
+            );
+            println!("  Reason: {reason}");
+            let restart = Confirm::new("Restart the daemon now?")
+                .with_default(true)
+                .prompt()?;
+            if restart {
+                manager.restart().await?;
+            }
+        }
+        DaemonHealth::Unknown(reason) => {
+            println!(
+                "{}",
+                format!("○ Daemon health is unknown: {reason}.").warning()
+            );
+        }
+    }
+    Ok(())
+}
+
+async fn probe_control_socket(socket_path: &Path) -> Result<SocketReply> {
+    let mut stream = tokio::time::timeout(SOCKET_TIMEOUT, UnixStream::connect(socket_path))
+        .await
+        .map_err(|_| {
+            anyhow!(
+                "the control socket did not accept a connection within {}s",
+                SOCKET_TIMEOUT.as_secs()
+            )
+        })?
+        .context(format!(
+            "control socket {} refused the connection",
+            socket_path.display()
+        ))?;
+    stream.shutdown().await?;
+
+    let mut response = String::new();
+    let read = tokio::time::timeout(
+        SOCKET_TIMEOUT,
+        BufReader::new(stream).read_line(&mut response),
+    )
+    .await;
+    match read {
+        Ok(Ok(0)) => Ok(SocketReply::Closed),
+        Ok(Ok(_)) => Ok(SocketReply::Answered),
+        Err(_) => Ok(SocketReply::TimedOut),
+        Ok(Err(e)) => Err(e).context(format!("Failed to talk to {}", socket_path.display())),
+    }
+}
+
+pub async fn handle_daemon_reload(manager: &DaemonManager) -> Result<()> {
+    if !manager.is_running().await {
+        bail!("The daemon is not running. Start it with `wayclip daemon start`.");
+    }
+
+    println!("{}", "◌ Re-reading settings...".warning());
+    Settings::load()
+        .await
+        .context("The settings could not be read, so the daemon was left running")?;
+    println!(
+        "{}",
+        "○ The daemon has no live reload; restarting it to apply the settings (buffered footage will be lost).".warning()
+    );
+    manager.restart().await
+}
+
+pub async fn handle_daemon_start(manager: &DaemonManager) -> Result<()> {
+    let lock_path = start_lock_path();
+    let lock_file = std::fs::OpenOptions::new()
+        .create(true)
+        .truncate(false)
+        .write(true)
+        .open(&lock_path)
+        .context(format!("Failed to open {}", lock_path.display()))?;
+    let lock_file = tokio::task::spawn_blocking(move || lock_file.lock().map(|_| lock_file))
+        .await?
+        .context("Failed to acquire the daemon start lock")?;
+
+    let result = async {
+        if manager.is_running().await {
+            println!("{}", "○ Daemon is already running.".warning());
+        } else {
+            manager.start().await?;
+        }
+        Ok::<(), anyhow::Error>(())
+    }
+    .await;
+    drop(lock_file);
+    result
+}
@@ -0,0 +1,245 @@
+use crate::theme::Themed;
+use anyhow::{Context, Result, bail};
+use colored::*;
+use inquire::Confirm;
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use tokio::io::AsyncWriteExt;
+use uuid::Uuid;
+use wayclip_core::settings::Settings;
+use wayclip_core::{ClipJsonData, Tag};
+
+const DEFAULT_TAG_COLOR: &str = "#808080";
+
+pub fn data_file_path() -> PathBuf {
+    Settings::config_path().join("wayclip").join("data.json")
+}
+
+fn backup_file_path() -> PathBuf {
+    data_file_path().with_extension("json.bak")
+}
+
+pub async fn write_atomic(path: &Path, contents: &[u8]) -> Result<()> {
+    let temp_path = path.with_extension("tmp");
+    let mut file = tokio::fs::File::create(&temp_path)
+        .await
+        .context(format!("Failed to create {}", temp_path.display()))?;
+    file.write_all(contents).await?;
+    file.sync_all().await?;
+    drop(file);
+
+    tokio::fs::rename(&temp_path, path)
+        .await
+        .context(format!("Failed to replace {}", path.display()))?;
+    Ok(())
+}
+
+async fn read_valid(path: &Path) -> Option<Vec<u8>> {
+    let contents = tokio::fs::read(path).await.ok()?;
+    serde_json::from_slice::<Value>(&contents).ok()?;
+    Some(contents)
+}
+
+pub async fn ensure_data_file_healthy(interactive: bool) -> Result<()> {
+    let data_path = data_file_path();
+    if !data_path.exists() {
+        return Ok(());
+    }
+
+    let backup_path = backup_file_path();
+    if let Some(contents) = read_valid(&data_path).await {
+        if tokio::fs::read(&backup_path).await.ok().as_deref() == Some(&contents[..]) {
+            return Ok(());
+        }
+        return write_atomic(&backup_path, &contents)
+            .await
+            .context("Failed to back up the local data file");
+    }
+
+    eprintln!(
+        "{}",
+        format!(
+            "⚠ The local data file at {} is corrupt or incomplete.",
+            data_path.display()
+        )
+        .warning()
+        .bold()
+    );
+
+    let Some(backup) = read_valid(&backup_path).await else {
+        bail!(
+            "No usable backup was found at {}. Move the corrupt data file aside to start fresh.",
+            backup_path.display()
+        );
+    };
+
+    if !interactive {
+        bail!(
+            "Not restoring without a terminal. Copy {} over {} to restore the last known-good backup, or rerun the command in a terminal to be prompted.",
+            backup_path.display(),
+            data_path.display()
+        );
+    }
+
+    let restore = Confirm::new("Restore it from the last known-good backup?")
+        .with_help_message("Changes made after the backup was taken will be lost.")
+        .with_default(true)
+        .prompt()?;
+    if !restore {
+        bail!("The local data file must be repaired before continuing.");
+    }
+
+    write_atomic(&data_path, &backup)
+        .await
+        .context("Failed to restore the local data file")?;
+    println!("{}", "✔ Local data file restored from backup.".success());
+    Ok(())
+}
+
+pub async fn read_clip_entries() -> HashMap<String, ClipJsonData> {
+    let Some(contents) = read_valid(&data_file_path()).await else {
+        return HashMap::new();
+    };
+    let Ok(data) = serde_json::from_slice::<Map<String, Value>>(&contents) else {
+        return HashMap::new();
+    };
+    data.into_iter()
+        .filter_map(|(name, entry)| Some((name, serde_json::from_value(entry).ok()?)))
+        .collect()
+}
+
+pub fn new_tag(name: &str) -> Tag {
+    Tag {
+        name: name.to_string(),
+        color: DEFAULT_TAG_COLOR.to_string(),
+    }
+}
+
+fn push_tags(clip: &mut ClipJsonData, tags: &[String]) {
+    for tag in tags {
+        if !clip.tags.iter().any(|t| t.name == *tag) {
+            clip.tags.push(new_tag(tag));
+        }
+    }
+}
+
+async fn update_clip_entry(
+    full_filename: &str,
+    create: bool,
+    update: impl FnOnce(&mut ClipJsonData, &mut Map<String, Value>),
+) -> Result<bool> {
+    let data_path = data_file_path();
+    let mut data: Map<String, Value> = match read_valid(&data_path).await {
+        Some(contents) => {
+            serde_json::from_slice(&contents).context("The local data file is not a JSON object")?
+        }
+        None if create && !data_path.exists() => Map::new(),
+        None => return Ok(false),
+    };
+    if create && !data.contains_key(full_filename) {
+        data.insert(
+            full_filename.to_string(),
+            serde_json::to_value(ClipJsonData::default())?,
+        );
+    }
+    let Some(entry) = data.get_mut(full_filename).and_then(Value::as_object_mut) else {
+        return Ok(false);
+    };
+    let mut clip: ClipJsonData = serde_json::from_value(Value::Object(entry.clone())).context(
+        format!("The data file entry for '{full_filename}' is malformed"),
+    )?;
+    update(&mut clip, entry);
+    if let Value::Object(fields) = serde_json::to_value(&clip)? {
+        entry.extend(fields);
+    }
+
+    write_data(data).await?;
+    Ok(true)
+}
+
+async fn write_data(data: Map<String, Value>) -> Result<()> {
+    let data_path = data_file_path();
+    let data = Value::Object(data);
+    serde_json::from_value::<HashMap<String, ClipJsonData>>(data.clone())
+        .context("Refusing to write a data file that wayclip could not read back")?;
+    if let Some(parent) = data_path.parent() {
+        tokio::fs::create_dir_all(parent).await?;
+    }
+    write_atomic(&data_path, &serde_json::to_vec_pretty(&data)?).await
+}
+
+pub async fn register_clip_entries(entries: Vec<(String, ClipJsonData)>) -> Result<()> {
+    let data_path = data_file_path();
+    let mut data: Map<String, Value> = match read_valid(&data_path).await {
+        Some(contents) => {
+            serde_json::from_slice(&contents).context("The local data file is not a JSON object")?
+        }
+        None if !data_path.exists() => Map::new(),
+        None => return Ok(()),
+    };
+    let mut added = false;
+    for (full_filename, clip) in entries {
+        if !data.contains_key(&full_filename) {
+            data.insert(full_filename, serde_json::to_value(clip)?);
+            added = true;
+        }
+    }
+    if added {
+        write_data(data).await?;
+    }
+    Ok(())
+}
+
+pub async fn move_clip_entry(old_filename: &str, new_filename: &str) -> Result<bool> {
+    let Some(contents) = read_valid(&data_file_path()).await else {
+        return Ok(false);
+    };
+    let mut data: Map<String, Value> =
+        serde_json::from_slice(&contents).context("The local data file is not a JSON object")?;
+    let Some(entry) = data.remove(old_filename) else {
+        return Ok(false);
+    };
+    data.insert(new_filename.to_string(), entry);
+    write_data(data).await?;
+    Ok(true)
+}
+
+pub async fn update_clip_metadata(full_filename: &str, length: f64, size: u64) -> Result<bool> {
+    update_clip_entry(full_filename, false, |_, entry| {
+        entry.insert("length".to_string(), Value::from(length));
+        entry.insert("size".to_string(), Value::from(size));
+    })
+    .await
+}
+
+pub async fn add_clip_tags(full_filename: &str, tags: &[String]) -> Result<bool> {
+    update_clip_entry(full_filename, false, |clip, _| push_tags(clip, tags)).await
+}
+
+pub async fn remove_clip_tags(full_filename: &str, tags: &[String]) -> Result<bool> {
+    update_clip_entry(full_filename, false, |clip, _| {
+        clip.tags.retain(|t| !tags.contains(&t.name));
+    })
+    .await
+}
+
+pub async fn restore_clip_entry(
+    full_filename: &str,
+    liked: bool,
+    tags: &[String],
+    hosted_id: Option<Uuid>,
+) -> Result<()> {
+    let restored = update_clip_entry(full_filename, true, |clip, _| {
+        clip.liked |= liked;
+        push_tags(clip, tags);
+        if clip.hosted_id.is_none() {
+            clip.hosted_id = hosted_id;
+        }
+    })
+    .await?;
+    if !restored {
+        bail!("The local data file could not be read");
+    }
+    Ok(())
+}
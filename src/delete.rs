@@ -1,41 +1,99 @@
-use crate::unified_clip::find_unified_clip;
-use anyhow::Result;
-use colored::*;
+use crate::api_client::delete_hosted_clip;
+use crate::profile_cache;
+use crate::theme::Themed;
+use crate::trash::move_to_trash;
+use crate::unified_clip::{ClipLookup, find_unified_clip};
+use anyhow::{Result, bail};
 use inquire::Confirm;
-use wayclip_core::{api, delete_file};
+use std::path::Path;
+use uuid::Uuid;
+use wayclip_core::delete_file;
 
-pub async fn handle_delete(name: &str) -> Result<()> {
-    let clip_to_delete = find_unified_clip(name).await?;
+async fn delete_server_copy(hosted_id: Uuid) -> Result<()> {
+    delete_hosted_clip(hosted_id).await?;
+    profile_cache::invalidate().await;
+    println!("{}", "✔ Server copy deleted.".success());
+    Ok(())
+}
+
+async fn delete_local_copy(name: &str, local_path: &str, permanent: bool) -> Result<()> {
+    if permanent {
+        delete_file(local_path)
+            .await
+            .map_err(|e| anyhow::anyhow!(e))?;
+        println!("{}", "✔ Local file deleted.".success());
+    } else {
+        move_to_trash(Path::new(local_path)).await?;
+        println!(
+            "{}",
+            format!(
+                "✔ Local file moved to the trash. Restore it with `wayclip trash restore {name}`."
+            )
+            .success()
+        );
+    }
+    Ok(())
+}
 
-    println!("○ Preparing to delete '{}'.", name.cyan());
+pub async fn handle_delete(
+    name: &str,
+    server_only: bool,
+    local_only: bool,
+    yes: bool,
+    permanent: bool,
+    lookup: ClipLookup,
+) -> Result<()> {
+    let clip_to_delete = find_unified_clip(name, lookup).await?;
+
+    println!("○ Preparing to delete '{}'.", name.accent());
+
+    if server_only || local_only || yes {
+        if server_only && clip_to_delete.hosted_id.is_none() {
+            bail!("'{}' is not hosted on the server.", clip_to_delete.name);
+        }
+        if local_only && clip_to_delete.local_path.is_none() {
+            bail!("'{}' does not exist locally.", clip_to_delete.name);
+        }
+        if clip_to_delete.local_path.is_none() && clip_to_delete.hosted_id.is_none() {
+            bail!(
+                "'{}' has no local or hosted file to delete.",
+                clip_to_delete.name
+            );
+        }
+        if !local_only && let Some(hosted_id) = clip_to_delete.hosted_id {
+            delete_server_copy(hosted_id).await?;
+        }
+        if !server_only && let Some(local_path_str) = &clip_to_delete.local_path {
+            delete_local_copy(&clip_to_delete.name, local_path_str, permanent).await?;
+        }
+        return Ok(());
+    }
 
     if let Some(hosted_id) = clip_to_delete.hosted_id {
         let confirmed = Confirm::new("This clip is hosted on the server. Delete the server copy?")
             .with_default(true)
             .prompt()?;
         if confirmed {
-            let client = api::get_api_client().await?;
-            api::delete_clip(&client, hosted_id).await?;
-            println!("{}", "✔ Server copy deleted.".green());
+            delete_server_copy(hosted_id).await?;
         }
     }
 
     if let Some(local_path_str) = &clip_to_delete.local_path {
-        let confirmed_local = Confirm::new("Delete the local file? This cannot be undone.")
-            .with_default(false)
-            .prompt()?;
+        let prompt = if permanent {
+            "Delete the local file? This cannot be undone."
+        } else {
+            "Move the local file to the trash?"
+        };
+        let confirmed_local = Confirm::new(prompt).with_default(false).prompt()?;
         if confirmed_local {
-            delete_file(local_path_str)
-                .await
-                .map_err(|e| anyhow::anyhow!(e))?;
-            println!("{}", "✔ Local file deleted.".green());
+            delete_local_copy(&clip_to_delete.name, local_path_str, permanent).await?;
         }
     }
 
     if clip_to_delete.local_path.is_none() && clip_to_delete.hosted_id.is_none() {
         println!(
             "{}",
-            "○ Clip metadata found, but no local or hosted file to delete.".yellow()
+            "○ Clip metadata found, but no local or hosted file to delete.".warning()
         );
     }
 
@@ -0,0 +1,219 @@
+use crate::theme::Themed;
+use crate::view::{player_binary, resolve_player};
+use anyhow::{Result, bail};
+use colored::*;
+use serde::Serialize;
+use std::path::PathBuf;
+use std::time::Duration;
+use tokio::process::Command;
+use wayclip_core::control::DaemonManager;
+use which::which;
+
+const VERSION_TIMEOUT: Duration = Duration::from_secs(3);
+
+struct Dependency {
+    name: &'static str,
+    version_flag: &'static str,
+    required: bool,
+    purpose: &'static str,
+}
+
+const DEPENDENCIES: [Dependency; 8] = [
+    Dependency {
+        name: "wayclip-daemon",
+        version_flag: "--version",
+        required: true,
+        purpose: "background recorder",
+    },
+    Dependency {
+        name: "ffmpeg",
+        version_flag: "-version",
+        required: true,
+        purpose: "editing and exporting clips",
+    },
+    Dependency {
+        name: "ffprobe",
+        version_flag: "-version",
+        required: true,
+        purpose: "reading clip metadata",
+    },
+    Dependency {
+        name: "pw-dump",
+        version_flag: "--version",
+        required: false,
+        purpose: "listing audio devices",
+    },
+    Dependency {
+        name: "wpctl",
+        version_flag: "--version",
+        required: false,
+        purpose: "detecting default audio devices",
+    },
+    Dependency {
+        name: "wl-copy",
+        version_flag: "--version",
+        required: false,
+        purpose: "clipboard on Wayland",
+    },
+    Dependency {
+        name: "xclip",
+        version_flag: "-version",
+        required: false,
+        purpose: "clipboard on X11",
+    },
+    Dependency {
+        name: "gpg",
+        version_flag: "--version",
+        required: false,
+        purpose: "clip encryption",
+    },
+];
+
+#[derive(Serialize)]
+struct DependencyReport {
+    name: String,
+    required: bool,
+    purpose: &'static str,
+    path: Option<String>,
+    version: Option<String>,
+    status: &'static str,
+}
+
+#[derive(Serialize)]
+struct DoctorReport {
+    healthy: bool,
+    daemon_running: bool,
+    dependencies: Vec<DependencyReport>,
+}
+
+async fn read_version(program: &str, flag: &str) -> Option<String> {
+    let output = tokio::time::timeout(VERSION_TIMEOUT, Command::new(program).arg(flag).output())
+        .await
+        .ok()?
+        .ok()?;
+    let text = if output.stdout.is_empty() {
+        output.stderr
+    } else {
+        output.stdout
+    };
+    String::from_utf8_lossy(&text)
+        .lines()
+        .map(str::trim)
+        .find(|line| !line.is_empty())
+        .map(String::from)
+}
+
+async fn check_dependency(dependency: &Dependency) -> DependencyReport {
+    let path = which(dependency.name).ok();
+    let version = match &path {
+        Some(_) => read_version(dependency.name, dependency.version_flag).await,
+        None => None,
+    };
+    report_dependency(
+        dependency.name,
+        dependency.required,
+        dependency.purpose,
+        path,
+        version,
+    )
+}
+
+async fn check_player(player: Option<&str>) -> Result<DependencyReport> {
+    let player_name = resolve_player(player).await?;
+    let player_cmd = player_name.split_whitespace().next().unwrap_or_default();
+    let path = which(player_cmd).ok();
+    let version_flag = match player_binary(player_cmd) {
+        "ffplay" | "mplayer" => "-version",
+        _ => "--version",
+    };
+    let version = match &path {
+        Some(_) => read_version(player_cmd, version_flag).await,
+        None => None,
+    };
+    Ok(report_dependency(
+        player_cmd,
+        false,
+        "media player for `wayclip view`",
+        path,
+        version,
+    ))
+}
+
+fn report_dependency(
+    name: &str,
+    required: bool,
+    purpose: &'static str,
+    path: Option<PathBuf>,
+    version: Option<String>,
+) -> DependencyReport {
+    let status = match (&path, required) {
+        (Some(_), _) => "ok",
+        (None, true) => "missing",
+        (None, false) => "optional-missing",
+    };
+    DependencyReport {
+        name: name.to_string(),
+        required,
+        purpose,
+        path: path.map(|p| p.display().to_string()),
+        version,
+        status,
+    }
+}
+
+pub async fn handle_doctor(json: bool, player: Option<&str>) -> Result<()> {
+    let mut dependencies = Vec::new();
+    for dependency in &DEPENDENCIES {
+        dependencies.push(check_dependency(dependency).await);
+    }
+    dependencies.push(check_player(player).await?);
+    let daemon_running = DaemonManager::new().is_running().await;
+    let healthy = dependencies.iter().all(|d| d.status != "missing");
+
+    let report = DoctorReport {
+        healthy,
+        daemon_running,
+        dependencies,
+    };
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&report)?);
+    } else {
+        println!("{}", "┌─ Wayclip Environment Check ─────".bold());
+        for dep in &report.dependencies {
+            let marker = match dep.status {
+                "ok" => "✔".success(),
+                "missing" => "✗".error(),
+                _ => "○".warning(),
+            };
+            let detail = match (&dep.version, &dep.path) {
+                (Some(version), _) => version.dimmed().to_string(),
+                (None, Some(path)) => path.dimmed().to_string(),
+                (None, None) if dep.required => "not found (required)".error().to_string(),
+                (None, None) => "not found (optional)".warning().to_string(),
+            };
+            println!(
+                "│ {} {} — {} {}",
+                marker,
+                dep.name.accent(),
+                dep.purpose,
+                detail
+            );
+        }
+        println!("├─ Daemon ──────────────────────");
+        if report.daemon_running {
+            println!("│ {} Daemon is running", "✔".success());
+        } else {
+            println!(
+                "│ {} Daemon is not running (start it with `wayclip daemon start`)",
+                "○".warning()
+            );
+        }
+        println!("└─────────────────────────────────");
+    }
+
+    if !report.healthy {
+        bail!("Some required dependencies are missing.");
+    }
+    Ok(())
+}
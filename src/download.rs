@@ -0,0 +1,276 @@
+use crate::api_client::api_client;
+use crate::checkpoint::Checkpoint;
+use crate::theme::Themed;
+use crate::unified_clip::{ClipLookup, clips_directory, find_unified_clip, gather_unified_clips};
+use anyhow::{Context, Result, bail};
+use colored::*;
+use indicatif::{MultiProgress, ProgressBar, ProgressStyle};
+use inquire::Confirm;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use tokio::io::AsyncWriteExt;
+use tokio::sync::Semaphore;
+use tokio::task::JoinSet;
+use uuid::Uuid;
+use wayclip_core::settings::Settings;
+use wayclip_core::update_hosted_id;
+
+struct DownloadJob {
+    name: String,
+    hosted_id: Uuid,
+    destination: PathBuf,
+}
+
+pub fn bytes_style() -> Result<ProgressStyle> {
+    Ok(ProgressStyle::with_template(
+        "{msg:20!} [{bar:30.cyan/blue}] {bytes}/{total_bytes} ({bytes_per_sec}, {eta})",
+    )?
+    .progress_chars("█▓░"))
+}
+
+async fn download_to(
+    client: &reqwest::Client,
+    url: &str,
+    destination: &Path,
+    progress: &ProgressBar,
+) -> Result<()> {
+    let mut response = client.get(url).send().await?;
+    if !response.status().is_success() {
+        bail!("Server responded with {}", response.status());
+    }
+    if let Some(total) = response.content_length() {
+        progress.set_length(total);
+    }
+
+    let partial_path = destination.with_extension("part");
+    let result = async {
+        let mut file = tokio::fs::File::create(&partial_path)
+            .await
+            .context(format!("Failed to create {}", partial_path.display()))?;
+        while let Some(chunk) = response.chunk().await? {
+            file.write_all(&chunk).await?;
+            progress.inc(chunk.len() as u64);
+        }
+        file.sync_all().await?;
+        Ok::<(), anyhow::Error>(())
+    }
+    .await;
+
+    if let Err(e) = result {
+        let _ = tokio::fs::remove_file(&partial_path).await;
+        return Err(e);
+    }
+    tokio::fs::rename(&partial_path, destination)
+        .await
+        .context(format!("Failed to save {}", destination.display()))?;
+    Ok(())
+}
+
+async fn link_download(job: &DownloadJob) -> Result<()> {
+    let full_filename = job
+        .destination
+        .file_name()
+        .and_then(|s| s.to_str())
+        .context("Invalid filename")?;
+    update_hosted_id(full_filename, job.hosted_id)
+        .await
+        .context("Failed to save hosted ID to local data file")?;
+    Ok(())
+}
+
+async fn run_download(
+    client: &reqwest::Client,
+    api_url: &str,
+    job: &DownloadJob,
+    progress: &ProgressBar,
+) -> Result<()> {
+    let url = format!("{api_url}/clip/{}/download", job.hosted_id);
+    download_to(client, &url, &job.destination, progress).await?;
+    link_download(job).await
+}
+
+pub async fn handle_download(name: &str, force: bool, lookup: ClipLookup) -> Result<()> {
+    let clip = find_unified_clip(name, lookup).await?;
+    let hosted_id = clip
+        .hosted_id
+        .context(format!("'{}' is not a hosted clip.", clip.name))?;
+
+    let settings = Settings::load().await?;
+    let clips_dir = clips_directory(&settings);
+    tokio::fs::create_dir_all(&clips_dir).await?;
+    let destination = clips_dir.join(format!("{}.mp4", clip.name));
+
+    if destination.exists() && !force {
+        let confirmed = Confirm::new(&format!(
+            "'{}' already exists locally. Overwrite it?",
+            destination.display()
+        ))
+        .with_default(false)
+        .prompt()?;
+        if !confirmed {
+            println!("{}", "○ Download cancelled.".warning());
+            return Ok(());
+        }
+    }
+
+    let client = api_client().await?;
+    let progress = ProgressBar::new(0);
+    progress.set_style(bytes_style()?);
+    progress.set_message(clip.name.clone());
+
+    let job = DownloadJob {
+        name: clip.name,
+        hosted_id,
+        destination,
+    };
+    run_download(&client, &settings.api_url, &job, &progress).await?;
+    progress.finish_and_clear();
+
+    println!(
+        "{}",
+        format!(
+            "✔ Downloaded '{}' to {}",
+            job.name,
+            job.destination.display()
+        )
+        .success()
+    );
+    Ok(())
+}
+
+pub async fn handle_download_all(force: bool, jobs: usize, resume: bool) -> Result<()> {
+    let mut checkpoint = Checkpoint::open("download-all", resume).await?;
+    let settings = Settings::load().await?;
+    let clips_dir = clips_directory(&settings);
+    tokio::fs::create_dir_all(&clips_dir).await?;
+
+    println!("{}", "◌ Loading hosted clips...".warning());
+    let mut download_jobs = Vec::new();
+    let mut skipped = 0;
+    for clip in gather_unified_clips().await? {
+        let Some(hosted_id) = clip.hosted_id else {
+            continue;
+        };
+        if checkpoint.is_done(&clip.name) {
+            skipped += 1;
+            continue;
+        }
+        let destination = clips_dir.join(format!("{}.mp4", clip.name));
+        if !force && (clip.local_path.is_some() || destination.exists()) {
+            skipped += 1;
+            continue;
+        }
+        download_jobs.push(DownloadJob {
+            name: clip.name,
+            hosted_id,
+            destination,
+        });
+    }
+
+    if download_jobs.is_empty() {
+        println!(
+            "{}",
+            format!("○ Nothing to download ({skipped} already present locally).").warning()
+        );
+        checkpoint.finish().await?;
+        return Ok(());
+    }
+
+    let cancelled = Arc::new(AtomicBool::new(false));
+    {
+        let cancelled = cancelled.clone();
+        tokio::spawn(async move {
+            if tokio::signal::ctrl_c().await.is_ok() {
+                cancelled.store(true, Ordering::SeqCst);
+            }
+        });
+    }
+
+    let client = api_client().await?;
+    let multi = MultiProgress::new();
+    let total = download_jobs.len();
+    let overall = multi.add(ProgressBar::new(total as u64));
+    overall.set_style(
+        ProgressStyle::with_template("downloading {pos}/{len} [{bar:30.green/white}] {msg}")?
+            .progress_chars("█▓░"),
+    );
+
+    let semaphore = Arc::new(Semaphore::new(jobs.max(1)));
+    let mut tasks = JoinSet::new();
+    for job in download_jobs {
+        let semaphore = semaphore.clone();
+        let cancelled = cancelled.clone();
+        let client = client.clone();
+        let api_url = settings.api_url.clone();
+        let multi = multi.clone();
+        let style = bytes_style()?;
+        tasks.spawn(async move {
+            let _permit = semaphore.acquire_owned().await;
+            if cancelled.load(Ordering::SeqCst) {
+                return (job, None);
+            }
+            let progress = multi.add(ProgressBar::new(0));
+            progress.set_style(style);
+            progress.set_message(job.name.clone());
+            let result = run_download(&client, &api_url, &job, &progress).await;
+            progress.finish_and_clear();
+            multi.remove(&progress);
+            (job, Some(result))
+        });
+    }
+
+    let mut completed = Vec::new();
+    let mut failed = 0;
+    let mut not_started = 0;
+    let mut cancel_notified = false;
+    while let Some(joined) = tasks.join_next().await {
+        let (job, result) = joined?;
+        if cancelled.load(Ordering::SeqCst) && !cancel_notified {
+            cancel_notified = true;
+            overall.set_message("stopping after current downloads...");
+        }
+        match result {
+            Some(Ok(_)) => {
+                checkpoint.mark_done(&job.name).await?;
+                completed.push(job.name);
+            }
+            Some(Err(e)) => {
+                failed += 1;
+                overall.println(format!("{} {}: {e:#}", "✗".error(), job.name));
+            }
+            None => not_started += 1,
+        }
+        overall.inc(1);
+    }
+    overall.finish_and_clear();
+
+    for name in &completed {
+        println!("{} {}", "✔".success(), name);
+    }
+    if not_started > 0 {
+        println!(
+            "{}",
+            format!(
+                "○ Interrupted: {} downloaded, {not_started} not started. Re-run with --resume to continue.",
+                completed.len()
+            )
+            .warning()
+        );
+        return Ok(());
+    }
+    if failed > 0 {
+        bail!("{failed} of {total} downloads failed. Re-run with --resume to retry only those.");
+    }
+    checkpoint.finish().await?;
+    println!(
+        "{}",
+        format!(
+            "✔ Downloaded {} clips ({skipped} skipped as already local).",
+            completed.len()
+        )
+        .success()
+        .bold()
+    );
+    Ok(())
+}
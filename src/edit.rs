@@ -1,94 +1,656 @@
-use crate::unified_clip::find_unified_clip;
-use crate::validate::{sanitize_and_validate_filename_stem, validate_ffmpeg_time};
+use crate::bumper::{Bumpers, apply_bumpers};
+use crate::checkpoint::Checkpoint;
+use crate::clip_state::ClipState;
+use crate::encrypt::{
+    DecryptedClip, decrypt_for_access, encrypt_file, private_temp_path, shred_file,
+};
+use crate::interactive_trim::pick_trim_points;
+use crate::model::EncodePreset;
+use crate::probe::{ProbeInfo, probe_chapters, probe_clip};
+use crate::theme::Themed;
+use crate::unified_clip::{
+    ClipLookup, find_unified_clip, find_unified_clips_matching, is_glob_pattern,
+};
+use crate::validate::{
+    FilenameRules, ffmpeg_time_to_seconds, fit_filename_stem, sanitize_and_validate_filename_stem,
+    validate_ffmpeg_time,
+};
 use anyhow::{Context, Result, bail};
 use colored::*;
+use indicatif::{ProgressBar, ProgressStyle};
 use inquire::{Confirm, Select, Text};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use std::time::Duration;
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, BufReader};
 use tokio::process::Command;
+use wayclip_core::models::UnifiedClipData;
+
+const HDR_CONTAINERS: [&str; 6] = ["mp4", "mkv", "mov", "m4v", "ts", "webm"];
+const MIC_GATE_FILTER: &str = "agate=threshold=0.015:ratio=9000:attack=5:release=300";
+const COPY_UNSAFE_CODECS: [&str; 6] = ["rawvideo", "huffyuv", "ffv1", "utvideo", "qtrle", "png"];
+const WEBM_VIDEO_CODECS: [&str; 3] = ["vp8", "vp9", "av1"];
+
+#[derive(Clone, Copy)]
+enum VideoCodec {
+    X264,
+    X265,
+    Vp9,
+}
+
+struct OutputCodecs {
+    video: VideoCodec,
+    hdr_video: VideoCodec,
+    audio: &'static str,
+    hevc_tag: bool,
+    copyable_video: Option<&'static [&'static str]>,
+}
+
+fn output_codecs(ext: &str) -> Result<OutputCodecs> {
+    let codecs = match ext.to_lowercase().as_str() {
+        "webm" => OutputCodecs {
+            video: VideoCodec::Vp9,
+            hdr_video: VideoCodec::Vp9,
+            audio: "libopus",
+            hevc_tag: false,
+            copyable_video: Some(&WEBM_VIDEO_CODECS),
+        },
+        "mp4" | "m4v" | "mov" => OutputCodecs {
+            video: VideoCodec::X264,
+            hdr_video: VideoCodec::X265,
+            audio: "aac",
+            hevc_tag: true,
+            copyable_video: None,
+        },
+        "mkv" | "ts" => OutputCodecs {
+            video: VideoCodec::X264,
+            hdr_video: VideoCodec::X265,
+            audio: "aac",
+            hevc_tag: false,
+            copyable_video: None,
+        },
+        "flv" => OutputCodecs {
+            video: VideoCodec::X264,
+            hdr_video: VideoCodec::X264,
+            audio: "aac",
+            hevc_tag: false,
+            copyable_video: None,
+        },
+        _ => bail!("Cannot write '.{ext}' clips; use mp4, mkv, mov, m4v, ts, flv or webm."),
+    };
+    Ok(codecs)
+}
+
+impl OutputCodecs {
+    fn can_copy(&self, info: &ProbeInfo) -> bool {
+        match (self.copyable_video, info.video_codec.as_deref()) {
+            (Some(allowed), Some(codec)) => allowed.contains(&codec),
+            (Some(_), None) => false,
+            (None, _) => true,
+        }
+    }
+
+    fn video_args(&self, command: &mut Command, info: &ProbeInfo, video: Option<VideoEncode>) {
+        let crf = video.map(|video| video.crf);
+        let codec = if info.hdr { self.hdr_video } else { self.video };
+        match codec {
+            VideoCodec::X264 => {
+                command
+                    .arg("-c:v")
+                    .arg("libx264")
+                    .arg("-crf")
+                    .arg(crf.unwrap_or(18).to_string());
+            }
+            VideoCodec::X265 => {
+                command
+                    .arg("-c:v")
+                    .arg("libx265")
+                    .arg("-crf")
+                    .arg(crf.unwrap_or(20).to_string())
+                    .arg("-pix_fmt")
+                    .arg("yuv420p10le");
+                if self.hevc_tag {
+                    command.arg("-tag:v").arg("hvc1");
+                }
+            }
+            VideoCodec::Vp9 => {
+                command
+                    .arg("-c:v")
+                    .arg("libvpx-vp9")
+                    .arg("-b:v")
+                    .arg("0")
+                    .arg("-crf")
+                    .arg(crf.unwrap_or(31).to_string())
+                    .arg("-row-mt")
+                    .arg("1");
+                if info.hdr {
+                    command.arg("-pix_fmt").arg("yuv420p10le");
+                }
+            }
+        }
+        if let Some(video) = video {
+            if matches!(codec, VideoCodec::Vp9) {
+                command
+                    .arg("-cpu-used")
+                    .arg(video.preset.vpx_cpu_used().to_string());
+            } else {
+                command.arg("-preset").arg(video.preset.to_string());
+            }
+        }
+        command.args(&info.color_args);
+    }
+}
+
+fn needs_transcode(info: &ProbeInfo, force_copy: bool) -> bool {
+    let Some(codec) = info.video_codec.as_deref() else {
+        return false;
+    };
+    if !COPY_UNSAFE_CODECS.contains(&codec) {
+        return false;
+    }
+    if force_copy {
+        println!(
+            "{}",
+            format!(
+                "⚠ '{codec}' video may not trim cleanly with stream copy; copying anyway (--force-copy)."
+            )
+            .warning()
+        );
+        return false;
+    }
+    println!(
+        "{}",
+        format!(
+            "○ '{codec}' video cannot be trimmed reliably with stream copy; re-encoding instead. Use --force-copy to override."
+        )
+        .warning()
+    );
+    true
+}
+
+async fn warn_if_hdr_lost(clip_path: &Path, ext: &str) {
+    let Ok(info) = probe_clip(clip_path).await else {
+        return;
+    };
+    if info.hdr && !HDR_CONTAINERS.contains(&ext.to_lowercase().as_str()) {
+        println!(
+            "{}",
+            format!(
+                "⚠ This clip is HDR, but '.{ext}' cannot carry BT.2020/PQ color metadata; colors may look washed out."
+            )
+            .warning()
+        );
+    }
+}
+
+fn resolve_output_ext(source: &Path, output_ext: Option<&str>) -> Result<String> {
+    let ext = match output_ext {
+        Some(ext) => {
+            let ext = ext.trim().trim_start_matches('.').to_lowercase();
+            if ext.is_empty() || !ext.chars().all(|c| c.is_ascii_alphanumeric()) {
+                bail!("Invalid output extension '{ext}'.");
+            }
+            ext
+        }
+        None => source
+            .extension()
+            .and_then(|e| e.to_str())
+            .unwrap_or("mp4")
+            .to_string(),
+    };
+    output_codecs(&ext)?;
+    Ok(ext)
+}
+
+pub enum TrimRange {
+    Times { start: String, end: String },
+    Chapters { from: usize, to: usize },
+    Interactive,
+}
+
+impl TrimRange {
+    pub fn from_times(start: &str, end: &str) -> Result<Self> {
+        Ok(TrimRange::Times {
+            start: validate_ffmpeg_time(start)?,
+            end: validate_ffmpeg_time(end)?,
+        })
+    }
+
+    pub fn from_chapters(from: usize, to: usize) -> Result<Self> {
+        if from == 0 || to == 0 {
+            bail!("Chapter numbers start at 1.");
+        }
+        if from > to {
+            bail!("--from-chapter ({from}) must not be after --to-chapter ({to}).");
+        }
+        Ok(TrimRange::Chapters { from, to })
+    }
+
+    async fn resolve(&self, clip_path: &Path) -> Result<(String, String)> {
+        match self {
+            TrimRange::Times { start, end } => Ok((start.clone(), end.clone())),
+            TrimRange::Chapters { from, to } => {
+                let chapters = probe_chapters(clip_path).await?;
+                if chapters.is_empty() {
+                    bail!("This clip has no chapter markers.");
+                }
+                let chapter = |number: usize| {
+                    chapters.get(number - 1).context(format!(
+                        "Chapter {number} does not exist (the clip has {} chapters).",
+                        chapters.len()
+                    ))
+                };
+                Ok((
+                    chapter(*from)?.start_time.clone(),
+                    chapter(*to)?.end_time.clone(),
+                ))
+            }
+            TrimRange::Interactive => pick_trim_points(clip_path).await,
+        }
+    }
+}
 
 pub async fn handle_edit(
     name: &str,
-    start_time_str: &str,
-    end_time_str: &str,
-    disable_audio: &bool,
+    range: &TrimRange,
+    output_ext: Option<&str>,
+    encode: &EncodeOptions,
+    filename_rules: &FilenameRules,
+    lookup: ClipLookup,
 ) -> Result<()> {
-    println!("○ Preparing to edit '{}'...", name.cyan());
+    println!("○ Preparing to edit '{}'...", name.accent());
     println!(
         "{}",
-        "Note: This operation is performed locally and does not affect hosted clips.".yellow()
+        "Note: This operation is performed locally and does not affect hosted clips.".warning()
     );
 
-    let start_time = validate_ffmpeg_time(start_time_str)?;
-    let end_time = validate_ffmpeg_time(end_time_str)?;
+    let decrypted = decrypt_for_access(name).await?;
+    let result = match &decrypted {
+        Some(decrypted) => {
+            edit_clip(
+                &decrypted.name,
+                &decrypted.path,
+                range,
+                output_ext,
+                encode,
+                Some(decrypted),
+                filename_rules,
+            )
+            .await
+        }
+        None => {
+            let clip = find_unified_clip(name, lookup).await?;
+            let clip_path_str = clip
+                .local_path
+                .context(format!("Clip '{}' not found locally.", clip.name))?;
+            edit_clip(
+                &clip.name,
+                Path::new(&clip_path_str),
+                range,
+                output_ext,
+                encode,
+                None,
+                filename_rules,
+            )
+            .await
+        }
+    };
 
-    let clip = find_unified_clip(name).await?;
-    let clip_path_str = clip
-        .local_path
-        .context(format!("Clip '{}' not found locally.", clip.name))?;
-    let clip_path = PathBuf::from(&clip_path_str);
+    if let Some(decrypted) = decrypted {
+        decrypted.remove().await?;
+    }
+    result
+}
 
-    let options = vec!["Create a new, edited copy", "Modify the original file"];
-    let choice = Select::new("What would you like to do?", options).prompt()?;
+#[derive(Clone, Copy, Debug)]
+pub struct SpeedChange {
+    pub factor: f64,
+    pub preserve_pitch: bool,
+}
 
-    let (output_path, is_overwrite) = if choice == "Create a new, edited copy" {
-        let new_name_suggestion = format!("{}_edited", clip.name);
-        let new_name_input = Text::new("› Enter name for the new clip (without extension):")
-            .with_initial_value(&new_name_suggestion)
-            .prompt()?;
-        let new_name_stem = sanitize_and_validate_filename_stem(&new_name_input)?;
-        (
-            clip_path.with_file_name(format!("{new_name_stem}.mp4")),
-            false,
-        )
-    } else {
-        let confirmed = Confirm::new("Modifying the original file cannot be undone. Are you sure?")
-            .with_default(false)
-            .prompt()?;
-        if !confirmed {
-            println!("{}", "○ Edit cancelled.".yellow());
-            return Ok(());
+impl SpeedChange {
+    pub fn new(factor: f64, preserve_pitch: bool) -> Result<Self> {
+        if !factor.is_finite() || !(0.25..=4.0).contains(&factor) {
+            bail!("Speed must be between 0.25 and 4.0, got {factor}.");
         }
-        (clip_path.clone(), true)
-    };
+        Ok(Self {
+            factor,
+            preserve_pitch,
+        })
+    }
 
-    let temp_output_path = output_path.with_extension("tmp.mp4");
+    fn audio_filter(&self, sample_rate: u32) -> String {
+        if !self.preserve_pitch {
+            return format!(
+                "asetrate={}*{},aresample={sample_rate}",
+                sample_rate, self.factor
+            );
+        }
+        let mut remaining = self.factor;
+        let mut stages = Vec::new();
+        while remaining > 2.0 {
+            stages.push("atempo=2.0".to_string());
+            remaining /= 2.0;
+        }
+        while remaining < 0.5 {
+            stages.push("atempo=0.5".to_string());
+            remaining /= 0.5;
+        }
+        stages.push(format!("atempo={remaining}"));
+        stages.join(",")
+    }
+}
 
-    println!("{}", "◌ Processing clip...".yellow());
+#[derive(Clone, Copy, Debug)]
+pub struct VideoEncode {
+    pub crf: u8,
+    pub preset: EncodePreset,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct EncodeOptions {
+    pub disable_audio: bool,
+    pub speed: Option<SpeedChange>,
+    pub loudness_target: Option<f64>,
+    pub gate_mic: bool,
+    pub video: Option<VideoEncode>,
+    pub force_copy: bool,
+    pub bumpers: Bumpers,
+}
+
+impl EncodeOptions {
+    pub fn new(
+        disable_audio: bool,
+        speed: Option<SpeedChange>,
+        loudness_target: Option<f64>,
+        gate_mic: bool,
+        video: Option<VideoEncode>,
+        force_copy: bool,
+        bumpers: Bumpers,
+    ) -> Result<Self> {
+        if let Some(lufs) = loudness_target
+            && (!lufs.is_finite() || !(-70.0..=-5.0).contains(&lufs))
+        {
+            bail!("Target loudness must be between -70 and -5 LUFS, got {lufs}.");
+        }
+        if disable_audio && loudness_target.is_some() {
+            bail!("--normalize cannot be combined with disabling audio.");
+        }
+        if disable_audio && gate_mic {
+            bail!("--gate-mic cannot be combined with disabling audio.");
+        }
+        Ok(Self {
+            disable_audio,
+            speed,
+            loudness_target,
+            gate_mic,
+            video,
+            force_copy,
+            bumpers,
+        })
+    }
+
+    fn audio_filters(&self, sample_rate: u32) -> Vec<String> {
+        let mut filters = Vec::new();
+        if let Some(speed) = self.speed {
+            filters.push(speed.audio_filter(sample_rate));
+        }
+        if let Some(lufs) = self.loudness_target {
+            filters.push(format!("loudnorm=I={lufs}:TP=-1.5:LRA=11"));
+        }
+        filters
+    }
+}
+
+async fn trim_clip(
+    input: &Path,
+    output: &Path,
+    start_time: &str,
+    end_time: &str,
+    encode: &EncodeOptions,
+) -> Result<()> {
+    if encode.bumpers.is_empty() {
+        return trim_segment(input, output, start_time, end_time, encode).await;
+    }
+    let ext = output.extension().and_then(|e| e.to_str()).unwrap_or("mp4");
+    let trimmed_path = private_temp_path(ext);
+    let result = match trim_segment(input, &trimmed_path, start_time, end_time, encode).await {
+        Ok(_) => {
+            println!("{}", "◌ Adding intro/outro...".warning());
+            apply_bumpers(
+                &encode.bumpers,
+                &trimmed_path,
+                output,
+                !encode.disable_audio,
+            )
+            .await
+        }
+        Err(e) => Err(e),
+    };
+    let _ = tokio::fs::remove_file(&trimmed_path).await;
+    result
+}
 
+async fn trim_segment(
+    input: &Path,
+    output: &Path,
+    start_time: &str,
+    end_time: &str,
+    encode: &EncodeOptions,
+) -> Result<()> {
+    let codecs = output_codecs(output.extension().and_then(|e| e.to_str()).unwrap_or("mp4"))?;
     let mut command = Command::new("ffmpeg");
     command
         .arg("-i")
-        .arg(&clip_path)
+        .arg(input)
         .arg("-ss")
         .arg(start_time)
         .arg("-to")
-        .arg(end_time)
-        .arg("-c:v")
-        .arg("copy");
+        .arg(end_time);
 
-    if *disable_audio {
+    let info = probe_clip(input).await?;
+    let reencode_video = encode.video.is_some()
+        || encode.speed.is_some()
+        || !codecs.can_copy(&info)
+        || needs_transcode(&info, encode.force_copy);
+    if reencode_video {
+        if let Some(speed) = encode.speed {
+            command
+                .arg("-filter:v")
+                .arg(format!("setpts=PTS/{}", speed.factor));
+        }
+        codecs.video_args(&mut command, &info, encode.video);
+    } else {
+        command.arg("-c:v").arg("copy");
+    }
+
+    if encode.gate_mic {
+        if info.audio_streams < 2 {
+            bail!(
+                "--gate-mic needs the microphone on its own audio track, but this clip has {}. Record desktop and microphone audio as separate tracks to use it.",
+                info.audio_streams
+            );
+        }
+        let chain: String = encode
+            .audio_filters(info.sample_rate.unwrap_or(48_000))
+            .iter()
+            .map(|filter| format!(",{filter}"))
+            .collect();
+        command
+            .arg("-filter_complex")
+            .arg(format!(
+                "[0:a:0]anull{chain}[game];[0:a:1]{MIC_GATE_FILTER}{chain}[mic]"
+            ))
+            .arg("-map")
+            .arg("0:v:0")
+            .arg("-map")
+            .arg("[game]")
+            .arg("-map")
+            .arg("[mic]")
+            .arg("-c:a")
+            .arg(codecs.audio);
+    } else if encode.disable_audio || !info.has_audio {
         command.arg("-an");
     } else {
-        command.arg("-c:a").arg("copy");
+        let filters = encode.audio_filters(info.sample_rate.unwrap_or(48_000));
+        if !filters.is_empty() {
+            command.arg("-filter:a").arg(filters.join(","));
+        }
+        if reencode_video || !filters.is_empty() {
+            command.arg("-c:a").arg(codecs.audio);
+        } else {
+            command.arg("-c:a").arg("copy");
+        }
     }
 
-    command.arg(&temp_output_path);
+    command
+        .arg("-progress")
+        .arg("pipe:1")
+        .arg("-nostats")
+        .arg(output);
 
-    let output = command
-        .output()
-        .await
+    let mut duration = ffmpeg_time_to_seconds(end_time)? - ffmpeg_time_to_seconds(start_time)?;
+    if let Some(speed) = encode.speed {
+        duration /= speed.factor;
+    }
+    run_with_progress(&mut command, duration).await
+}
+
+fn progress_style() -> Result<ProgressStyle> {
+    Ok(ProgressStyle::with_template(
+        "{spinner:.cyan} {msg} [{bar:30.cyan/blue}] {percent}% ({eta})",
+    )?
+    .progress_chars("█▓░"))
+}
+
+async fn run_with_progress(command: &mut Command, duration: f64) -> Result<()> {
+    let mut child = command
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
         .context("Failed to execute ffmpeg. Is it installed and in your PATH?")?;
 
-    if !output.status.success() {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        bail!("ffmpeg failed with status: {}\n{}", output.status, stderr);
+    let mut stderr = child
+        .stderr
+        .take()
+        .context("Failed to capture ffmpeg output")?;
+    let stderr_task = tokio::spawn(async move {
+        let mut buffer = String::new();
+        let _ = stderr.read_to_string(&mut buffer).await;
+        buffer
+    });
+
+    let total_ms = (duration.max(0.0) * 1000.0) as u64;
+    let progress = ProgressBar::new(total_ms.max(1));
+    progress.set_style(progress_style()?);
+    progress.set_message("Processing clip");
+    progress.enable_steady_tick(Duration::from_millis(120));
+
+    let stdout = child
+        .stdout
+        .take()
+        .context("Failed to capture ffmpeg output")?;
+    let mut lines = BufReader::new(stdout).lines();
+    while let Some(line) = lines.next_line().await? {
+        if let Some(micros) = line.strip_prefix("out_time_us=")
+            && let Ok(micros) = micros.trim().parse::<u64>()
+        {
+            progress.set_position((micros / 1000).min(total_ms));
+        }
+    }
+
+    let status = child.wait().await?;
+    progress.finish_and_clear();
+    let stderr = stderr_task.await.unwrap_or_default();
+    if !status.success() {
+        bail!("ffmpeg failed with status: {}\n{}", status, stderr);
+    }
+    Ok(())
+}
+
+async fn edit_clip(
+    clip_name: &str,
+    clip_path: &Path,
+    range: &TrimRange,
+    output_ext: Option<&str>,
+    encode: &EncodeOptions,
+    decrypted: Option<&DecryptedClip>,
+    filename_rules: &FilenameRules,
+) -> Result<()> {
+    let (start_time, end_time) = range.resolve(clip_path).await?;
+    let ext = resolve_output_ext(clip_path, output_ext)?;
+    warn_if_hdr_lost(clip_path, &ext).await;
+
+    let output_dir = match decrypted {
+        Some(decrypted) => decrypted
+            .encrypted_path
+            .parent()
+            .context("Encrypted clip has no parent directory")?
+            .to_path_buf(),
+        None => clip_path
+            .parent()
+            .context("Clip has no parent directory")?
+            .to_path_buf(),
+    };
+
+    let options = vec!["Create a new, edited copy", "Modify the original file"];
+    let choice = Select::new("What would you like to do?", options).prompt()?;
+
+    let (output_path, is_overwrite) = if choice == "Create a new, edited copy" {
+        let new_name_suggestion = format!("{clip_name}_edited");
+        let new_name_input = Text::new("› Enter name for the new clip (without extension):")
+            .with_initial_value(&new_name_suggestion)
+            .prompt()?;
+        let new_name_stem = fit_filename_stem(
+            &sanitize_and_validate_filename_stem(&new_name_input, filename_rules)?,
+            &ext,
+            filename_rules,
+        )?;
+        (output_dir.join(format!("{new_name_stem}.{ext}")), false)
+    } else {
+        if !clip_path
+            .extension()
+            .and_then(|e| e.to_str())
+            .is_some_and(|e| e.eq_ignore_ascii_case(&ext))
+        {
+            bail!(
+                "Cannot change the container to '.{ext}' when modifying the original. Create a new copy instead."
+            );
+        }
+        let confirmed = Confirm::new("Modifying the original file cannot be undone. Are you sure?")
+            .with_default(false)
+            .prompt()?;
+        if !confirmed {
+            println!("{}", "○ Edit cancelled.".warning());
+            return Ok(());
+        }
+        (clip_path.to_path_buf(), true)
+    };
+
+    let temp_output_path = if decrypted.is_some() {
+        private_temp_path(&ext)
+    } else {
+        output_path.with_extension(format!("tmp.{ext}"))
+    };
+
+    if let Err(e) = trim_clip(clip_path, &temp_output_path, &start_time, &end_time, encode).await {
+        if decrypted.is_some() && temp_output_path.exists() {
+            let _ = shred_file(&temp_output_path).await;
+        }
+        return Err(e);
+    }
+
+    if let Some(decrypted) = decrypted {
+        return store_encrypted_output(decrypted, &temp_output_path, &output_path, is_overwrite)
+            .await;
     }
 
     if is_overwrite {
-        tokio::fs::rename(&temp_output_path, &clip_path)
+        tokio::fs::rename(&temp_output_path, clip_path)
             .await
             .context("Failed to replace original file")?;
-        println!("{}", "✔ Original clip successfully modified.".green());
+        println!("{}", "✔ Original clip successfully modified.".success());
     } else {
         tokio::fs::rename(&temp_output_path, &output_path)
             .await
@@ -99,9 +661,181 @@ pub async fn handle_edit(
                 "✔ New clip saved as '{}'",
                 output_path.file_name().unwrap().to_str().unwrap()
             )
-            .green()
+            .success()
+        );
+    }
+
+    Ok(())
+}
+
+async fn store_encrypted_output(
+    decrypted: &DecryptedClip,
+    temp_output_path: &Path,
+    output_path: &Path,
+    is_overwrite: bool,
+) -> Result<()> {
+    let encrypted_output = if is_overwrite {
+        decrypted.encrypted_path.clone()
+    } else {
+        PathBuf::from(format!("{}.enc", output_path.display()))
+    };
+    let staging_path = encrypted_output.with_extension("enc.tmp");
+
+    let encrypted = encrypt_file(temp_output_path, &staging_path, &decrypted.passphrase).await;
+    shred_file(temp_output_path).await?;
+    encrypted?;
+    tokio::fs::rename(&staging_path, &encrypted_output)
+        .await
+        .context("Failed to save encrypted clip")?;
+
+    if is_overwrite {
+        println!(
+            "{}",
+            "✔ Original encrypted clip successfully modified.".success()
+        );
+    } else {
+        let new_name = output_path
+            .file_stem()
+            .and_then(|s| s.to_str())
+            .context("Invalid output filename")?
+            .to_string();
+        let mut state = ClipState::load().await?;
+        state.encrypted.insert(new_name.clone(), encrypted_output);
+        state.save().await?;
+        println!(
+            "{}",
+            format!("✔ New encrypted clip saved as '{new_name}'").success()
         );
     }
+    Ok(())
+}
 
+pub async fn handle_edit_batch(
+    patterns: &[String],
+    range: &TrimRange,
+    output_ext: Option<&str>,
+    encode: &EncodeOptions,
+    resume: bool,
+    filename_rules: &FilenameRules,
+    lookup: ClipLookup,
+) -> Result<()> {
+    let mut checkpoint = Checkpoint::open("edit-batch", resume).await?;
+    let mut clips: Vec<UnifiedClipData> = Vec::new();
+    for pattern in patterns {
+        let matched = if is_glob_pattern(pattern) {
+            find_unified_clips_matching(pattern, lookup).await?
+        } else {
+            vec![find_unified_clip(pattern, lookup).await?]
+        };
+        for clip in matched {
+            if !clips.iter().any(|c| c.name == clip.name) {
+                clips.push(clip);
+            }
+        }
+    }
+
+    println!("○ Batch editing {} clips:", clips.len());
+    for clip in &clips {
+        println!("  {}", clip.name.accent());
+    }
+    println!(
+        "{}",
+        "Note: Edited copies are saved as '<name>_edited'; originals are left untouched.".warning()
+    );
+
+    let confirmed = Confirm::new("Proceed?").with_default(true).prompt()?;
+    if !confirmed {
+        println!("{}", "○ Edit cancelled.".warning());
+        return Ok(());
+    }
+
+    if checkpoint.completed_count() > 0 {
+        println!(
+            "○ Resuming: skipping {} clips already edited.",
+            checkpoint.completed_count()
+        );
+    }
+
+    let mut failed = 0;
+    for clip in &clips {
+        if checkpoint.is_done(&clip.name) {
+            continue;
+        }
+        let Some(local_path) = &clip.local_path else {
+            println!("{} {}: not found locally", "✗".error(), clip.name);
+            failed += 1;
+            continue;
+        };
+        let clip_path = Path::new(local_path);
+        let ext = resolve_output_ext(clip_path, output_ext)?;
+        warn_if_hdr_lost(clip_path, &ext).await;
+        let output_stem =
+            match fit_filename_stem(&format!("{}_edited", clip.name), &ext, filename_rules) {
+                Ok(stem) => stem,
+                Err(e) => {
+                    println!("{} {}: {e}", "✗".error(), clip.name);
+                    failed += 1;
+                    continue;
+                }
+            };
+        let output_path = clip_path.with_file_name(format!("{output_stem}.{ext}"));
+        if output_path.exists() {
+            println!(
+                "{} {}: '{}' already exists",
+                "✗".error(),
+                clip.name,
+                output_path.display()
+            );
+            failed += 1;
+            continue;
+        }
+
+        let (start_time, end_time) = match range.resolve(clip_path).await {
+            Ok(times) => times,
+            Err(e) => {
+                println!("{} {}: {e:#}", "✗".error(), clip.name);
+                failed += 1;
+                continue;
+            }
+        };
+
+        let temp_output_path = output_path.with_extension(format!("tmp.{ext}"));
+        let result =
+            match trim_clip(clip_path, &temp_output_path, &start_time, &end_time, encode).await {
+                Ok(_) => tokio::fs::rename(&temp_output_path, &output_path)
+                    .await
+                    .context("Failed to save new clip"),
+                Err(e) => Err(e),
+            };
+
+        match result {
+            Ok(_) => {
+                println!(
+                    "{} {} → {}",
+                    "✔".success(),
+                    clip.name,
+                    output_path.display()
+                );
+                checkpoint.mark_done(&clip.name).await?;
+            }
+            Err(e) => {
+                let _ = tokio::fs::remove_file(&temp_output_path).await;
+                println!("{} {}: {e:#}", "✗".error(), clip.name);
+                failed += 1;
+            }
+        }
+    }
+
+    if failed > 0 {
+        bail!(
+            "{failed} of {} clips failed to edit. Fix the problems and re-run with --resume to skip finished clips.",
+            clips.len()
+        );
+    }
+    checkpoint.finish().await?;
+    println!(
+        "{}",
+        format!("✔ Edited {} clips.", clips.len()).success().bold()
+    );
     Ok(())
 }
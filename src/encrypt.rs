@@ -0,0 +1,253 @@
+use crate::clip_state::ClipState;
+use crate::data_file::move_clip_entry;
+use crate::theme::Themed;
+use crate::unified_clip::{ClipLookup, find_unified_clip};
+use anyhow::{Context, Result, bail};
+use chrono::Utc;
+use colored::*;
+use inquire::{Password, PasswordDisplayMode};
+use std::env;
+use std::ffi::OsStr;
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use tokio::io::AsyncWriteExt;
+use tokio::process::Command;
+
+pub struct DecryptedClip {
+    pub name: String,
+    pub path: PathBuf,
+    pub encrypted_path: PathBuf,
+    pub passphrase: String,
+}
+
+impl DecryptedClip {
+    pub async fn remove(self) -> Result<()> {
+        shred_file(&self.path).await
+    }
+}
+
+async fn run_gpg(args: &[&OsStr], passphrase: &str) -> Result<()> {
+    let mut process = Command::new("gpg")
+        .args(["--batch", "--yes", "--quiet", "--pinentry-mode", "loopback"])
+        .args(["--passphrase-fd", "0"])
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn()
+        .context("Failed to execute gpg. Is it installed and in your PATH?")?;
+
+    if let Some(mut stdin) = process.stdin.take() {
+        stdin.write_all(passphrase.as_bytes()).await?;
+        stdin.write_all(b"\n").await?;
+    }
+
+    let output = process.wait_with_output().await?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        bail!(
+            "gpg failed with status: {}\n{}",
+            output.status,
+            stderr.trim()
+        );
+    }
+    Ok(())
+}
+
+pub async fn encrypt_file(input: &Path, output: &Path, passphrase: &str) -> Result<()> {
+    run_gpg(
+        &[
+            OsStr::new("--symmetric"),
+            OsStr::new("--cipher-algo"),
+            OsStr::new("AES256"),
+            OsStr::new("--output"),
+            output.as_os_str(),
+            input.as_os_str(),
+        ],
+        passphrase,
+    )
+    .await
+}
+
+async fn decrypt_file(input: &Path, output: &Path, passphrase: &str) -> Result<()> {
+    let result = run_gpg(
+        &[
+            OsStr::new("--output"),
+            output.as_os_str(),
+            OsStr::new("--decrypt"),
+            input.as_os_str(),
+        ],
+        passphrase,
+    )
+    .await;
+    if result.is_err() && output.exists() {
+        let _ = shred_file(output).await;
+    }
+    result.context("Decryption failed. Is the passphrase correct?")
+}
+
+async fn wipe_file(path: &Path) -> Result<()> {
+    let len = tokio::fs::metadata(path).await?.len();
+    let mut file = tokio::fs::OpenOptions::new()
+        .write(true)
+        .open(path)
+        .await
+        .context(format!("Failed to open {} for wiping", path.display()))?;
+    let zeros = vec![0u8; 1 << 20];
+    let mut remaining = len;
+    while remaining > 0 {
+        let chunk = remaining.min(zeros.len() as u64) as usize;
+        file.write_all(&zeros[..chunk]).await?;
+        remaining -= chunk as u64;
+    }
+    file.sync_all().await?;
+    Ok(())
+}
+
+pub async fn shred_file(path: &Path) -> Result<()> {
+    wipe_file(path).await?;
+    tokio::fs::remove_file(path)
+        .await
+        .context(format!("Failed to remove {}", path.display()))
+}
+
+pub fn private_temp_path(extension: &str) -> PathBuf {
+    let dir = env::var_os("XDG_RUNTIME_DIR")
+        .map(PathBuf::from)
+        .unwrap_or_else(env::temp_dir);
+    dir.join(format!(
+        "wayclip-{}-{}.{extension}",
+        std::process::id(),
+        Utc::now().timestamp_millis()
+    ))
+}
+
+fn prompt_passphrase(message: &str, confirm: bool) -> Result<String> {
+    let mut prompt = Password::new(message).with_display_mode(PasswordDisplayMode::Masked);
+    if !confirm {
+        prompt = prompt.without_confirmation();
+    }
+    let passphrase = prompt.prompt()?;
+    if passphrase.is_empty() {
+        bail!("Passphrase cannot be empty.");
+    }
+    Ok(passphrase)
+}
+
+pub async fn decrypt_for_access(name: &str) -> Result<Option<DecryptedClip>> {
+    let state = ClipState::load().await?;
+    let Some((clip_name, encrypted_path)) = state.find_encrypted(name) else {
+        return Ok(None);
+    };
+
+    println!("○ '{}' is encrypted.", clip_name.accent());
+    let passphrase = prompt_passphrase("› Enter the passphrase:", false)?;
+    let extension = encrypted_path
+        .with_extension("")
+        .extension()
+        .and_then(|e| e.to_str())
+        .unwrap_or("mp4")
+        .to_string();
+    let temp_path = private_temp_path(&extension);
+
+    println!("{}", "◌ Decrypting to a temporary file...".warning());
+    decrypt_file(&encrypted_path, &temp_path, &passphrase).await?;
+
+    Ok(Some(DecryptedClip {
+        name: clip_name,
+        path: temp_path,
+        encrypted_path,
+        passphrase,
+    }))
+}
+
+async fn move_data_entry(from: &Path, to: &Path) {
+    let (Some(from), Some(to)) = (
+        from.file_name().and_then(|s| s.to_str()),
+        to.file_name().and_then(|s| s.to_str()),
+    ) else {
+        return;
+    };
+    if let Err(e) = move_clip_entry(from, to).await {
+        println!(
+            "{}",
+            format!("⚠ Could not carry the clip's tags and likes over: {e:#}").warning()
+        );
+    }
+}
+
+pub async fn handle_encrypt(name: &str, lookup: ClipLookup) -> Result<()> {
+    let mut state = ClipState::load().await?;
+    if let Some((clip_name, _)) = state.find_encrypted(name) {
+        bail!("Clip '{clip_name}' is already encrypted.");
+    }
+
+    let clip = find_unified_clip(name, lookup).await?;
+    let clip_path_str = clip
+        .local_path
+        .context(format!("Clip '{}' not found locally.", clip.name))?;
+    let clip_path = PathBuf::from(&clip_path_str);
+
+    if clip.is_hosted {
+        println!(
+            "{}",
+            "⚠ This clip is also hosted. The server copy will not be encrypted.".warning()
+        );
+    }
+
+    let passphrase = prompt_passphrase("› Choose a passphrase:", true)?;
+    let encrypted_path = PathBuf::from(format!("{clip_path_str}.enc"));
+
+    println!("{}", "◌ Encrypting clip...".warning());
+    encrypt_file(&clip_path, &encrypted_path, &passphrase).await?;
+
+    shred_file(&clip_path).await?;
+    move_data_entry(&clip_path, &encrypted_path).await;
+
+    state
+        .encrypted
+        .insert(clip.name.clone(), encrypted_path.clone());
+    state.save().await?;
+
+    println!(
+        "{}",
+        format!("✔ Clip '{}' encrypted.", clip.name)
+            .success()
+            .bold()
+    );
+    println!("  Stored at: {}", encrypted_path.display());
+    Ok(())
+}
+
+pub async fn handle_decrypt(name: &str) -> Result<()> {
+    let mut state = ClipState::load().await?;
+    let (clip_name, encrypted_path) = state
+        .find_encrypted(name)
+        .context(format!("No encrypted clip named '{}' found.", name.trim()))?;
+
+    let output_path = encrypted_path.with_extension("");
+    if output_path.exists() {
+        bail!(
+            "Cannot decrypt: '{}' already exists.",
+            output_path.display()
+        );
+    }
+
+    let passphrase = prompt_passphrase("› Enter the passphrase:", false)?;
+
+    println!("{}", "◌ Decrypting clip...".warning());
+    decrypt_file(&encrypted_path, &output_path, &passphrase).await?;
+
+    tokio::fs::remove_file(&encrypted_path)
+        .await
+        .context("Failed to remove the encrypted file")?;
+    move_data_entry(&encrypted_path, &output_path).await;
+    state.encrypted.remove(&clip_name);
+    state.save().await?;
+
+    println!(
+        "{}",
+        format!("✔ Clip '{clip_name}' decrypted.").success().bold()
+    );
+    Ok(())
+}
@@ -0,0 +1,12 @@
+use std::fmt;
+
+#[derive(Debug)]
+pub struct SilentExit(pub u8);
+
+impl fmt::Display for SilentExit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "exited with status {}", self.0)
+    }
+}
+
+impl std::error::Error for SilentExit {}
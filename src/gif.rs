@@ -0,0 +1,160 @@
+use crate::encrypt::private_temp_path;
+use crate::theme::Themed;
+use crate::unified_clip::{ClipLookup, find_unified_clip};
+use crate::validate::{ffmpeg_time_to_seconds, validate_ffmpeg_time};
+use anyhow::{Context, Result, bail};
+use inquire::Confirm;
+use std::path::Path;
+use tokio::process::Command;
+
+fn validate_gif_options(fps: u32, width: u32) -> Result<()> {
+    if !(1..=50).contains(&fps) {
+        bail!("--fps must be between 1 and 50, got {fps}.");
+    }
+    if !(16..=1920).contains(&width) {
+        bail!("--width must be between 16 and 1920, got {width}.");
+    }
+    Ok(())
+}
+
+fn time_args(start: Option<&str>, end: Option<&str>) -> Vec<String> {
+    let mut args = Vec::new();
+    if let Some(start) = start {
+        args.push("-ss".to_string());
+        args.push(start.to_string());
+    }
+    if let Some(end) = end {
+        args.push("-to".to_string());
+        args.push(end.to_string());
+    }
+    args
+}
+
+async fn run_ffmpeg(command: &mut Command) -> Result<()> {
+    let output = command
+        .output()
+        .await
+        .context("Failed to execute ffmpeg. Is it installed and in your PATH?")?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        bail!("ffmpeg failed with status: {}\n{}", output.status, stderr);
+    }
+    Ok(())
+}
+
+async fn render_gif(
+    input: &Path,
+    output: &Path,
+    start: Option<&str>,
+    end: Option<&str>,
+    fps: u32,
+    width: u32,
+) -> Result<()> {
+    let scale = format!("fps={fps},scale={width}:-1:flags=lanczos");
+    let palette_path = private_temp_path("png");
+
+    let result = async {
+        run_ffmpeg(
+            Command::new("ffmpeg")
+                .arg("-y")
+                .arg("-i")
+                .arg(input)
+                .args(time_args(start, end))
+                .arg("-vf")
+                .arg(format!("{scale},palettegen=stats_mode=diff"))
+                .arg(&palette_path),
+        )
+        .await?;
+
+        run_ffmpeg(
+            Command::new("ffmpeg")
+                .arg("-y")
+                .arg("-i")
+                .arg(input)
+                .arg("-i")
+                .arg(&palette_path)
+                .args(time_args(start, end))
+                .arg("-lavfi")
+                .arg(format!(
+                    "{scale}[x];[x][1:v]paletteuse=dither=bayer:bayer_scale=5"
+                ))
+                .arg("-loop")
+                .arg("0")
+                .arg(output),
+        )
+        .await
+    }
+    .await;
+
+    let _ = tokio::fs::remove_file(&palette_path).await;
+    result
+}
+
+pub async fn handle_gif(
+    name: &str,
+    start: Option<&str>,
+    end: Option<&str>,
+    fps: u32,
+    width: u32,
+    force: bool,
+    lookup: ClipLookup,
+) -> Result<()> {
+    validate_gif_options(fps, width)?;
+    let start = start.map(validate_ffmpeg_time).transpose()?;
+    let end = end.map(validate_ffmpeg_time).transpose()?;
+    if let (Some(start), Some(end)) = (&start, &end)
+        && ffmpeg_time_to_seconds(start)? >= ffmpeg_time_to_seconds(end)?
+    {
+        bail!("--start ({start}) must be before --end ({end}).");
+    }
+
+    let clip = find_unified_clip(name, lookup).await?;
+    let clip_path_str = clip
+        .local_path
+        .context(format!("Clip '{}' not found locally.", clip.name))?;
+    let clip_path = Path::new(&clip_path_str);
+    let output_path = clip_path.with_file_name(format!("{}.gif", clip.name));
+
+    if output_path.exists() && !force {
+        let confirmed = Confirm::new(&format!(
+            "'{}' already exists. Overwrite it?",
+            output_path.display()
+        ))
+        .with_default(false)
+        .prompt()?;
+        if !confirmed {
+            println!("{}", "○ GIF export cancelled.".warning());
+            return Ok(());
+        }
+    }
+
+    println!(
+        "{}",
+        format!("◌ Rendering '{}' as a GIF...", clip.name).warning()
+    );
+    render_gif(
+        clip_path,
+        &output_path,
+        start.as_deref(),
+        end.as_deref(),
+        fps,
+        width,
+    )
+    .await?;
+
+    let size = tokio::fs::metadata(&output_path)
+        .await
+        .map(|m| m.len())
+        .unwrap_or(0);
+    println!(
+        "{}",
+        format!(
+            "✔ Saved {} ({:.1} MB)",
+            output_path.display(),
+            size as f64 / 1_048_576.0
+        )
+        .success()
+    );
+    Ok(())
+}
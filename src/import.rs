@@ -0,0 +1,330 @@
+use crate::data_file::add_clip_tags;
+use crate::sidecar::import_sidecar;
+use crate::theme::Themed;
+use crate::unified_clip::{ClipLookup, clips_directory, gather_clips};
+use crate::validate::{FilenameRules, fit_filename_stem, sanitize_and_validate_filename_stem};
+use anyhow::{Context, Result, bail};
+use colored::*;
+use comfy_table::presets::UTF8_FULL;
+use comfy_table::{Cell, ContentArrangement, Table};
+use inquire::Confirm;
+use regex::Regex;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use tokio::process::Command;
+use wayclip_core::settings::Settings;
+
+const VIDEO_EXTENSIONS: [&str; 5] = ["mp4", "mkv", "mov", "webm", "flv"];
+
+pub struct NameRules {
+    prefix: Option<Regex>,
+    suffix: Option<Regex>,
+}
+
+impl NameRules {
+    pub fn new(
+        strip_prefix: Option<&str>,
+        strip_suffix: Option<&str>,
+        use_regex: bool,
+    ) -> Result<Self> {
+        let build = |pattern: &str, anchor_start: bool| -> Result<Regex> {
+            let body = if use_regex {
+                pattern.to_string()
+            } else {
+                regex::escape(pattern)
+            };
+            let source = if anchor_start {
+                format!("^(?:{body})")
+            } else {
+                format!("(?:{body})$")
+            };
+            Regex::new(&source).context(format!("Invalid strip pattern '{pattern}'"))
+        };
+        Ok(Self {
+            prefix: strip_prefix.map(|p| build(p, true)).transpose()?,
+            suffix: strip_suffix.map(|p| build(p, false)).transpose()?,
+        })
+    }
+
+    fn apply(&self, stem: &str) -> String {
+        let mut name = stem.to_string();
+        if let Some(prefix) = &self.prefix {
+            name = prefix.replace(&name, "").into_owned();
+        }
+        if let Some(suffix) = &self.suffix {
+            name = suffix.replace(&name, "").into_owned();
+        }
+        name
+    }
+}
+
+pub struct ImportPlan {
+    pub source: PathBuf,
+    pub destination: PathBuf,
+    pub problem: Option<String>,
+}
+
+pub fn is_video_file(path: &Path) -> bool {
+    path.extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|e| VIDEO_EXTENSIONS.contains(&e.to_lowercase().as_str()))
+}
+
+async fn collect_sources(paths: &[PathBuf]) -> Result<Vec<PathBuf>> {
+    let mut sources = Vec::new();
+    for path in paths {
+        if path.is_dir() {
+            let mut entries = tokio::fs::read_dir(path)
+                .await
+                .context(format!("Failed to read directory {}", path.display()))?;
+            let mut found = Vec::new();
+            while let Some(entry) = entries.next_entry().await? {
+                let entry_path = entry.path();
+                if entry_path.is_file() && is_video_file(&entry_path) {
+                    found.push(entry_path);
+                }
+            }
+            found.sort();
+            sources.extend(found);
+        } else if path.is_file() {
+            if !is_video_file(path) {
+                bail!("'{}' is not a supported video file.", path.display());
+            }
+            sources.push(path.clone());
+        } else {
+            bail!("'{}' does not exist.", path.display());
+        }
+    }
+    Ok(sources)
+}
+
+pub fn plan_imports(
+    sources: &[PathBuf],
+    clips_dir: &Path,
+    rules: &NameRules,
+    filename_rules: &FilenameRules,
+) -> Vec<ImportPlan> {
+    let mut taken = HashSet::new();
+    sources
+        .iter()
+        .map(|source| {
+            let stem = source
+                .file_stem()
+                .and_then(|s| s.to_str())
+                .unwrap_or_default();
+            let (destination, problem) =
+                match sanitize_and_validate_filename_stem(&rules.apply(stem), filename_rules)
+                    .and_then(|new_stem| fit_filename_stem(&new_stem, "mp4", filename_rules))
+                {
+                    Ok(new_stem) => {
+                        let destination = clips_dir.join(format!("{new_stem}.mp4"));
+                        let problem = if destination.exists() {
+                            Some("a clip with this name already exists".to_string())
+                        } else if !taken.insert(new_stem.to_lowercase()) {
+                            Some("duplicate name in this import".to_string())
+                        } else {
+                            None
+                        };
+                        (destination, problem)
+                    }
+                    Err(e) => (clips_dir.join(stem), Some(e.to_string())),
+                };
+            ImportPlan {
+                source: source.clone(),
+                destination,
+                problem,
+            }
+        })
+        .collect()
+}
+
+pub async fn import_file(source: &Path, destination: &Path) -> Result<()> {
+    let is_mp4 = source
+        .extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|e| e.eq_ignore_ascii_case("mp4"));
+
+    if is_mp4 {
+        tokio::fs::copy(source, destination)
+            .await
+            .context(format!("Failed to copy {}", source.display()))?;
+        return Ok(());
+    }
+
+    let output = Command::new("ffmpeg")
+        .arg("-n")
+        .arg("-i")
+        .arg(source)
+        .arg("-c")
+        .arg("copy")
+        .arg(destination)
+        .output()
+        .await
+        .context("Failed to execute ffmpeg. Is it installed and in your PATH?")?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        bail!("ffmpeg failed with status: {}\n{}", output.status, stderr);
+    }
+    Ok(())
+}
+
+pub fn display_stem(path: &Path) -> String {
+    path.file_stem()
+        .and_then(|s| s.to_str())
+        .unwrap_or_default()
+        .to_string()
+}
+
+pub fn normalize_tags(tags: &[String]) -> Result<Vec<String>> {
+    let mut normalized: Vec<String> = Vec::new();
+    for tag in tags {
+        let tag = tag.trim();
+        if tag.is_empty() {
+            bail!("Tags cannot be empty.");
+        }
+        if !normalized.iter().any(|t| t == tag) {
+            normalized.push(tag.to_string());
+        }
+    }
+    Ok(normalized)
+}
+
+async fn tag_imported(destinations: &[&Path], tags: &[String], lookup: ClipLookup) {
+    if let Err(e) = gather_clips(lookup).await {
+        println!(
+            "{}",
+            format!("⚠ Could not refresh clip data: {e:#}").warning()
+        );
+    }
+    let mut untagged = 0;
+    for destination in destinations {
+        let Some(full_filename) = destination.file_name().and_then(|s| s.to_str()) else {
+            untagged += 1;
+            continue;
+        };
+        if !matches!(add_clip_tags(full_filename, tags).await, Ok(true)) {
+            untagged += 1;
+        }
+    }
+    if untagged > 0 {
+        println!(
+            "{}",
+            format!("⚠ Could not tag {untagged} clips; they have no local data entry yet.")
+                .warning()
+        );
+    } else {
+        println!(
+            "○ Tagged {} clips with {}.",
+            destinations.len(),
+            tags.join(", ").accent()
+        );
+    }
+}
+
+pub async fn handle_import(
+    paths: &[PathBuf],
+    rules: &NameRules,
+    sidecar: bool,
+    tags: &[String],
+    filename_rules: &FilenameRules,
+    lookup: ClipLookup,
+) -> Result<()> {
+    let tags = normalize_tags(tags)?;
+    let settings = Settings::load().await?;
+    let clips_dir = clips_directory(&settings);
+    tokio::fs::create_dir_all(&clips_dir)
+        .await
+        .context(format!(
+            "Failed to create clips directory {}",
+            clips_dir.display()
+        ))?;
+
+    let sources = collect_sources(paths).await?;
+    if sources.is_empty() {
+        println!("{}", "○ No video files found to import.".warning());
+        return Ok(());
+    }
+
+    let plans = plan_imports(&sources, &clips_dir, rules, filename_rules);
+
+    let mut table = Table::new();
+    table
+        .load_preset(UTF8_FULL)
+        .set_content_arrangement(ContentArrangement::Dynamic)
+        .set_header(vec![
+            Cell::new("Source").add_attribute(comfy_table::Attribute::Bold),
+            Cell::new("Imported As").add_attribute(comfy_table::Attribute::Bold),
+            Cell::new("Status").add_attribute(comfy_table::Attribute::Bold),
+        ]);
+    for plan in &plans {
+        let status = match &plan.problem {
+            Some(problem) => format!("✗ {problem}").error().to_string(),
+            None => "✔ ok".success().to_string(),
+        };
+        table.add_row(vec![
+            Cell::new(
+                plan.source
+                    .file_name()
+                    .unwrap_or_default()
+                    .to_string_lossy(),
+            ),
+            Cell::new(display_stem(&plan.destination)),
+            Cell::new(status),
+        ]);
+    }
+    println!("{table}");
+
+    let importable: Vec<&ImportPlan> = plans.iter().filter(|p| p.problem.is_none()).collect();
+    if importable.is_empty() {
+        bail!("None of the files can be imported.");
+    }
+
+    let confirmed = Confirm::new(&format!(
+        "Import {} of {} files into {}?",
+        importable.len(),
+        plans.len(),
+        clips_dir.display()
+    ))
+    .with_default(true)
+    .prompt()?;
+    if !confirmed {
+        println!("{}", "○ Import cancelled.".warning());
+        return Ok(());
+    }
+
+    let mut failed = 0;
+    let mut imported = Vec::new();
+    for plan in &importable {
+        match import_file(&plan.source, &plan.destination).await {
+            Ok(_) => {
+                imported.push(plan.destination.as_path());
+                println!("{} {}", "✔".success(), display_stem(&plan.destination));
+                if sidecar && let Err(e) = import_sidecar(&plan.source, &plan.destination).await {
+                    println!(
+                        "  {}",
+                        format!("⚠ Sidecar metadata not applied: {e:#}").warning()
+                    );
+                }
+            }
+            Err(e) => {
+                failed += 1;
+                println!("{} {}: {e:#}", "✗".error(), plan.source.display());
+            }
+        }
+    }
+
+    if !tags.is_empty() && !imported.is_empty() {
+        tag_imported(&imported, &tags, lookup).await;
+    }
+
+    if failed > 0 {
+        bail!("{failed} of {} imports failed.", importable.len());
+    }
+    println!(
+        "{}",
+        format!("✔ Imported {} clips.", importable.len())
+            .success()
+            .bold()
+    );
+    Ok(())
+}
@@ -0,0 +1,70 @@
+use crate::probe::{probe_clip, probe_keyframes};
+use crate::social::format_bytes;
+use crate::theme::Themed;
+use crate::unified_clip::{ClipLookup, find_unified_clip};
+use anyhow::{Context, Result};
+use colored::*;
+use std::path::Path;
+
+const KEYFRAME_LIST_LIMIT: usize = 40;
+
+pub async fn handle_info(name: &str, keyframes: bool, lookup: ClipLookup) -> Result<()> {
+    let clip = find_unified_clip(name, lookup).await?;
+    let clip_path_str = clip
+        .local_path
+        .context(format!("Clip '{}' not found locally.", clip.name))?;
+    let clip_path = Path::new(&clip_path_str);
+
+    let info = probe_clip(clip_path)
+        .await
+        .context(format!("Clip '{}' is not playable", clip.name))?;
+    let size = tokio::fs::metadata(clip_path)
+        .await
+        .context(format!("Failed to read {}", clip_path.display()))?
+        .len();
+
+    println!("{}", "┌─ Clip Info ─────────────────────".bold());
+    println!("│ {} {}", "Name:".accent(), clip.name);
+    println!("│ {} {}", "Path:".accent(), clip_path.display());
+    println!("│ {} {}", "Size:".accent(), format_bytes(size as f64));
+    println!("│ {} {:.2}s", "Duration:".accent(), info.duration);
+    println!(
+        "│ {} {}{}",
+        "Video:".accent(),
+        info.video_codec.as_deref().unwrap_or("unknown"),
+        if info.hdr { " (HDR)" } else { "" }
+    );
+    match info.sample_rate {
+        Some(rate) if info.has_audio => println!("│ {} {rate} Hz", "Audio:".accent()),
+        _ if info.has_audio => println!("│ {} yes", "Audio:".accent()),
+        _ => println!("│ {} none", "Audio:".accent()),
+    }
+
+    if keyframes {
+        let keyframes = probe_keyframes(clip_path).await?;
+        println!("├─ Keyframes ───────────────────");
+        if keyframes.is_empty() {
+            println!("│ {}", "No keyframes found.".warning());
+        } else {
+            let average_gop = if keyframes.len() > 1 {
+                (keyframes[keyframes.len() - 1] - keyframes[0]) / (keyframes.len() - 1) as f64
+            } else {
+                info.duration
+            };
+            println!("│ {} {}", "Count:".accent(), keyframes.len());
+            println!("│ {} {average_gop:.2}s", "Average GOP:".accent());
+            let listed = keyframes.len().min(KEYFRAME_LIST_LIMIT);
+            for time in &keyframes[..listed] {
+                println!("│   {time:.3}");
+            }
+            if keyframes.len() > listed {
+                println!(
+                    "│   {}",
+                    format!("… {} more", keyframes.len() - listed).dimmed()
+                );
+            }
+        }
+    }
+    println!("└─────────────────────────────────");
+    Ok(())
+}
@@ -0,0 +1,162 @@
+use crate::import::{NameRules, display_stem, import_file, is_video_file, plan_imports};
+use crate::theme::Themed;
+use crate::unified_clip::{ClipLookup, clips_directory, gather_clips};
+use crate::validate::FilenameRules;
+use anyhow::{Context, Result, bail};
+use chrono::Local;
+use colored::*;
+use notify::{Event, RecursiveMode, Watcher};
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tokio::sync::mpsc;
+use tokio::time::Instant;
+use wayclip_core::settings::Settings;
+
+async fn video_files(dir: &Path) -> Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    let mut entries = tokio::fs::read_dir(dir)
+        .await
+        .context(format!("Failed to read directory {}", dir.display()))?;
+    while let Some(entry) = entries.next_entry().await? {
+        let path = entry.path();
+        if path.is_file() && is_video_file(&path) {
+            files.push(path);
+        }
+    }
+    Ok(files)
+}
+
+async fn ingest_file(
+    source: &Path,
+    clips_dir: &Path,
+    rules: &NameRules,
+    filename_rules: &FilenameRules,
+) -> Result<String> {
+    let plan = plan_imports(&[source.to_path_buf()], clips_dir, rules, filename_rules)
+        .pop()
+        .context("Nothing to import")?;
+    if let Some(problem) = plan.problem {
+        bail!("{problem}");
+    }
+    import_file(&plan.source, &plan.destination).await?;
+    Ok(display_stem(&plan.destination))
+}
+
+async fn settled(deadline: Option<Instant>) {
+    match deadline {
+        Some(deadline) => tokio::time::sleep_until(deadline).await,
+        None => std::future::pending().await,
+    }
+}
+
+pub async fn handle_ingest(
+    dir: &Path,
+    settle_secs: u64,
+    filename_rules: &FilenameRules,
+    lookup: ClipLookup,
+) -> Result<()> {
+    if !dir.is_dir() {
+        bail!("'{}' is not a directory.", dir.display());
+    }
+    let settings = Settings::load().await?;
+    let clips_dir = clips_directory(&settings);
+    tokio::fs::create_dir_all(&clips_dir)
+        .await
+        .context(format!(
+            "Failed to create clips directory {}",
+            clips_dir.display()
+        ))?;
+    let rules = NameRules::new(None, None, false)?;
+
+    let (sender, mut events) = mpsc::unbounded_channel();
+    let mut watcher = notify::recommended_watcher(move |event: notify::Result<Event>| {
+        let _ = sender.send(event);
+    })
+    .context("Failed to start the file watcher")?;
+    watcher
+        .watch(dir, RecursiveMode::NonRecursive)
+        .context(format!("Failed to watch {}", dir.display()))?;
+
+    let mut handled: HashSet<PathBuf> = video_files(dir).await?.into_iter().collect();
+    let mut pending: HashMap<PathBuf, Instant> = HashMap::new();
+
+    println!("{}", "┌─ Wayclip Ingest ─────".bold());
+    println!("│ Watching {}", dir.display().to_string().accent());
+    if !handled.is_empty() {
+        println!(
+            "│ {} existing files ignored; use `wayclip import` for those.",
+            handled.len()
+        );
+    }
+    println!("│ Press {} to stop.", "Ctrl-C".accent());
+    println!("└──────────────────────");
+
+    let mut ingested = 0;
+    let mut failed = 0;
+    let settle = Duration::from_secs(settle_secs.max(1));
+    loop {
+        let next = pending.values().min().map(|changed| *changed + settle);
+        tokio::select! {
+            event = events.recv() => {
+                match event {
+                    Some(Ok(event)) if event.kind.is_create() || event.kind.is_modify() => {
+                        for path in event.paths {
+                            if is_video_file(&path) && !handled.contains(&path) {
+                                pending.insert(path, Instant::now());
+                            }
+                        }
+                    }
+                    Some(Ok(_)) => {}
+                    Some(Err(e)) => println!("{}", format!("⚠ Watcher error: {e}").warning()),
+                    None => break,
+                }
+                continue;
+            }
+            _ = settled(next) => {}
+            _ = tokio::signal::ctrl_c() => break,
+        }
+
+        let now = Instant::now();
+        let ready: Vec<PathBuf> = pending
+            .iter()
+            .filter(|(_, changed)| now.duration_since(**changed) >= settle)
+            .map(|(path, _)| path.clone())
+            .collect();
+        for path in ready {
+            pending.remove(&path);
+            let size = tokio::fs::metadata(&path)
+                .await
+                .map(|m| m.len())
+                .unwrap_or(0);
+            if size == 0 {
+                continue;
+            }
+            handled.insert(path.clone());
+
+            let timestamp = Local::now().format("%H:%M:%S");
+            match ingest_file(&path, &clips_dir, &rules, filename_rules).await {
+                Ok(name) => {
+                    ingested += 1;
+                    println!("[{timestamp}] {} {name}", "✔".success());
+                    if let Err(e) = gather_clips(lookup).await {
+                        println!(
+                            "{}",
+                            format!("⚠ Could not refresh clip data: {e:#}").warning()
+                        );
+                    }
+                }
+                Err(e) => {
+                    failed += 1;
+                    println!("[{timestamp}] {} {}: {e:#}", "✗".error(), path.display());
+                }
+            }
+        }
+    }
+
+    println!(
+        "{}",
+        format!("○ Stopped ingesting ({ingested} imported, {failed} failed).").warning()
+    );
+    Ok(())
+}
@@ -0,0 +1,127 @@
+use crate::probe::probe_clip;
+use crate::theme::Themed;
+use crate::validate::ffmpeg_time_to_seconds;
+use crate::view::{Playback, play_file};
+use anyhow::Result;
+use colored::*;
+use inquire::{InquireError, Select, Text};
+use std::path::Path;
+
+const DEFAULT_FRAME_RATE: f64 = 60.0;
+
+const ACTIONS: [&str; 12] = [
+    "◀  In −1 frame",
+    "▶  In +1 frame",
+    "◀◀ In −1s",
+    "▶▶ In +1s",
+    "◀  Out −1 frame",
+    "▶  Out +1 frame",
+    "◀◀ Out −1s",
+    "▶▶ Out +1s",
+    "⏵  Preview segment",
+    "✎  Type exact points",
+    "✔  Trim with these points",
+    "✗  Cancel",
+];
+
+fn format_timestamp(seconds: f64) -> String {
+    let millis = (seconds * 1000.0).round() as u64;
+    format!(
+        "{:02}:{:02}:{:02}.{:03}",
+        millis / 3_600_000,
+        millis / 60_000 % 60,
+        millis / 1000 % 60,
+        millis % 1000
+    )
+}
+
+fn snap_to_frame(seconds: f64, frame: f64) -> f64 {
+    (seconds / frame).round() * frame
+}
+
+pub async fn pick_trim_points(clip_path: &Path) -> Result<(String, String)> {
+    let info = probe_clip(clip_path).await?;
+    let frame = 1.0 / info.frame_rate.unwrap_or(DEFAULT_FRAME_RATE);
+    let duration = info.duration;
+    let mut start = 0.0_f64;
+    let mut end = snap_to_frame(duration, frame).min(duration);
+    let mut cursor = 0;
+
+    println!("{}", "┌─ Interactive Trim ──────────────".bold());
+    println!(
+        "│ Use {} to choose a step and {} to apply it.",
+        "↑/↓".accent(),
+        "Enter".accent()
+    );
+    println!("│ One frame is {:.3}s.", frame);
+    println!("└─────────────────────────────────");
+
+    loop {
+        println!(
+            "○ In {}  Out {}  ({:.3}s)",
+            format_timestamp(start).accent(),
+            format_timestamp(end).accent(),
+            end - start
+        );
+        let action = Select::new("Adjust trim points:", ACTIONS.to_vec())
+            .with_starting_cursor(cursor)
+            .with_page_size(ACTIONS.len())
+            .prompt()?;
+        cursor = ACTIONS.iter().position(|a| *a == action).unwrap_or(0);
+
+        let (new_start, new_end) = match cursor {
+            0 => (start - frame, end),
+            1 => (start + frame, end),
+            2 => (start - 1.0, end),
+            3 => (start + 1.0, end),
+            4 => (start, end - frame),
+            5 => (start, end + frame),
+            6 => (start, end - 1.0),
+            7 => (start, end + 1.0),
+            8 => {
+                let playback = Playback::new(
+                    false,
+                    Some(&start.to_string()),
+                    Some(&end.to_string()),
+                    true,
+                )?;
+                if let Err(e) = play_file(clip_path, None, &playback).await {
+                    println!("{} {e:#}", "✗ Preview failed:".error());
+                }
+                continue;
+            }
+            9 => {
+                let typed_start = Text::new("› In point:")
+                    .with_initial_value(&format_timestamp(start))
+                    .prompt()?;
+                let typed_end = Text::new("› Out point:")
+                    .with_initial_value(&format_timestamp(end))
+                    .prompt()?;
+                match (
+                    ffmpeg_time_to_seconds(&typed_start),
+                    ffmpeg_time_to_seconds(&typed_end),
+                ) {
+                    (Ok(s), Ok(e)) => (s, e),
+                    (Err(e), _) | (_, Err(e)) => {
+                        println!("{} {e}", "✗".error());
+                        continue;
+                    }
+                }
+            }
+            10 => return Ok((format_timestamp(start), format_timestamp(end))),
+            _ => return Err(InquireError::OperationCanceled.into()),
+        };
+
+        let new_start = snap_to_frame(new_start.max(0.0), frame);
+        let new_end = snap_to_frame(new_end.min(duration), frame).min(duration);
+        if new_end - new_start < frame {
+            println!(
+                "{}",
+                "⚠ The out point must stay at least one frame after the in point.".warning()
+            );
+            continue;
+        }
+        start = new_start;
+        end = new_end;
+    }
+}
@@ -1,48 +1,278 @@
 use crate::Commands;
-use anyhow::{Context, Result};
-use chrono::Utc;
+use crate::api_client::api_client;
+use crate::clip_state::ClipState;
+use crate::model::ListFormat;
+use crate::probe_cache::{CachedProbe, ProbeCache};
+use crate::tag::{clip_tags, has_tag};
+use crate::theme::Themed;
+use crate::unified_clip::{ClipLookup, gather_clips};
+use anyhow::{Context, Result, bail};
+use chrono::{DateTime, FixedOffset, Utc};
 use colored::*;
 use comfy_table::presets::UTF8_FULL;
 use comfy_table::{Cell, ContentArrangement, Table};
-use wayclip_core::{Collect, PullClipsArgs, gather_clip_data};
+use serde_json::{Value, json};
+use std::cmp::Reverse;
+use std::collections::HashSet;
+use std::path::Path;
+use wayclip_core::models::UnifiedClipData;
+use wayclip_core::settings::Settings;
 
-pub async fn handle_list(command: &Commands) -> Result<()> {
+struct HostedClip {
+    id: String,
+    name: String,
+    size: u64,
+    created_at: Option<DateTime<FixedOffset>>,
+}
+
+async fn fetch_hosted_clips() -> Result<Vec<HostedClip>> {
+    let settings = Settings::load().await?;
+    let client = api_client().await?;
+    let response = client
+        .get(format!("{}/api/clips", settings.api_url))
+        .send()
+        .await?;
+
+    let status = response.status();
+    if status == reqwest::StatusCode::UNAUTHORIZED {
+        bail!("You are not logged in. Please run `wayclip login` first.");
+    }
+    if !status.is_success() {
+        let error_body: Value = response.json().await.unwrap_or_default();
+        let error_msg = error_body["message"]
+            .as_str()
+            .unwrap_or("Unexpected server response.");
+        bail!("Failed to fetch hosted clips: {error_msg}");
+    }
+
+    let body: Value = response.json().await?;
+    let entries = body
+        .as_array()
+        .or_else(|| body["clips"].as_array())
+        .context("Unexpected response format from the server.")?;
+    Ok(entries
+        .iter()
+        .filter_map(|entry| {
+            let name = entry["file_name"].as_str()?;
+            Some(HostedClip {
+                id: entry["id"].as_str().unwrap_or_default().to_string(),
+                name: name.strip_suffix(".mp4").unwrap_or(name).to_string(),
+                size: entry["file_size"].as_u64().unwrap_or(0),
+                created_at: entry["created_at"]
+                    .as_str()
+                    .and_then(|s| DateTime::parse_from_rfc3339(s).ok()),
+            })
+        })
+        .collect())
+}
+
+fn hosted_porcelain_line(clip: &HostedClip, local: bool) -> String {
+    format!(
+        "{}\t{}\t{}\t{}\t{}",
+        clip.name,
+        clip.id,
+        clip.created_at.map(|t| t.to_rfc3339()).unwrap_or_default(),
+        clip.size,
+        u8::from(local)
+    )
+}
+
+async fn handle_list_server(reverse: bool, porcelain: bool, lookup: ClipLookup) -> Result<()> {
+    if lookup.offline {
+        bail!("--server cannot be combined with --offline.");
+    }
+    if !porcelain {
+        println!("{}", "◌ Fetching hosted clips...".warning());
+    }
+    let mut hosted = fetch_hosted_clips().await?;
+    if reverse {
+        hosted.sort_by_key(|c| Reverse(c.created_at));
+    } else {
+        hosted.sort_by_key(|c| c.name.to_lowercase());
+    }
+
+    let local_ids: HashSet<String> = gather_clips(lookup)
+        .await?
+        .into_iter()
+        .filter(|clip| clip.local_path.is_some())
+        .filter_map(|clip| clip.hosted_id.map(|id| id.to_string()))
+        .collect();
+
+    if porcelain {
+        for clip in &hosted {
+            println!(
+                "{}",
+                hosted_porcelain_line(clip, local_ids.contains(&clip.id))
+            );
+        }
+        return Ok(());
+    }
+
+    if hosted.is_empty() {
+        println!("{}", "○ No clips are hosted on the server.".warning());
+        return Ok(());
+    }
+
+    println!("Found {} hosted clips:", hosted.len());
+    let mut table = Table::new();
+    table
+        .load_preset(UTF8_FULL)
+        .set_content_arrangement(ContentArrangement::Dynamic)
+        .set_header(vec![
+            Cell::new("Clip Name").add_attribute(comfy_table::Attribute::Bold),
+            Cell::new("Uploaded").add_attribute(comfy_table::Attribute::Bold),
+            Cell::new("Size").add_attribute(comfy_table::Attribute::Bold),
+            Cell::new("Local Copy").add_attribute(comfy_table::Attribute::Bold),
+        ]);
+    for clip in &hosted {
+        let local = if local_ids.contains(&clip.id) {
+            "✔".success().to_string()
+        } else {
+            "server only".warning().to_string()
+        };
+        table.add_row(vec![
+            Cell::new(&clip.name),
+            Cell::new(
+                clip.created_at
+                    .map(|t| t.format("%Y-%m-%d %H:%M").to_string())
+                    .unwrap_or_default(),
+            ),
+            Cell::new(format!("{:.2} MB", clip.size as f64 / 1_048_576.0)),
+            Cell::new(local),
+        ]);
+    }
+    println!("{table}");
+    Ok(())
+}
+
+fn is_liked(clip: &UnifiedClipData) -> bool {
+    clip.local_data.as_ref().is_some_and(|d| d.liked)
+}
+
+fn porcelain_line(clip: &UnifiedClipData, size: u64, duration: f64, corrupt: bool) -> String {
+    format!(
+        "{}\t{}\t{}\t{:.3}\t{}\t{}",
+        clip.name,
+        clip.created_at.to_rfc3339(),
+        size,
+        duration,
+        u8::from(is_liked(clip)),
+        u8::from(corrupt)
+    )
+}
+
+async fn handle_list_json(reverse: bool, tag: Option<&str>, lookup: ClipLookup) -> Result<()> {
+    let mut clips = gather_clips(lookup).await?;
+    if let Some(tag) = tag {
+        clips.retain(|clip| has_tag(clip, tag));
+    }
+    if reverse {
+        clips.sort_by_key(|c| Reverse(c.created_at));
+    } else {
+        clips.sort_by_key(|c| c.name.to_lowercase());
+    }
+
+    let mut entries = Vec::new();
+    for clip in &clips {
+        let size = match &clip.local_path {
+            Some(path) => tokio::fs::metadata(path).await.ok().map(|m| m.len()),
+            None => None,
+        };
+        entries.push(json!({
+            "name": clip.name,
+            "full_filename": clip.full_filename,
+            "created_at": clip.created_at.to_rfc3339(),
+            "size": size,
+            "local": clip.local_path.is_some(),
+            "hosted": clip.is_hosted,
+            "hosted_id": clip.hosted_id.map(|id| id.to_string()),
+            "liked": is_liked(clip),
+            "tags": clip_tags(clip),
+        }));
+    }
+    println!("{}", serde_json::to_string_pretty(&entries)?);
+    Ok(())
+}
+
+pub async fn handle_list(command: &Commands, porcelain: bool, lookup: ClipLookup) -> Result<()> {
     let Commands::List {
         timestamp,
         length,
         reverse,
         size,
         extra,
+        format,
+        server,
+        tag,
+        json,
     } = command
     else {
         unreachable!()
     };
 
-    println!("{}", "◌ Fetching clips...".yellow());
-    let mut clips = gather_clip_data(
-        Collect::All,
-        PullClipsArgs {
-            page: 1,
-            page_size: 100,
-            search_query: None,
-        },
-    )
-    .await
-    .context("Could not list clips")?
-    .clips;
+    if *server {
+        return handle_list_server(*reverse, porcelain, lookup).await;
+    }
+    if *json {
+        return handle_list_json(*reverse, tag.as_deref(), lookup).await;
+    }
 
-    if clips.is_empty() {
-        println!("{}", "○ No clips found.".yellow());
-        return Ok(());
+    if !porcelain {
+        println!("{}", "◌ Fetching clips...".warning());
+    }
+    let mut clips: Vec<UnifiedClipData> = gather_clips(lookup)
+        .await
+        .context("Could not list clips")?
+        .into_iter()
+        .filter(|clip| clip.local_path.is_some())
+        .collect();
+    if let Some(tag) = tag {
+        clips.retain(|clip| has_tag(clip, tag));
     }
 
+    clips.sort_by_key(|c| c.name.to_lowercase());
     if *reverse {
         clips.reverse();
-    } else {
-        clips.sort_by(|a, b| a.name.to_lowercase().cmp(&b.name.to_lowercase()));
     }
 
-    println!("Found {} clips:", clips.len());
+    let clip_state = ClipState::load().await?;
+    let mut probe_cache = ProbeCache::load().await;
+    let needs_probe = porcelain || *length || *extra;
+    let mut rows = Vec::new();
+    for clip in &clips {
+        let Some(path) = clip.local_path.as_deref().map(Path::new) else {
+            continue;
+        };
+        let size = tokio::fs::metadata(path)
+            .await
+            .map(|m| m.len())
+            .unwrap_or(0);
+        let probe = if needs_probe {
+            probe_cache.probe(path).await
+        } else {
+            None
+        };
+        rows.push((clip, size, probe));
+    }
+
+    if porcelain {
+        for (clip, size, probe) in &rows {
+            let corrupt = *size == 0 || clip_state.unplayable.contains(&clip.name);
+            let duration = probe.as_ref().map_or(0.0, |p| p.duration);
+            println!("{}", porcelain_line(clip, *size, duration, corrupt));
+        }
+        if let Err(e) = probe_cache.save().await {
+            eprintln!("{}", format!("⚠ {e:#}").warning());
+        }
+        return Ok(());
+    }
+
+    if rows.is_empty() {
+        println!("{}", "○ No clips found.".warning());
+        return Ok(());
+    }
+
+    println!("Found {} clips:", rows.len());
 
     let mut table = Table::new();
     table
@@ -60,55 +290,144 @@ pub async fn handle_list(command: &Commands) -> Result<()> {
         headers.push(Cell::new("Duration").add_attribute(comfy_table::Attribute::Bold));
     }
     if *extra {
+        if !*length {
+            headers.push(Cell::new("Duration").add_attribute(comfy_table::Attribute::Bold));
+        }
+        headers.push(Cell::new("Resolution").add_attribute(comfy_table::Attribute::Bold));
+        headers.push(Cell::new("Codec").add_attribute(comfy_table::Attribute::Bold));
+        headers.push(Cell::new("Bitrate").add_attribute(comfy_table::Attribute::Bold));
         headers.push(Cell::new("Metadata").add_attribute(comfy_table::Attribute::Bold));
     }
     table.set_header(headers);
 
     let now = Utc::now();
 
-    for clip in clips {
+    for (clip, file_size, probe) in rows {
         let mut row = Vec::new();
 
         let clip_age = now.signed_duration_since(clip.created_at);
-        let display_name = if clip_age < chrono::Duration::hours(24) {
-            format!("{} {}", clip.name, "[NEW]".yellow())
+        let mut display_name = if clip_age < chrono::Duration::hours(24) {
+            format!("{} {}", clip.name, "[NEW]".warning())
         } else {
             clip.name.clone()
         };
-        row.push(Cell::new(display_name));
+        if file_size == 0 || clip_state.unplayable.contains(&clip.name) {
+            display_name = format!("{} {}", display_name, "[CORRUPT]".error());
+        }
+        row.push(display_name);
 
         if *timestamp {
-            row.push(Cell::new(clip.created_at.format("%Y-%m-%d %H:%M")));
+            row.push(clip.created_at.format("%Y-%m-%d %H:%M").to_string());
         }
         if *size {
-            row.push(Cell::new(format!(
-                "{:.2} MB",
-                clip.size as f64 / 1_048_576.0
-            )));
+            row.push(format!("{:.2} MB", file_size as f64 / 1_048_576.0));
         }
         if *length {
-            row.push(Cell::new(format!("{:.2}s", clip.length)));
+            row.push(format!(
+                "{:.2}s",
+                probe.as_ref().map_or(0.0, |p| p.duration)
+            ));
         }
         if *extra {
+            if !*length {
+                row.push(
+                    probe
+                        .as_ref()
+                        .map(|p| format!("{:.2}s", p.duration))
+                        .unwrap_or_default(),
+                );
+            }
+            row.push(
+                probe
+                    .as_ref()
+                    .and_then(CachedProbe::resolution)
+                    .unwrap_or_default(),
+            );
+            row.push(
+                probe
+                    .as_ref()
+                    .and_then(|p| p.video_codec.clone())
+                    .unwrap_or_default(),
+            );
+            row.push(
+                probe
+                    .as_ref()
+                    .and_then(|p| p.bit_rate)
+                    .map(|rate| format!("{:.1} Mb/s", rate as f64 / 1_000_000.0))
+                    .unwrap_or_default(),
+            );
+
             let mut meta = Vec::new();
-            if clip.liked {
+            if is_liked(clip) {
                 meta.push("♥".red().to_string());
             }
-            if !clip.tags.is_empty() {
-                meta.push(format!(
-                    "[{}]",
-                    clip.tags
-                        .iter()
-                        .map(ToString::to_string)
-                        .collect::<Vec<_>>()
-                        .join(", ")
-                ));
+            let tags = clip_tags(clip);
+            if !tags.is_empty() {
+                meta.push(format!("[{}]", tags.join(", ")));
             }
-            row.push(Cell::new(meta.join(" ")));
+            row.push(meta.join(" "));
+        }
+
+        match format {
+            ListFormat::Table => {
+                table.add_row(row.into_iter().map(Cell::new).collect::<Vec<_>>());
+            }
+            ListFormat::Compact => println!("{}", row.join("  ")),
         }
-        table.add_row(row);
     }
 
-    println!("{table}");
+    if *format == ListFormat::Table {
+        println!("{table}");
+    }
+    if let Err(e) = probe_cache.save().await {
+        println!("{}", format!("⚠ {e:#}").warning());
+    }
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Local;
+    use wayclip_core::ClipJsonData;
+
+    #[test]
+    fn porcelain_line_keeps_field_order() {
+        let created_at = DateTime::parse_from_rfc3339("2026-01-02T03:04:05+00:00")
+            .unwrap()
+            .with_timezone(&Local);
+        let clip = UnifiedClipData {
+            name: "raid_win".to_string(),
+            full_filename: "raid_win.mp4".to_string(),
+            local_path: Some("/clips/raid_win.mp4".to_string()),
+            local_data: Some(ClipJsonData {
+                liked: true,
+                ..ClipJsonData::default()
+            }),
+            created_at,
+            is_hosted: false,
+            hosted_id: None,
+        };
+        assert_eq!(
+            porcelain_line(&clip, 1_048_576, 12.5, false),
+            format!(
+                "raid_win\t{}\t1048576\t12.500\t1\t0",
+                created_at.to_rfc3339()
+            )
+        );
+    }
+
+    #[test]
+    fn hosted_porcelain_line_keeps_field_order() {
+        let clip = HostedClip {
+            id: "6f1c2a9e-0000-4000-8000-000000000001".to_string(),
+            name: "raid_win.mp4".to_string(),
+            size: 2048,
+            created_at: DateTime::parse_from_rfc3339("2026-01-02T03:04:05+00:00").ok(),
+        };
+        assert_eq!(
+            hosted_porcelain_line(&clip, true),
+            "raid_win.mp4\t6f1c2a9e-0000-4000-8000-000000000001\t2026-01-02T03:04:05+00:00\t2048\t1"
+        );
+    }
+}
@@ -1,48 +1,141 @@
+use crate::add_audio::handle_add_audio;
+use crate::alias::{handle_alias_add, handle_alias_list, handle_alias_remove};
 use crate::audio::handle_audio;
-use crate::auth::{handle_2fa_setup, handle_2fa_status, handle_login, handle_logout};
-use crate::autostart::{handle_autostart_off, handle_autostart_on};
+use crate::auth::{
+    handle_2fa_recovery_codes, handle_2fa_setup, handle_2fa_status, handle_login,
+    handle_login_check, handle_logout,
+};
+use crate::autostart::{handle_autostart_off, handle_autostart_on, handle_autostart_status};
+use crate::backup::{handle_config_backup, handle_config_restore};
+use crate::batch::handle_batch;
+use crate::bumper::Bumpers;
+use crate::cli_settings::CliSettings;
+use crate::clip_password::handle_set_password;
 use crate::clipboard::copy_to_clipboard;
+use crate::compare::handle_compare_render;
+use crate::completions::handle_completions;
 use crate::config::handle_config;
+use crate::daemon::{handle_daemon_reload, handle_daemon_start, handle_daemon_status};
 use crate::delete::handle_delete;
-use crate::edit::handle_edit;
+use crate::doctor::handle_doctor;
+use crate::download::{handle_download, handle_download_all};
+use crate::edit::{
+    EncodeOptions, SpeedChange, TrimRange, VideoEncode, handle_edit, handle_edit_batch,
+};
+use crate::encrypt::{handle_decrypt, handle_encrypt};
+use crate::gif::handle_gif;
+use crate::import::{NameRules, handle_import};
+use crate::info::handle_info;
+use crate::ingest::handle_ingest;
 use crate::like::handle_like;
 use crate::list::handle_list;
 use crate::manage::handle_manage;
-use crate::model::{AutostartAction, Cli, Commands, DaemonCommand, TwoFactorCommand};
+use crate::merge::handle_merge;
+use crate::model::{
+    AliasCommand, AutostartAction, Cli, Commands, ConfigCommand, DaemonCommand, TagCommand,
+    TrashCommand, TwoFactorCommand,
+};
+use crate::notify::notify_completion;
+use crate::prune::handle_prune;
 use crate::rename::handle_rename;
-use crate::save::handle_save;
-use crate::social::{handle_me, handle_share};
+use crate::reveal::handle_reveal;
+use crate::save::handle_save_scoped;
+use crate::search::handle_search;
+use crate::sidecar::handle_sidecar;
+use crate::social::{
+    ShareOptions, handle_analytics, handle_anonymous_share, handle_me, handle_share,
+};
+use crate::tag::{handle_tag_add, handle_tag_list, handle_tag_remove};
+use crate::theme::Themed;
+use crate::thumbnail::{handle_set_thumbnail, handle_thumbnail};
+use crate::trash::{handle_trash_empty, handle_trash_list, handle_trash_restore};
+use crate::unified_clip::{ClipLookup, handle_resolve, is_glob_pattern};
+use crate::upload::ShareMetadata;
+use crate::uploader::handle_uploader_share;
 use crate::url::{handle_open, handle_url};
-use crate::view::handle_view;
-use anyhow::Result;
+use crate::validate::FilenameRules;
+use crate::verify::handle_verify;
+use crate::view::{Playback, handle_view};
+use crate::watch::handle_watch;
+use anyhow::{Result, bail};
 use clap::Parser;
 use colored::*;
+use std::io::IsTerminal;
 use std::process::ExitCode;
 use wayclip_core::control::DaemonManager;
 
+pub mod add_audio;
+pub mod alias;
+pub mod api_client;
 pub mod audio;
 pub mod auth;
 pub mod autostart;
+pub mod backup;
+pub mod batch;
+pub mod bumper;
+pub mod checkpoint;
+pub mod cli_settings;
+pub mod clip_password;
+pub mod clip_state;
 pub mod clipboard;
+pub mod compare;
+pub mod completions;
 pub mod config;
+pub mod daemon;
+pub mod data_file;
 pub mod delete;
+pub mod doctor;
+pub mod download;
 pub mod edit;
+pub mod encrypt;
+pub mod exit;
+pub mod gif;
+pub mod import;
+pub mod info;
+pub mod ingest;
+pub mod interactive_trim;
 pub mod like;
 pub mod list;
 pub mod manage;
+pub mod merge;
 pub mod model;
+pub mod notify;
+pub mod probe;
+pub mod probe_cache;
+pub mod profile_cache;
+pub mod prompt;
+pub mod prune;
 pub mod rename;
+pub mod reveal;
 pub mod save;
+pub mod search;
+pub mod sidecar;
 pub mod social;
+pub mod tag;
+pub mod theme;
+pub mod thumbnail;
+pub mod token_store;
+pub mod trash;
 pub mod unified_clip;
+pub mod upload;
+pub mod uploader;
 pub mod url;
 pub mod validate;
+pub mod verify;
 pub mod view;
+pub mod watch;
 
 #[tokio::main]
 async fn main() -> ExitCode {
     if let Err(e) = run().await {
-        eprintln!("{} {:#}", "✗ Error:".red().bold(), e);
+        if let Some(exit::SilentExit(code)) = e.downcast_ref::<exit::SilentExit>() {
+            return ExitCode::from(*code);
+        }
+        if prompt::is_cancellation(&e) {
+            eprintln!("{}", "○ Cancelled.".warning());
+            return prompt::cancelled_exit();
+        }
+        eprintln!("{} {:#}", "✗ Error:".error().bold(), e);
         return ExitCode::FAILURE;
     }
     ExitCode::SUCCESS
@@ -50,51 +143,418 @@ async fn main() -> ExitCode {
 
 async fn run() -> Result<()> {
     let cli = Cli::parse();
+    let cli_settings = CliSettings::load().await?;
+    theme::init(&cli_settings)?;
+    let filename_rules = FilenameRules::from_settings(&cli_settings)?;
+    let lookup = ClipLookup {
+        fuzzy: cli.fuzzy,
+        offline: cli.offline,
+    };
     if cli.debug {
-        println!("{}", "○ Debug mode is ON".yellow());
+        println!("{}", "○ Debug mode is ON".warning());
+    }
+
+    if cli.command.uses_data_file() {
+        let interactive =
+            std::io::stdin().is_terminal() && !cli.porcelain && !cli.command.prints_json();
+        data_file::ensure_data_file_healthy(interactive).await?;
     }
 
     match &cli.command {
-        Commands::Login { browser } => handle_login(browser).await?,
+        Commands::Login {
+            browser,
+            timeout,
+            check,
+            verbose,
+            token,
+            email,
+            password,
+        } => {
+            if *check {
+                handle_login_check(*verbose).await?
+            } else {
+                handle_login(
+                    browser,
+                    *timeout,
+                    token.as_deref(),
+                    email.as_deref(),
+                    password.as_deref(),
+                )
+                .await?
+            }
+        }
         Commands::Logout => handle_logout().await?,
-        Commands::Me => handle_me().await?,
+        Commands::Me { fresh, json } => handle_me(cli.porcelain, *fresh, *json).await?,
+        Commands::Alias { action } => match action {
+            AliasCommand::Add { alias, target } => handle_alias_add(alias, target, lookup).await?,
+            AliasCommand::List => handle_alias_list().await?,
+            AliasCommand::Remove { alias } => handle_alias_remove(alias).await?,
+        },
         Commands::TwoFactorAuth { action } => match action {
             TwoFactorCommand::Setup => handle_2fa_setup().await?,
             TwoFactorCommand::Status => handle_2fa_status().await?,
+            TwoFactorCommand::RecoveryCodes { output } => {
+                handle_2fa_recovery_codes(output.as_deref()).await?
+            }
+        },
+        Commands::Share {
+            name,
+            anonymous,
+            uploader,
+            thumbnail_at,
+            replace,
+            password,
+            title,
+            description,
+            visibility,
+            retries,
+            notify,
+        } => {
+            let metadata = ShareMetadata {
+                title: title.clone(),
+                description: description.clone(),
+                visibility: *visibility,
+            };
+            let result = match uploader {
+                Some(uploader) => handle_uploader_share(name, uploader, cli.markdown, lookup)
+                    .await
+                    .map(|_| None),
+                None if *anonymous => handle_anonymous_share(name, cli.markdown, lookup)
+                    .await
+                    .map(|_| None),
+                None => {
+                    let options = ShareOptions {
+                        thumbnail_at: thumbnail_at.as_deref(),
+                        replace: replace.as_deref(),
+                        password: password.as_deref(),
+                        retries: *retries,
+                        markdown: cli.markdown,
+                        yes: false,
+                    };
+                    handle_share(name, &options, &metadata, lookup).await
+                }
+            };
+            if *notify {
+                let subject = match &result {
+                    Ok(Some(url)) => format!("{name}\n{url}"),
+                    _ => name.clone(),
+                };
+                notify_completion("Share", &subject, &result).await;
+            }
+            result?;
+        }
+        Commands::Save {
+            monitor,
+            region,
+            name,
+            tags,
+        } => {
+            handle_save_scoped(
+                monitor.as_deref(),
+                region.as_deref(),
+                name.as_deref(),
+                tags,
+                &filename_rules,
+                lookup,
+            )
+            .await?
+        }
+        Commands::Watch => handle_watch(&filename_rules, lookup).await?,
+        Commands::List { .. } => handle_list(&cli.command, cli.porcelain, lookup).await?,
+        Commands::Manage { sort } => {
+            handle_manage(*sort, &filename_rules, cli.markdown, lookup).await?
+        }
+        Commands::Config { editor, action } => match action {
+            Some(ConfigCommand::Backup {
+                file,
+                include_token,
+            }) => handle_config_backup(file, *include_token).await?,
+            Some(ConfigCommand::Restore { file, yes }) => handle_config_restore(file, *yes).await?,
+            None => handle_config(editor.as_deref()).await?,
+        },
+        Commands::View {
+            name,
+            player,
+            mute,
+            loop_start,
+            loop_end,
+            repeat,
+            seek,
+        } => {
+            let playback = Playback::new(
+                *mute,
+                loop_start.as_deref().or(seek.as_deref()),
+                loop_end.as_deref(),
+                *repeat || loop_end.is_some(),
+            )?;
+            handle_view(name, player.as_deref(), &playback, lookup).await?
+        }
+        Commands::Rename { name, truncate } => {
+            handle_rename(name, &filename_rules.with_truncate(*truncate), lookup).await?
+        }
+        Commands::Delete {
+            name,
+            server_only,
+            local_only,
+            yes,
+            permanent,
+        } => handle_delete(name, *server_only, *local_only, *yes, *permanent, lookup).await?,
+        Commands::Trash { action } => match action {
+            TrashCommand::List => handle_trash_list().await?,
+            TrashCommand::Restore { name } => handle_trash_restore(name).await?,
+            TrashCommand::Empty { yes } => handle_trash_empty(*yes).await?,
+        },
+        Commands::Tag { action } => match action {
+            TagCommand::Add { name, tags } => handle_tag_add(name, tags, lookup).await?,
+            TagCommand::Remove { name, tags } => handle_tag_remove(name, tags, lookup).await?,
+            TagCommand::List { name } => handle_tag_list(name, lookup).await?,
         },
-        Commands::Share { name } => handle_share(name).await?,
-        Commands::Save => handle_save().await?,
-        Commands::List { .. } => handle_list(&cli.command).await?,
-        Commands::Manage => handle_manage().await?,
-        Commands::Config { editor } => handle_config(editor.as_deref()).await?,
-        Commands::View { name, player } => handle_view(name, player.as_deref()).await?,
-        Commands::Rename { name } => handle_rename(name).await?,
-        Commands::Delete { name } => handle_delete(name).await?,
+        Commands::Prune {
+            pattern,
+            older_than,
+            dry_run,
+            yes,
+            permanent,
+        } => {
+            handle_prune(
+                pattern.as_deref(),
+                *older_than,
+                *dry_run,
+                *yes,
+                *permanent,
+                lookup,
+            )
+            .await?
+        }
         Commands::Edit {
             name,
             start_time,
             end_time,
             disable_audio,
-        } => handle_edit(name, start_time, end_time, disable_audio).await?,
-        Commands::Like { name } => handle_like(name).await?,
-        Commands::Url { name } => handle_url(name).await?,
-        Commands::Open { name } => handle_open(name).await?,
+            also,
+            output_ext,
+            from_chapter,
+            to_chapter,
+            speed,
+            preserve_pitch: _,
+            no_preserve_pitch,
+            interactive_trim,
+            resume,
+            truncate,
+            force_copy,
+            reencode,
+            crf,
+            preset,
+            normalize,
+            target_lufs,
+            gate_mic,
+            concat_intro,
+            concat_outro,
+            notify,
+        } => {
+            let filename_rules = filename_rules.with_truncate(*truncate);
+            let speed = speed
+                .map(|factor| SpeedChange::new(factor, !*no_preserve_pitch))
+                .transpose()?;
+            let video = reencode.then_some(VideoEncode {
+                crf: *crf,
+                preset: *preset,
+            });
+            let encode = EncodeOptions::new(
+                *disable_audio,
+                speed,
+                normalize.then_some(*target_lufs),
+                *gate_mic,
+                video,
+                *force_copy,
+                Bumpers::resolve(concat_intro.as_ref(), concat_outro.as_ref(), &cli_settings)
+                    .await?,
+            )?;
+            let range = match (from_chapter, to_chapter, start_time, end_time) {
+                _ if *interactive_trim => TrimRange::Interactive,
+                (Some(from), Some(to), _, _) => TrimRange::from_chapters(*from, *to)?,
+                (_, _, Some(start), Some(end)) => TrimRange::from_times(start, end)?,
+                _ => bail!("Provide a start and end time, or --from-chapter and --to-chapter."),
+            };
+            let result = if is_glob_pattern(name) || !also.is_empty() {
+                let patterns: Vec<String> = std::iter::once(name.clone())
+                    .chain(also.iter().cloned())
+                    .collect();
+                handle_edit_batch(
+                    &patterns,
+                    &range,
+                    output_ext.as_deref(),
+                    &encode,
+                    *resume,
+                    &filename_rules,
+                    lookup,
+                )
+                .await
+            } else {
+                handle_edit(
+                    name,
+                    &range,
+                    output_ext.as_deref(),
+                    &encode,
+                    &filename_rules,
+                    lookup,
+                )
+                .await
+            };
+            if *notify {
+                notify_completion("Edit", name, &result).await;
+            }
+            result?;
+        }
+        Commands::Like { name } => handle_like(name, lookup).await?,
+        Commands::Url { name } => handle_url(name, cli.markdown, lookup).await?,
+        Commands::Open { name } => handle_open(name, lookup).await?,
+        Commands::Import {
+            paths,
+            strip_prefix,
+            strip_suffix,
+            regex,
+            truncate,
+            sidecar,
+            tag,
+        } => {
+            handle_import(
+                paths,
+                &NameRules::new(strip_prefix.as_deref(), strip_suffix.as_deref(), *regex)?,
+                *sidecar,
+                tag,
+                &filename_rules.with_truncate(*truncate),
+                lookup,
+            )
+            .await?
+        }
+        Commands::Ingest { dir, settle } => {
+            handle_ingest(dir, *settle, &filename_rules, lookup).await?
+        }
+        Commands::Verify => handle_verify(lookup).await?,
+        Commands::Doctor { json, player } => handle_doctor(*json, player.as_deref()).await?,
+        Commands::CompareRender {
+            a,
+            b,
+            output,
+            vertical,
+        } => handle_compare_render(a, b, output, *vertical, lookup).await?,
+        Commands::Info { name, keyframes } => handle_info(name, *keyframes, lookup).await?,
+        Commands::Reveal { name } => handle_reveal(name, lookup).await?,
+        Commands::Download {
+            name,
+            all,
+            force,
+            jobs,
+            resume,
+            notify,
+        } => {
+            let (subject, result) = match name {
+                Some(name) if !*all => (name.clone(), handle_download(name, *force, lookup).await),
+                _ => (
+                    "All hosted clips".to_string(),
+                    handle_download_all(*force, *jobs, *resume).await,
+                ),
+            };
+            if *notify {
+                notify_completion("Download", &subject, &result).await;
+            }
+            result?;
+        }
+        Commands::Sidecar { name, all } => handle_sidecar(name.as_deref(), *all, lookup).await?,
+        Commands::Completions { shell } => handle_completions(*shell),
+        Commands::Search { query, limit, json } => {
+            handle_search(query, *limit, *json, lookup).await?
+        }
+        Commands::Resolve { name, stem } => handle_resolve(name, *stem, lookup).await?,
+        Commands::Analytics { name } => handle_analytics(name, lookup).await?,
         Commands::Daemon { action } => {
             let manager = DaemonManager::new();
             match action {
-                DaemonCommand::Start => manager.start().await?,
+                DaemonCommand::Start => handle_daemon_start(&manager).await?,
                 DaemonCommand::Stop => manager.stop().await?,
                 DaemonCommand::Restart => manager.restart().await?,
+                DaemonCommand::Reload => handle_daemon_reload(&manager).await?,
                 DaemonCommand::Logs => manager.logs().await?,
-                DaemonCommand::Status => {
-                    manager.status().await?;
-                }
+                DaemonCommand::Status => handle_daemon_status(&manager).await?,
                 DaemonCommand::Autostart { action } => match action {
-                    AutostartAction::On => handle_autostart_on().await?,
-                    AutostartAction::Off => handle_autostart_off().await?,
+                    AutostartAction::On { init } => handle_autostart_on(*init).await?,
+                    AutostartAction::Off { init } => handle_autostart_off(*init).await?,
+                    AutostartAction::Status { init } => handle_autostart_status(*init).await?,
                 },
             }
         }
+        Commands::SetThumbnail { name, at } => handle_set_thumbnail(name, at, lookup).await?,
+        Commands::SetPassword { name, password, .. } => {
+            handle_set_password(name, password.as_deref(), lookup).await?
+        }
+        Commands::Encrypt { name } => handle_encrypt(name, lookup).await?,
+        Commands::Decrypt { name } => handle_decrypt(name).await?,
+        Commands::Thumbnail {
+            names,
+            all,
+            output_dir,
+            output,
+            at,
+            jobs,
+        } => {
+            handle_thumbnail(
+                names,
+                *all,
+                output_dir.as_deref(),
+                output.as_deref(),
+                at.as_deref(),
+                *jobs,
+                lookup,
+            )
+            .await?
+        }
+        Commands::Merge {
+            names,
+            output,
+            reencode,
+        } => handle_merge(names, output.as_deref(), *reencode, &filename_rules, lookup).await?,
+        Commands::Gif {
+            name,
+            start,
+            end,
+            fps,
+            width,
+            force,
+        } => {
+            handle_gif(
+                name,
+                start.as_deref(),
+                end.as_deref(),
+                *fps,
+                *width,
+                *force,
+                lookup,
+            )
+            .await?
+        }
+        Commands::Batch {
+            file,
+            continue_on_error,
+        } => handle_batch(file, *continue_on_error, &filename_rules, lookup).await?,
+        Commands::AddAudio {
+            name,
+            audio,
+            keep_original,
+            original_volume,
+            audio_volume,
+        } => {
+            handle_add_audio(
+                name,
+                audio,
+                *keep_original,
+                *original_volume,
+                *audio_volume,
+                &filename_rules,
+                lookup,
+            )
+            .await?
+        }
         Commands::Audio => handle_audio().await?,
     }
 
@@ -1,106 +1,443 @@
-use crate::model::ClipDisplay;
-use crate::validate::sanitize_and_validate_filename_stem;
+use crate::api_client::delete_hosted_clip;
+use crate::cli_settings::CliSettings;
+use crate::clip_state::ClipState;
+use crate::data_file::{add_clip_tags, update_clip_metadata};
+use crate::edit::{EncodeOptions, TrimRange};
+use crate::model::{ClipDisplay, ManageSort};
+use crate::probe::probe_clip;
+use crate::prompt::is_cancellation;
+use crate::rename::rename_clip;
+use crate::social::ShareOptions;
+use crate::tag::{clip_tags, has_tag};
+use crate::theme::Themed;
+use crate::trash::move_to_trash;
+use crate::unified_clip::{ClipLookup, gather_clips};
+use crate::upload::ShareMetadata;
+use crate::url::{clip_public_url, format_url, markdown_link, open_url_or_print};
+use crate::validate::{FilenameRules, sanitize_and_validate_filename_stem};
+use crate::view::Playback;
 use crate::{copy_to_clipboard, handle_edit, handle_share, handle_view};
-use anyhow::{Context, Result, anyhow};
+use anyhow::{Context, Result, anyhow, bail};
 use chrono::Utc;
 use colored::*;
-use inquire::{Confirm, Select, Text};
+use inquire::{Confirm, MultiSelect, Select, Text};
+use std::cmp::Reverse;
+use std::collections::BTreeSet;
+use std::fmt;
 use std::path::Path;
-use wayclip_core::{
-    api, delete_file, gather_unified_clips, models::UnifiedClipData, rename_all_entries,
-    update_liked,
-};
+use wayclip_core::{models::UnifiedClipData, update_liked};
 
-fn generate_display_string(clip: &UnifiedClipData) -> String {
+#[derive(Clone, Debug, PartialEq)]
+enum ManageFilter {
+    Liked,
+    Hosted,
+    Local,
+    Tag(String),
+    Search(String),
+}
+
+impl ManageFilter {
+    fn matches(&self, clip: &UnifiedClipData) -> bool {
+        match self {
+            ManageFilter::Liked => clip.local_data.as_ref().is_some_and(|d| d.liked),
+            ManageFilter::Hosted => clip.is_hosted,
+            ManageFilter::Local => clip.local_path.is_some(),
+            ManageFilter::Tag(tag) => has_tag(clip, tag),
+            ManageFilter::Search(query) => clip.name.to_lowercase().contains(query),
+        }
+    }
+}
+
+impl fmt::Display for ManageFilter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ManageFilter::Liked => write!(f, "Only Liked"),
+            ManageFilter::Hosted => write!(f, "Only Hosted"),
+            ManageFilter::Local => write!(f, "Only Local"),
+            ManageFilter::Tag(tag) => write!(f, "Tag: {tag}"),
+            ManageFilter::Search(query) => write!(f, "Search: {query}"),
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum SortMenuEntry {
+    Sort(ManageSort),
+    Search,
+}
+
+impl fmt::Display for SortMenuEntry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SortMenuEntry::Sort(sort) => write!(f, "{sort}"),
+            SortMenuEntry::Search => write!(f, "Search by Name..."),
+        }
+    }
+}
+
+enum ManageEntry {
+    Clip(ClipDisplay),
+    ChangeFilter(Option<ManageFilter>),
+    ClearFilter,
+    ChangeSort(ManageSort),
+    BulkActions,
+    Refresh,
+    Quit,
+}
+
+impl fmt::Display for ManageEntry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ManageEntry::Clip(clip) => write!(f, "{clip}"),
+            ManageEntry::ChangeFilter(Some(filter)) => write!(f, "[Change Filter: {filter}]"),
+            ManageEntry::ChangeFilter(None) => write!(f, "[Filter Clips]"),
+            ManageEntry::ClearFilter => write!(f, "[Clear Filter]"),
+            ManageEntry::ChangeSort(sort) => write!(f, "[Change Sort: {sort}]"),
+            ManageEntry::BulkActions => write!(f, "[Bulk Actions]"),
+            ManageEntry::Refresh => write!(f, "[Refresh List]"),
+            ManageEntry::Quit => write!(f, "[Quit]"),
+        }
+    }
+}
+
+fn pick_filter(clips: &[UnifiedClipData]) -> Option<ManageFilter> {
+    let options = vec!["Only Liked", "Only Hosted", "Only Local", "By Tag"];
+    match Select::new("Show only:", options).prompt().ok()? {
+        "Only Liked" => Some(ManageFilter::Liked),
+        "Only Hosted" => Some(ManageFilter::Hosted),
+        "Only Local" => Some(ManageFilter::Local),
+        _ => {
+            let tags: BTreeSet<String> = clips.iter().flat_map(clip_tags).collect();
+            if tags.is_empty() {
+                println!("{}", "○ No clips have tags yet.".warning());
+                return None;
+            }
+            let tag = Select::new("Tag:", tags.into_iter().collect())
+                .prompt()
+                .ok()?;
+            Some(ManageFilter::Tag(tag))
+        }
+    }
+}
+
+pub fn generate_display_string(clip: &UnifiedClipData, unplayable: &BTreeSet<String>) -> String {
     let now = Utc::now();
     let clip_age = now.signed_duration_since(clip.created_at.with_timezone(&Utc));
     format!(
-        "{} {} {}{}{}",
+        "{} {} {}{}{}{}",
         if clip.local_path.is_some() {
             "⌨"
         } else {
             "  "
         },
         if clip.is_hosted { "☁" } else { "  " },
-        if clip.local_data.as_ref().map_or(false, |d| d.liked) {
+        if clip.local_data.as_ref().is_some_and(|d| d.liked) {
             "♥ ".red().to_string()
         } else {
             "".normal().to_string()
         },
         clip.name,
         if clip_age < chrono::Duration::hours(24) {
-            " [NEW]".yellow().to_string()
+            " [NEW]".warning().to_string()
+        } else {
+            "".normal().to_string()
+        },
+        if unplayable.contains(&clip.name) {
+            " [CORRUPT]".error().to_string()
         } else {
             "".normal().to_string()
         }
     )
 }
 
-pub async fn handle_manage() -> Result<()> {
-    let settings = wayclip_core::settings::Settings::load().await?;
+fn sort_clips(clips: &mut [UnifiedClipData], sort: ManageSort) {
+    match sort {
+        ManageSort::Date => clips.sort_by_key(|c| Reverse(c.created_at)),
+        ManageSort::Name => clips.sort_by_key(|c| c.name.to_lowercase()),
+        ManageSort::Liked => clips.sort_by(|a, b| {
+            b.local_data
+                .as_ref()
+                .is_some_and(|d| d.liked)
+                .cmp(&a.local_data.as_ref().is_some_and(|d| d.liked))
+                .then_with(|| b.created_at.cmp(&a.created_at))
+        }),
+        ManageSort::Hosted => clips.sort_by(|a, b| {
+            b.is_hosted
+                .cmp(&a.is_hosted)
+                .then_with(|| b.created_at.cmp(&a.created_at))
+        }),
+        ManageSort::Tag => clips.sort_by_cached_key(|clip| {
+            let first_tag = clip_tags(clip).into_iter().map(|t| t.to_lowercase()).min();
+            (first_tag.is_none(), first_tag, clip.name.to_lowercase())
+        }),
+    }
+}
 
-    println!("\n{}", "◌ Loading clips...".yellow());
-    let mut all_clips: Vec<UnifiedClipData> = gather_unified_clips().await?;
+async fn apply_bulk_action(
+    action: &str,
+    clip: &UnifiedClipData,
+    tag: &str,
+    markdown: bool,
+    lookup: ClipLookup,
+) -> Result<bool> {
+    match action {
+        "⌫ Delete Local Files" => {
+            let local_path = clip.local_path.as_ref().context("No local file")?;
+            move_to_trash(Path::new(local_path)).await?;
+            Ok(true)
+        }
+        "⌫ Delete Server Copies" => {
+            let hosted_id = clip.hosted_id.context("Not hosted")?;
+            delete_hosted_clip(hosted_id)
+                .await
+                .map_err(|e| anyhow!(e))?;
+            Ok(true)
+        }
+        "♥ Like" => {
+            if clip.local_path.is_none() {
+                bail!("No local file");
+            }
+            update_liked(&clip.full_filename, true)
+                .await
+                .map_err(|e| anyhow!(e))?;
+            Ok(true)
+        }
+        "# Add Tag" => {
+            if !add_clip_tags(&clip.full_filename, &[tag.to_string()]).await? {
+                bail!("No local data entry");
+            }
+            Ok(true)
+        }
+        "↗ Share" => {
+            if clip.is_hosted {
+                bail!("Already shared");
+            }
+            handle_share(
+                &clip.name,
+                &ShareOptions {
+                    markdown,
+                    yes: true,
+                    ..ShareOptions::default()
+                },
+                &ShareMetadata::default(),
+                lookup,
+            )
+            .await
+            .map(|url| url.is_some())
+        }
+        _ => unreachable!(),
+    }
+}
 
-    'main_loop: loop {
-        if all_clips.is_empty() {
-            println!("{}", "○ No clips found.".yellow());
-            return Ok(());
+async fn bulk_actions(
+    clips: &[&UnifiedClipData],
+    unplayable: &BTreeSet<String>,
+    markdown: bool,
+    lookup: ClipLookup,
+) -> Result<bool> {
+    let items: Vec<ClipDisplay> = clips
+        .iter()
+        .map(|clip| ClipDisplay {
+            name: clip.name.clone(),
+            display_string: generate_display_string(clip, unplayable),
+        })
+        .collect();
+    let Ok(selected) = MultiSelect::new("Select clips (space to toggle):", items)
+        .with_page_size(15)
+        .prompt()
+    else {
+        return Ok(false);
+    };
+    if selected.is_empty() {
+        println!("{}", "○ No clips selected.".warning());
+        return Ok(false);
+    }
+
+    let options = vec![
+        "⌫ Delete Local Files",
+        "⌫ Delete Server Copies",
+        "♥ Like",
+        "# Add Tag",
+        "↗ Share",
+    ];
+    let Ok(action) =
+        Select::new(&format!("Action for {} clips:", selected.len()), options).prompt()
+    else {
+        return Ok(false);
+    };
+    let tag = if action == "# Add Tag" {
+        let input = Text::new("› Tag to add:").prompt()?;
+        let tag = input.trim().to_string();
+        if tag.is_empty() {
+            bail!("Tags cannot be empty.");
         }
+        tag
+    } else {
+        String::new()
+    };
 
-        let sort_options = vec![
-            "Date (Newest First)",
-            "Name (A-Z)",
-            "Liked First",
-            "Hosted First",
-            "[Refresh List]",
-            "[Quit]",
-        ];
-
-        let sort_choice = match Select::new("Filter / Sort clips:", sort_options).prompt() {
-            Ok(choice) => choice,
-            Err(_) => break 'main_loop,
-        };
+    let confirmed = Confirm::new(&format!("Apply '{action}' to {} clips?", selected.len()))
+        .with_help_message(match action {
+            "⌫ Delete Local Files" => "Local files are moved to the trash.",
+            "⌫ Delete Server Copies" => "This cannot be undone.",
+            _ => "Clips the action does not apply to are skipped.",
+        })
+        .with_default(false)
+        .prompt()?;
+    if !confirmed {
+        println!("{}", "○ Bulk action cancelled.".warning());
+        return Ok(false);
+    }
 
-        match sort_choice {
-            "Date (Newest First)" => all_clips.sort_by(|a, b| b.created_at.cmp(&a.created_at)),
-            "Name (A-Z)" => {
-                all_clips.sort_by(|a, b| a.name.to_lowercase().cmp(&b.name.to_lowercase()))
+    let mut failed = 0;
+    let mut skipped = 0;
+    for item in &selected {
+        let Some(clip) = clips.iter().find(|c| c.name == item.name) else {
+            skipped += 1;
+            continue;
+        };
+        match apply_bulk_action(action, clip, &tag, markdown, lookup).await {
+            Ok(true) => println!("{} {}", "✔".success(), clip.name),
+            Ok(false) => {
+                skipped += 1;
+                println!("{} {}: skipped", "○".warning(), clip.name);
             }
-            "Liked First" => all_clips.sort_by(|a, b| {
-                b.local_data
-                    .as_ref()
-                    .map_or(false, |d| d.liked)
-                    .cmp(&a.local_data.as_ref().map_or(false, |d| d.liked))
-                    .then_with(|| b.created_at.cmp(&a.created_at))
-            }),
-            "Hosted First" => all_clips.sort_by(|a, b| {
-                b.is_hosted
-                    .cmp(&a.is_hosted)
-                    .then_with(|| b.created_at.cmp(&a.created_at))
-            }),
-            "[Refresh List]" => {
-                println!("{}", "◌ Refreshing clips...".yellow());
-                all_clips = gather_unified_clips().await?;
-                continue 'main_loop;
+            Err(e) => {
+                failed += 1;
+                println!("{} {}: {e:#}", "✗".error(), clip.name);
             }
-            _ => break 'main_loop,
+        }
+    }
+    let succeeded = selected.len() - failed - skipped;
+    if failed > 0 || skipped > 0 {
+        println!(
+            "{}",
+            format!("⚠ {succeeded} of {} clips updated.", selected.len()).warning()
+        );
+    } else {
+        println!(
+            "{}",
+            format!("✔ Updated {succeeded} clips.").success().bold()
+        );
+    }
+    Ok(true)
+}
+
+pub async fn handle_manage(
+    sort: Option<ManageSort>,
+    filename_rules: &FilenameRules,
+    markdown: bool,
+    lookup: ClipLookup,
+) -> Result<()> {
+    let settings = wayclip_core::settings::Settings::load().await?;
+    let mut cli_settings = CliSettings::load().await?;
+    let mut clip_state = ClipState::load().await?;
+    let mut current_sort = sort.unwrap_or(cli_settings.manage_sort);
+    let mut current_filter: Option<ManageFilter> = None;
+
+    println!("\n{}", "◌ Loading clips...".warning());
+    let mut all_clips: Vec<UnifiedClipData> = gather_clips(lookup).await?;
+
+    'main_loop: loop {
+        if all_clips.is_empty() {
+            println!("{}", "○ No clips found.".warning());
+            return Ok(());
         }
 
-        let display_items: Vec<_> = all_clips
+        sort_clips(&mut all_clips, current_sort);
+
+        let mut display_items: Vec<ManageEntry> = all_clips
             .iter()
-            .map(|clip| ClipDisplay {
-                name: clip.name.clone(),
-                display_string: generate_display_string(clip),
+            .filter(|clip| current_filter.as_ref().is_none_or(|f| f.matches(clip)))
+            .map(|clip| {
+                ManageEntry::Clip(ClipDisplay {
+                    name: clip.name.clone(),
+                    display_string: generate_display_string(clip, &clip_state.unplayable),
+                })
             })
             .collect();
+        if let Some(filter) = &current_filter {
+            let message = format!(
+                "○ {} of {} clips match '{filter}'.",
+                display_items.len(),
+                all_clips.len()
+            );
+            println!("{}", message.warning());
+        }
+        display_items.push(ManageEntry::ChangeFilter(current_filter.clone()));
+        if current_filter.is_some() {
+            display_items.push(ManageEntry::ClearFilter);
+        }
+        display_items.push(ManageEntry::ChangeSort(current_sort));
+        display_items.push(ManageEntry::BulkActions);
+        display_items.push(ManageEntry::Refresh);
+        display_items.push(ManageEntry::Quit);
 
         let selected_display_item = match Select::new("Select a clip to manage:", display_items)
             .with_page_size(15)
             .prompt()
         {
-            Ok(item) => item,
-            Err(_) => continue 'main_loop,
+            Ok(ManageEntry::Clip(item)) => item,
+            Ok(ManageEntry::ChangeFilter(_)) => {
+                if let Some(filter) = pick_filter(&all_clips) {
+                    current_filter = Some(filter);
+                }
+                continue 'main_loop;
+            }
+            Ok(ManageEntry::ClearFilter) => {
+                current_filter = None;
+                continue 'main_loop;
+            }
+            Ok(ManageEntry::ChangeSort(_)) => {
+                let sort_options = vec![
+                    SortMenuEntry::Sort(ManageSort::Date),
+                    SortMenuEntry::Sort(ManageSort::Name),
+                    SortMenuEntry::Sort(ManageSort::Liked),
+                    SortMenuEntry::Sort(ManageSort::Hosted),
+                    SortMenuEntry::Sort(ManageSort::Tag),
+                    SortMenuEntry::Search,
+                ];
+                let cursor = sort_options
+                    .iter()
+                    .position(|s| *s == SortMenuEntry::Sort(current_sort))
+                    .unwrap_or(0);
+                let choice = Select::new("Sort clips by:", sort_options)
+                    .with_starting_cursor(cursor)
+                    .prompt();
+                if let Ok(SortMenuEntry::Search) = choice {
+                    if let Ok(query) = Text::new("› Search clip names:").prompt() {
+                        let query = query.trim().to_lowercase();
+                        current_filter = (!query.is_empty()).then_some(ManageFilter::Search(query));
+                    }
+                } else if let Ok(SortMenuEntry::Sort(choice)) = choice {
+                    current_sort = choice;
+                    cli_settings.manage_sort = choice;
+                    if let Err(e) = cli_settings.save().await {
+                        println!(
+                            "{}",
+                            format!("✗ Could not save sort preference: {e:#}").warning()
+                        );
+                    }
+                }
+                continue 'main_loop;
+            }
+            Ok(ManageEntry::BulkActions) => {
+                let visible: Vec<&UnifiedClipData> = all_clips
+                    .iter()
+                    .filter(|clip| current_filter.as_ref().is_none_or(|f| f.matches(clip)))
+                    .collect();
+                if bulk_actions(&visible, &clip_state.unplayable, markdown, lookup).await? {
+                    println!("{}", "◌ Refreshing clips...".warning());
+                    all_clips = gather_clips(lookup).await?;
+                }
+                continue 'main_loop;
+            }
+            Ok(ManageEntry::Refresh) => {
+                println!("{}", "◌ Refreshing clips...".warning());
+                all_clips = gather_clips(lookup).await?;
+                continue 'main_loop;
+            }
+            Ok(ManageEntry::Quit) | Err(_) => break 'main_loop,
         };
 
         let selected_idx = all_clips
@@ -117,13 +454,15 @@ pub async fn handle_manage() -> Result<()> {
             if clip.is_hosted {
                 options.push("⌂ Open URL");
                 options.push("☐ Copy URL");
+                options.push("☐ Copy as Markdown Link");
             }
             if clip.local_path.is_some() {
                 options.push("▷ View Local File");
                 options.push("✎ Rename");
                 options.push("✎ Edit");
                 options.push("⎘ Copy Name");
-                if clip.local_data.as_ref().map_or(false, |d| d.liked) {
+                options.push("⟳ Refresh Metadata");
+                if clip.local_data.as_ref().is_some_and(|d| d.liked) {
                     options.push("♡ Unlike");
                 } else {
                     options.push("♥ Like");
@@ -140,135 +479,201 @@ pub async fn handle_manage() -> Result<()> {
             }
             options.push("← Back to Clip List");
 
-            let action = match Select::new(&format!("Action for '{}':", clip.name.cyan()), options)
-                .prompt()
-            {
-                Ok(choice) => choice,
-                Err(_) => break 'action_loop,
-            };
+            let action =
+                match Select::new(&format!("Action for '{}':", clip.name.accent()), options)
+                    .prompt()
+                {
+                    Ok(choice) => choice,
+                    Err(_) => break 'action_loop,
+                };
 
             match action {
                 "← Back to Clip List" => break 'action_loop,
 
                 "▷ View Local File" => {
-                    if let Err(e) = handle_view(&clip.full_filename, None).await {
-                        println!("{} {}", "✗ Error viewing clip:".red(), e);
+                    if let Err(e) =
+                        handle_view(&clip.full_filename, None, &Playback::default(), lookup).await
+                    {
+                        if is_cancellation(&e) {
+                            println!("{}", "○ Cancelled.".warning());
+                        } else {
+                            println!("{} {}", "✗ Error viewing clip:".error(), e);
+                        }
                     }
                 }
 
                 "♥ Like" | "♡ Unlike" => {
-                    let new_status = !clip.local_data.as_ref().map_or(false, |d| d.liked);
+                    let new_status = !clip.local_data.as_ref().is_some_and(|d| d.liked);
                     match update_liked(&clip.full_filename, new_status).await {
                         Ok(_) => {
                             if let Some(local_data) = clip.local_data.as_mut() {
                                 local_data.liked = new_status;
                             }
-                            println!("{}", "✔ Liked status updated!".green());
+                            println!("{}", "✔ Liked status updated!".success());
                         }
                         Err(e) => {
-                            println!("{}", format!("✗ Failed to update liked status: {e}").red())
+                            println!(
+                                "{}",
+                                format!("✗ Failed to update liked status: {e}").error()
+                            )
                         }
                     }
                 }
 
                 "⌫ Delete Server Copy" | "⌫ Delete Local File" => {
                     let is_server = action == "⌫ Delete Server Copy";
-                    let confirmed = Confirm::new(if is_server {
-                        "Delete server copy?"
+                    let (prompt, help) = if is_server {
+                        ("Delete server copy?", "This cannot be undone.")
                     } else {
-                        "Delete local file?"
-                    })
-                    .with_help_message("This cannot be undone.")
-                    .with_default(false)
-                    .prompt()?;
+                        (
+                            "Move local file to the trash?",
+                            "Restore it with `wayclip trash restore`.",
+                        )
+                    };
+                    let confirmed = Confirm::new(prompt)
+                        .with_help_message(help)
+                        .with_default(false)
+                        .prompt()?;
 
                     if confirmed {
                         let result: Result<()> = if is_server {
-                            let client = api::get_api_client().await?;
-                            api::delete_clip(&client, clip.hosted_id.unwrap())
+                            delete_hosted_clip(clip.hosted_id.unwrap())
                                 .await
                                 .map_err(|e| anyhow!(e))
                         } else {
-                            delete_file(clip.local_path.as_ref().unwrap())
-                                .await
-                                .map_err(|e| anyhow!(e))
+                            move_to_trash(Path::new(clip.local_path.as_ref().unwrap())).await
                         };
 
                         match result {
                             Ok(_) => {
-                                println!("{}", "✔ Operation successful.".green());
+                                println!("{}", "✔ Operation successful.".success());
                                 all_clips.remove(selected_idx);
                                 break_to_main_menu = true;
                             }
-                            Err(e) => println!("✗ Operation failed: {}", e.to_string().red()),
+                            Err(e) => println!("✗ Operation failed: {}", e.to_string().error()),
                         }
                     }
                 }
 
                 "⌂ Open URL" => {
-                    let url = format!("{}/clip/{}", settings.api_url, clip.hosted_id.unwrap());
-                    if opener::open(&url).is_ok() {
-                        println!("○ Opening URL in browser: {}", url.cyan());
-                    } else {
-                        println!("✗ Failed to open URL.");
-                    }
+                    let url = clip_public_url(&settings, &cli_settings, clip.hosted_id.unwrap());
+                    open_url_or_print(&url).await;
                 }
 
                 "☐ Copy URL" => {
                     let public_url =
-                        format!("{}/clip/{}", settings.api_url, clip.hosted_id.unwrap());
-                    match copy_to_clipboard(&public_url).await {
-                        Ok(_) => println!("{}", "✔ Public URL copied!".green()),
-                        Err(e) => println!("{}", format!("✗ Failed to copy URL: {e}").red()),
+                        clip_public_url(&settings, &cli_settings, clip.hosted_id.unwrap());
+                    match copy_to_clipboard(&format_url(&clip.name, &public_url, markdown)).await {
+                        Ok(_) => println!("{}", "✔ Public URL copied!".success()),
+                        Err(e) => println!("{}", format!("✗ Failed to copy URL: {e}").error()),
                     }
                 }
 
+                "☐ Copy as Markdown Link" => {
+                    let public_url =
+                        clip_public_url(&settings, &cli_settings, clip.hosted_id.unwrap());
+                    match copy_to_clipboard(&markdown_link(&clip.name, &public_url)).await {
+                        Ok(_) => println!("{}", "✔ Markdown link copied!".success()),
+                        Err(e) => println!("{}", format!("✗ Failed to copy link: {e}").error()),
+                    }
+                }
+
+                "⟳ Refresh Metadata" => {
+                    let local_path = clip.local_path.as_ref().context("No local path")?.clone();
+                    println!("{}", "◌ Re-probing clip...".warning());
+                    let size = tokio::fs::metadata(&local_path)
+                        .await
+                        .map(|m| m.len())
+                        .unwrap_or(0);
+                    match probe_clip(Path::new(&local_path)).await {
+                        Ok(info) => {
+                            clip_state.unplayable.remove(&clip.name);
+                            match update_clip_metadata(&clip.full_filename, info.duration, size)
+                                .await
+                            {
+                                Ok(_) => println!(
+                                    "{}",
+                                    format!(
+                                        "✔ Metadata refreshed: {:.2}s, {:.2} MB",
+                                        info.duration,
+                                        size as f64 / 1_048_576.0
+                                    )
+                                    .success()
+                                ),
+                                Err(e) => println!(
+                                    "{}",
+                                    format!("✗ Failed to update local data: {e:#}").error()
+                                ),
+                            }
+                        }
+                        Err(e) => {
+                            clip_state.unplayable.insert(clip.name.clone());
+                            println!("{}", format!("✗ Clip could not be probed: {e:#}").error());
+                        }
+                    }
+                    if let Err(e) = clip_state.save().await {
+                        println!(
+                            "{}",
+                            format!("✗ Could not save clip state: {e:#}").warning()
+                        );
+                    }
+                    break_to_main_menu = true;
+                }
+
                 "⎘ Copy Name" => match copy_to_clipboard(&clip.name).await {
-                    Ok(_) => println!("{}", "✔ Name copied!".green()),
-                    Err(e) => println!("{}", format!("✗ Failed to copy name: {e}").red()),
+                    Ok(_) => println!("{}", "✔ Name copied!".success()),
+                    Err(e) => println!("{}", format!("✗ Failed to copy name: {e}").error()),
                 },
 
                 "↗ Share" => {
-                    if let Err(e) = handle_share(&clip.name).await {
-                        println!("{} {}", "✗ Share failed:".red(), e);
+                    if let Err(e) = handle_share(
+                        &clip.name,
+                        &ShareOptions {
+                            markdown,
+                            ..ShareOptions::default()
+                        },
+                        &ShareMetadata::default(),
+                        lookup,
+                    )
+                    .await
+                    {
+                        if is_cancellation(&e) {
+                            println!("{}", "○ Cancelled.".warning());
+                        } else {
+                            println!("{} {}", "✗ Share failed:".error(), e);
+                        }
                     } else {
-                        println!("{}", "◌ Refreshing clip state...".yellow());
-                        if let Some(updated_clip) = gather_unified_clips()
+                        println!("{}", "◌ Refreshing clip state...".warning());
+                        if let Some(updated_clip) = gather_clips(lookup)
                             .await?
                             .into_iter()
                             .find(|c| c.name == clip.name)
                         {
                             *clip = updated_clip;
                         }
-                        println!("{}", "✔ Clip is now hosted.".green());
+                        println!("{}", "✔ Clip is now hosted.".success());
                     }
                 }
 
                 "✎ Rename" => {
-                    let local_path_str = clip.local_path.as_ref().context("No local path")?.clone();
                     let new_name_input = Text::new("› Enter new name:")
                         .with_initial_value(&clip.name)
                         .prompt()?;
 
-                    match sanitize_and_validate_filename_stem(&new_name_input) {
+                    match sanitize_and_validate_filename_stem(&new_name_input, filename_rules) {
                         Ok(new_stem) if new_stem != clip.name => {
-                            let ext = Path::new(&local_path_str)
-                                .extension()
-                                .and_then(|s| s.to_str())
-                                .unwrap_or("mp4");
-                            let new_full = format!("{new_stem}.{ext}");
-                            match rename_all_entries(&local_path_str, &new_full).await {
-                                Ok(_) => {
-                                    println!("✔ Renamed to '{}'", new_full.green());
-                                    println!("{}", "◌ Refreshing clip list...".yellow());
-                                    all_clips = gather_unified_clips().await?;
+                            match rename_clip(clip, &new_stem, filename_rules).await {
+                                Ok(new_full) => {
+                                    println!("✔ Renamed to '{}'", new_full.success());
+                                    println!("{}", "◌ Refreshing clip list...".warning());
+                                    all_clips = gather_clips(lookup).await?;
                                     break_to_main_menu = true;
                                 }
-                                Err(e) => println!("✗ Failed to rename: {}", e.to_string().red()),
+                                Err(e) => println!("✗ Failed to rename: {}", e.to_string().error()),
                             }
                         }
-                        Ok(_) => println!("{}", "○ Rename cancelled.".yellow()),
-                        Err(e) => println!("✗ Invalid name: {}", e.to_string().red()),
+                        Ok(_) => println!("{}", "○ Rename cancelled.".warning()),
+                        Err(e) => println!("✗ Invalid name: {}", e.to_string().error()),
                     }
                 }
 
@@ -281,14 +686,32 @@ pub async fn handle_manage() -> Result<()> {
                         .with_default(false)
                         .prompt()?;
 
-                    if let Err(e) =
-                        handle_edit(&clip.full_filename, &start_time, &end_time, &disable_audio)
+                    let result = match TrimRange::from_times(&start_time, &end_time) {
+                        Ok(range) => {
+                            handle_edit(
+                                &clip.full_filename,
+                                &range,
+                                None,
+                                &EncodeOptions {
+                                    disable_audio,
+                                    ..EncodeOptions::default()
+                                },
+                                filename_rules,
+                                lookup,
+                            )
                             .await
-                    {
-                        println!("{} {}", "✗ Edit failed:".red(), e);
+                        }
+                        Err(e) => Err(e),
+                    };
+                    if let Err(e) = result {
+                        if is_cancellation(&e) {
+                            println!("{}", "○ Cancelled.".warning());
+                        } else {
+                            println!("{} {}", "✗ Edit failed:".error(), e);
+                        }
                     } else {
-                        println!("{}", "◌ Refreshing clip list...".yellow());
-                        all_clips = gather_unified_clips().await?;
+                        println!("{}", "◌ Refreshing clip list...".warning());
+                        all_clips = gather_clips(lookup).await?;
                         break_to_main_menu = true;
                     }
                 }
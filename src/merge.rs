@@ -0,0 +1,193 @@
+use crate::bumper::concat_reencode;
+use crate::encrypt::private_temp_path;
+use crate::probe::{ProbeInfo, probe_clip};
+use crate::theme::Themed;
+use crate::unified_clip::{ClipLookup, find_unified_clip};
+use crate::validate::{FilenameRules, fit_filename_stem, sanitize_and_validate_filename_stem};
+use anyhow::{Context, Result, bail};
+use colored::*;
+use std::path::{Path, PathBuf};
+use tokio::process::Command;
+
+struct MergeInput {
+    name: String,
+    path: PathBuf,
+    info: ProbeInfo,
+}
+
+fn stream_mismatch(first: &ProbeInfo, other: &ProbeInfo) -> Option<String> {
+    if first.video_codec != other.video_codec {
+        return Some(format!(
+            "video codec {} vs {}",
+            first.video_codec.as_deref().unwrap_or("unknown"),
+            other.video_codec.as_deref().unwrap_or("unknown")
+        ));
+    }
+    if (first.width, first.height) != (other.width, other.height) {
+        return Some(format!(
+            "resolution {}x{} vs {}x{}",
+            first.width.unwrap_or(0),
+            first.height.unwrap_or(0),
+            other.width.unwrap_or(0),
+            other.height.unwrap_or(0)
+        ));
+    }
+    let fps_differs = match (first.frame_rate, other.frame_rate) {
+        (Some(a), Some(b)) => (a - b).abs() > 0.01,
+        (a, b) => a.is_some() != b.is_some(),
+    };
+    if fps_differs {
+        return Some("frame rate".to_string());
+    }
+    if first.has_audio != other.has_audio || first.sample_rate != other.sample_rate {
+        return Some("audio layout".to_string());
+    }
+    None
+}
+
+fn concat_list_entry(path: &Path) -> String {
+    format!(
+        "file '{}'\n",
+        path.display().to_string().replace('\'', "'\\''")
+    )
+}
+
+async fn concat_copy(inputs: &[MergeInput], output: &Path) -> Result<()> {
+    let list_path = private_temp_path("txt");
+    let list: String = inputs
+        .iter()
+        .map(|input| concat_list_entry(&input.path))
+        .collect();
+    tokio::fs::write(&list_path, list)
+        .await
+        .context("Failed to write the concat list")?;
+
+    let output_result = Command::new("ffmpeg")
+        .arg("-f")
+        .arg("concat")
+        .arg("-safe")
+        .arg("0")
+        .arg("-i")
+        .arg(&list_path)
+        .arg("-map")
+        .arg("0")
+        .arg("-c")
+        .arg("copy")
+        .arg(output)
+        .output()
+        .await;
+    let _ = tokio::fs::remove_file(&list_path).await;
+    let output_result =
+        output_result.context("Failed to execute ffmpeg. Is it installed and in your PATH?")?;
+
+    if !output_result.status.success() {
+        let stderr = String::from_utf8_lossy(&output_result.stderr);
+        bail!(
+            "ffmpeg failed with status: {}\n{}",
+            output_result.status,
+            stderr
+        );
+    }
+    Ok(())
+}
+
+pub async fn handle_merge(
+    names: &[String],
+    output: Option<&str>,
+    reencode: bool,
+    filename_rules: &FilenameRules,
+    lookup: ClipLookup,
+) -> Result<()> {
+    if names.len() < 2 {
+        bail!("Specify at least two clips to merge.");
+    }
+
+    println!("{}", "◌ Checking clips...".warning());
+    let mut inputs = Vec::new();
+    for name in names {
+        let clip = find_unified_clip(name, lookup).await?;
+        let local_path = clip
+            .local_path
+            .context(format!("Clip '{}' not found locally.", clip.name))?;
+        let path = PathBuf::from(local_path);
+        let info = probe_clip(&path)
+            .await
+            .context(format!("Clip '{}' is not playable", clip.name))?;
+        inputs.push(MergeInput {
+            name: clip.name,
+            path,
+            info,
+        });
+    }
+
+    let first = &inputs[0];
+    let mismatch = inputs[1..].iter().find_map(|input| {
+        stream_mismatch(&first.info, &input.info).map(|reason| (input.name.clone(), reason))
+    });
+    if let Some((name, reason)) = &mismatch
+        && !reencode
+    {
+        bail!(
+            "'{}' and '{name}' cannot be joined without re-encoding ({reason} differs). Use --reencode to convert them to a common format.",
+            first.name
+        );
+    }
+
+    let ext = if mismatch.is_some() {
+        "mp4".to_string()
+    } else {
+        first
+            .path
+            .extension()
+            .and_then(|e| e.to_str())
+            .unwrap_or("mp4")
+            .to_string()
+    };
+    let stem = output
+        .map(str::to_string)
+        .unwrap_or_else(|| format!("{}_merged", first.name));
+    let stem = fit_filename_stem(
+        &sanitize_and_validate_filename_stem(&stem, filename_rules)?,
+        &ext,
+        filename_rules,
+    )?;
+    let output_path = first.path.with_file_name(format!("{stem}.{ext}"));
+    if output_path.exists() {
+        bail!(
+            "'{}' already exists. Choose another name with --output.",
+            output_path.display()
+        );
+    }
+    let temp_output_path = output_path.with_extension(format!("tmp.{ext}"));
+
+    println!(
+        "{}",
+        format!("◌ Merging {} clips...", inputs.len()).warning()
+    );
+    let result = if mismatch.is_some() {
+        let (Some(width), Some(height)) = (first.info.width, first.info.height) else {
+            bail!("Could not read the resolution of '{}'.", first.name);
+        };
+        let fps = first.info.frame_rate.unwrap_or(60.0);
+        let with_audio = inputs.iter().any(|input| input.info.has_audio);
+        let paths: Vec<&Path> = inputs.iter().map(|input| input.path.as_path()).collect();
+        concat_reencode(&paths, &temp_output_path, width, height, fps, with_audio).await
+    } else {
+        concat_copy(&inputs, &temp_output_path).await
+    };
+    if let Err(e) = result {
+        let _ = tokio::fs::remove_file(&temp_output_path).await;
+        return Err(e);
+    }
+    tokio::fs::rename(&temp_output_path, &output_path)
+        .await
+        .context("Failed to save merged clip")?;
+
+    println!(
+        "{}",
+        format!("✔ Merged {} clips into '{stem}'", inputs.len())
+            .success()
+            .bold()
+    );
+    Ok(())
+}
@@ -1,6 +1,8 @@
-use clap::{Parser, Subcommand};
+use clap::{Parser, Subcommand, ValueEnum};
+use clap_complete::Shell;
 use std::collections::HashMap;
 use std::fmt;
+use std::path::PathBuf;
 
 #[derive(Clone)]
 pub struct ClipDisplay {
@@ -25,6 +27,31 @@ pub struct Cli {
     pub command: Commands,
     #[arg(long, hide = true)]
     pub debug: bool,
+    #[arg(
+        long,
+        global = true,
+        help = "Skip the server and work with local clips only"
+    )]
+    pub offline: bool,
+    #[arg(
+        long,
+        global = true,
+        help = "Stable tab-separated output for scripts (list, me)",
+        long_help = "Stable tab-separated output for scripts, one record per line. The field order is fixed:\n  list:          name, created (RFC 3339), size (bytes), length (seconds), liked (0/1), corrupt (0/1)\n  list --server: name, hosted ID, uploaded (RFC 3339), size (bytes), local copy (0/1)\n  me:            username, email, tier, two-factor enabled (0/1), clip count, storage used (bytes), storage limit (bytes)"
+    )]
+    pub porcelain: bool,
+    #[arg(
+        long,
+        global = true,
+        help = "Match clip names by substring, prompting when several match"
+    )]
+    pub fuzzy: bool,
+    #[arg(
+        long,
+        global = true,
+        help = "Print and copy clip URLs as Markdown links"
+    )]
+    pub markdown: bool,
 }
 
 #[derive(Subcommand)]
@@ -33,7 +60,34 @@ pub enum Commands {
         #[command(subcommand)]
         action: DaemonCommand,
     },
-    Save,
+    Alias {
+        #[command(subcommand)]
+        action: AliasCommand,
+    },
+    Save {
+        #[arg(short = 'm', long = "monitor", help = "Capture only this output")]
+        monitor: Option<String>,
+        #[arg(
+            short = 'r',
+            long = "region",
+            value_name = "WxH+X+Y",
+            help = "Capture only this region"
+        )]
+        region: Option<String>,
+        #[arg(
+            short = 'n',
+            long = "name",
+            help = "Rename the saved clip to this name (without extension)"
+        )]
+        name: Option<String>,
+        #[arg(
+            long = "tag",
+            value_name = "TAG",
+            help = "Tag the saved clip (repeatable)"
+        )]
+        tags: Vec<String>,
+    },
+    Watch,
     List {
         #[arg(short = 't', long = "timestamp")]
         timestamp: bool,
@@ -45,36 +99,332 @@ pub enum Commands {
         size: bool,
         #[arg(short = 'e', long = "extra")]
         extra: bool,
+        #[arg(short = 'f', long = "format", value_enum, default_value_t = ListFormat::Table)]
+        format: ListFormat,
+        #[arg(
+            long = "server",
+            help = "List every clip hosted on the server, marking which exist locally"
+        )]
+        server: bool,
+        #[arg(
+            long = "tag",
+            value_name = "TAG",
+            conflicts_with = "server",
+            help = "Only list clips with this tag"
+        )]
+        tag: Option<String>,
+        #[arg(
+            long = "json",
+            conflicts_with = "server",
+            help = "Print the clip inventory as JSON"
+        )]
+        json: bool,
+    },
+    Manage {
+        #[arg(short = 's', long = "sort", value_enum)]
+        sort: Option<ManageSort>,
     },
-    Manage,
     Config {
         #[arg(short = 'e', long = "editor")]
         editor: Option<String>,
+        #[command(subcommand)]
+        action: Option<ConfigCommand>,
     },
     View {
         name: String,
-        #[arg(short = 'p', long = "player")]
+        #[arg(
+            short = 'p',
+            long = "player",
+            help = "Player command, split on whitespace so arguments can be included (e.g. \"vlc --fullscreen\"); defaults to default_player in cli-settings.json, then mpv"
+        )]
         player: Option<String>,
+        #[arg(short = 'm', long = "mute", help = "Play the clip without sound")]
+        mute: bool,
+        #[arg(
+            long = "loop-start",
+            requires = "loop_end",
+            help = "Start of a segment to loop (e.g., 5.5 or 00:00:05)"
+        )]
+        loop_start: Option<String>,
+        #[arg(
+            long = "loop-end",
+            requires = "loop_start",
+            help = "End of the looped segment"
+        )]
+        loop_end: Option<String>,
+        #[arg(
+            long = "loop",
+            conflicts_with = "loop_start",
+            help = "Loop the whole clip until the player is closed"
+        )]
+        repeat: bool,
+        #[arg(
+            long = "seek",
+            value_name = "TIME",
+            conflicts_with = "loop_start",
+            help = "Start playback at this time (e.g., 5.5 or 00:00:05)"
+        )]
+        seek: Option<String>,
     },
     Delete {
         name: String,
+        #[arg(
+            long = "server-only",
+            conflicts_with = "local_only",
+            help = "Delete only the hosted copy, without prompting"
+        )]
+        server_only: bool,
+        #[arg(
+            long = "local-only",
+            help = "Delete only the local file, without prompting"
+        )]
+        local_only: bool,
+        #[arg(
+            short = 'y',
+            long = "yes",
+            help = "Delete both the hosted copy and the local file without prompting"
+        )]
+        yes: bool,
+        #[arg(
+            long = "permanent",
+            help = "Delete the local file for good instead of moving it to the trash"
+        )]
+        permanent: bool,
+    },
+    Trash {
+        #[command(subcommand)]
+        action: TrashCommand,
+    },
+    Tag {
+        #[command(subcommand)]
+        action: TagCommand,
+    },
+    Prune {
+        #[arg(help = "Clip name or glob pattern (e.g. 'raid_*') to match")]
+        pattern: Option<String>,
+        #[arg(
+            long = "older-than",
+            value_name = "AGE",
+            value_parser = crate::prune::parse_age,
+            help = "Only match clips created more than AGE ago using min, h, d or w (e.g. 45min, 12h, 30d, 2w)"
+        )]
+        older_than: Option<chrono::TimeDelta>,
+        #[arg(
+            long = "dry-run",
+            help = "List the matching clips without deleting them"
+        )]
+        dry_run: bool,
+        #[arg(
+            short = 'y',
+            long = "yes",
+            help = "Remove the matching clips without asking for confirmation"
+        )]
+        yes: bool,
+        #[arg(
+            long = "permanent",
+            help = "Delete the local files for good instead of moving them to the trash"
+        )]
+        permanent: bool,
     },
     Rename {
         name: String,
+        #[arg(
+            long = "truncate",
+            help = "Shorten names that exceed the maximum filename length instead of failing"
+        )]
+        truncate: bool,
     },
     Edit {
         name: String,
-        start_time: String,
-        end_time: String,
+        #[arg(required_unless_present_any = ["from_chapter", "interactive_trim"])]
+        start_time: Option<String>,
+        #[arg(required_unless_present_any = ["from_chapter", "interactive_trim"])]
+        end_time: Option<String>,
         #[arg(default_value_t = false)]
         disable_audio: bool,
+        #[arg(
+            long = "also",
+            value_name = "NAME",
+            help = "Additional clip names or glob patterns to trim with the same range"
+        )]
+        also: Vec<String>,
+        #[arg(
+            long = "output-ext",
+            help = "Container extension for the output (defaults to the source's)"
+        )]
+        output_ext: Option<String>,
+        #[arg(
+            long = "from-chapter",
+            value_name = "N",
+            requires = "to_chapter",
+            conflicts_with_all = ["start_time", "end_time"],
+            help = "Trim from the start of chapter N (1-based) instead of a timestamp"
+        )]
+        from_chapter: Option<usize>,
+        #[arg(
+            long = "to-chapter",
+            value_name = "M",
+            requires = "from_chapter",
+            help = "Trim to the end of chapter M (1-based)"
+        )]
+        to_chapter: Option<usize>,
+        #[arg(
+            long = "speed",
+            help = "Playback speed multiplier for the output (0.25-4.0); re-encodes the clip (VP9/Opus for .webm, H.264/AAC otherwise)"
+        )]
+        speed: Option<f64>,
+        #[arg(
+            long = "preserve-pitch",
+            overrides_with = "no_preserve_pitch",
+            help = "Keep the original audio pitch when changing speed (default)"
+        )]
+        preserve_pitch: bool,
+        #[arg(
+            long = "no-preserve-pitch",
+            overrides_with = "preserve_pitch",
+            requires = "speed",
+            help = "Let the audio pitch shift with the speed change"
+        )]
+        no_preserve_pitch: bool,
+        #[arg(
+            long = "interactive-trim",
+            conflicts_with_all = ["start_time", "end_time", "from_chapter"],
+            help = "Pick the in/out points by stepping frame by frame, with a looped preview"
+        )]
+        interactive_trim: bool,
+        #[arg(
+            long = "resume",
+            help = "Skip clips finished by a previous interrupted batch edit"
+        )]
+        resume: bool,
+        #[arg(
+            long = "force-copy",
+            conflicts_with_all = ["speed", "reencode"],
+            help = "Always stream-copy, even for codecs that do not trim cleanly"
+        )]
+        force_copy: bool,
+        #[arg(
+            long = "reencode",
+            help = "Re-encode the video so cuts are frame-accurate instead of landing on keyframes"
+        )]
+        reencode: bool,
+        #[arg(
+            long = "crf",
+            default_value_t = 18,
+            requires = "reencode",
+            value_parser = clap::value_parser!(u8).range(0..=51),
+            help = "Quality for --reencode (0-51, lower is better)"
+        )]
+        crf: u8,
+        #[arg(
+            long = "preset",
+            value_enum,
+            default_value_t = EncodePreset::Medium,
+            requires = "reencode",
+            help = "Encoder speed/quality trade-off for --reencode"
+        )]
+        preset: EncodePreset,
+        #[arg(
+            long = "normalize",
+            conflicts_with = "disable_audio",
+            help = "Normalize audio loudness (EBU R128); re-encodes only the audio, so cannot be combined with disabling audio"
+        )]
+        normalize: bool,
+        #[arg(
+            long = "target-lufs",
+            value_name = "LUFS",
+            default_value_t = -16.0,
+            allow_negative_numbers = true,
+            requires = "normalize",
+            help = "Integrated loudness target for --normalize"
+        )]
+        target_lufs: f64,
+        #[arg(
+            long = "gate-mic",
+            conflicts_with = "disable_audio",
+            help = "Silence the microphone track (the second audio track) between speech; game audio is untouched"
+        )]
+        gate_mic: bool,
+        #[arg(
+            long = "concat-intro",
+            value_name = "PATH",
+            num_args = 0..=1,
+            help = "Prepend an intro video, scaled to the clip (defaults to intro_path in cli-settings.json)"
+        )]
+        concat_intro: Option<Option<PathBuf>>,
+        #[arg(
+            long = "concat-outro",
+            value_name = "PATH",
+            num_args = 0..=1,
+            help = "Append an outro video, scaled to the clip (defaults to outro_path in cli-settings.json)"
+        )]
+        concat_outro: Option<Option<PathBuf>>,
+        #[arg(
+            long = "notify",
+            help = "Send a desktop notification when the operation finishes"
+        )]
+        notify: bool,
+        #[arg(
+            long = "truncate",
+            help = "Shorten names that exceed the maximum filename length instead of failing"
+        )]
+        truncate: bool,
     },
     Login {
         #[arg(short = 'b', long = "browser")]
         browser: Option<String>,
+        #[arg(
+            short = 't',
+            long = "timeout",
+            default_value_t = 120,
+            value_parser = clap::value_parser!(u64).range(1..),
+            help = "Seconds to wait for the browser login callback"
+        )]
+        timeout: u64,
+        #[arg(
+            long = "check",
+            help = "Only check whether the stored token is valid (exit 0 if it is)"
+        )]
+        check: bool,
+        #[arg(
+            short = 'v',
+            long = "verbose",
+            requires = "check",
+            help = "Print a short status line with --check"
+        )]
+        verbose: bool,
+        #[arg(
+            long = "token",
+            conflicts_with_all = ["check", "email", "browser"],
+            help = "Store this session token directly instead of logging in interactively"
+        )]
+        token: Option<String>,
+        #[arg(
+            long = "email",
+            conflicts_with_all = ["check", "browser"],
+            help = "Log in with email and password without the provider menu"
+        )]
+        email: Option<String>,
+        #[arg(
+            long = "password",
+            requires = "email",
+            help = "Password for --email (prompted for if omitted, keeping it out of shell history)"
+        )]
+        password: Option<String>,
     },
     Logout,
-    Me,
+    Me {
+        #[arg(
+            long = "fresh",
+            help = "Bypass the cached profile and fetch it from the server"
+        )]
+        fresh: bool,
+        #[arg(
+            long = "json",
+            help = "Print the profile as JSON (errors are JSON objects too)"
+        )]
+        json: bool,
+    },
     #[command(name = "2fa")]
     TwoFactorAuth {
         #[command(subcommand)]
@@ -83,11 +433,98 @@ pub enum Commands {
     Share {
         #[arg(help = "Name of the clip to share")]
         name: String,
+        #[arg(
+            long = "anonymous",
+            help = "Upload without linking the clip to your account"
+        )]
+        anonymous: bool,
+        #[arg(
+            short = 'u',
+            long = "uploader",
+            conflicts_with = "anonymous",
+            help = "Upload to a custom host configured under \"uploaders\" in cli-settings.json"
+        )]
+        uploader: Option<String>,
+        #[arg(
+            long = "thumbnail-at",
+            value_name = "TIME",
+            conflicts_with_all = ["anonymous", "uploader"],
+            help = "Use the frame at this time as the shared clip's thumbnail"
+        )]
+        thumbnail_at: Option<String>,
+        #[arg(
+            long = "replace",
+            value_name = "HOSTED_NAME",
+            conflicts_with_all = ["anonymous", "uploader"],
+            help = "Upload over an existing hosted clip, keeping its public URL"
+        )]
+        replace: Option<String>,
+        #[arg(
+            long = "password",
+            conflicts_with_all = ["anonymous", "uploader"],
+            help = "Require viewers to enter this password to watch the shared clip"
+        )]
+        password: Option<String>,
+        #[arg(
+            long = "title",
+            conflicts_with_all = ["anonymous", "uploader", "replace"],
+            help = "Title shown on the clip's public page"
+        )]
+        title: Option<String>,
+        #[arg(
+            long = "description",
+            conflicts_with_all = ["anonymous", "uploader", "replace"],
+            help = "Description shown on the clip's public page"
+        )]
+        description: Option<String>,
+        #[arg(
+            long = "visibility",
+            value_enum,
+            conflicts_with_all = ["anonymous", "uploader", "replace"],
+            help = "Who can see the clip (server default when omitted)"
+        )]
+        visibility: Option<ShareVisibility>,
+        #[arg(
+            long = "retries",
+            value_name = "N",
+            default_value_t = crate::upload::DEFAULT_UPLOAD_RETRIES,
+            conflicts_with_all = ["anonymous", "uploader"],
+            help = "Retry timeouts and server errors up to N times with exponential backoff"
+        )]
+        retries: u32,
+        #[arg(
+            long = "notify",
+            help = "Send a desktop notification when the operation finishes"
+        )]
+        notify: bool,
     },
     Like {
         #[arg(help = "Name of the local clip to like/unlike")]
         name: String,
     },
+    Completions {
+        #[arg(value_enum, help = "Shell to generate the completion script for")]
+        shell: Shell,
+    },
+    Search {
+        #[arg(help = "Text to fuzzy-match against clip names")]
+        query: String,
+        #[arg(
+            short = 'n',
+            long = "limit",
+            default_value_t = 20,
+            help = "Show at most this many results"
+        )]
+        limit: usize,
+        #[arg(long = "json", help = "Print the ranked results as JSON")]
+        json: bool,
+    },
+    Resolve {
+        #[arg(help = "Clip name, alias or partial name (with --fuzzy) to resolve")]
+        name: String,
+        #[arg(long = "stem", help = "Print the clip name without its file extension")]
+        stem: bool,
+    },
     Url {
         #[arg(help = "Name of the hosted clip to get the URL for")]
         name: String,
@@ -96,13 +533,409 @@ pub enum Commands {
         #[arg(help = "Name of the hosted clip to open in a browser")]
         name: String,
     },
+    Import {
+        #[arg(required = true, help = "Video files or directories to import")]
+        paths: Vec<PathBuf>,
+        #[arg(
+            long = "strip-prefix",
+            help = "Text to strip from the start of each name"
+        )]
+        strip_prefix: Option<String>,
+        #[arg(
+            long = "strip-suffix",
+            help = "Text to strip from the end of each name"
+        )]
+        strip_suffix: Option<String>,
+        #[arg(
+            long = "regex",
+            help = "Treat --strip-prefix/--strip-suffix as regular expressions"
+        )]
+        regex: bool,
+        #[arg(
+            long = "sidecar",
+            help = "Restore liked status, tags and hosted ID from a <name>.json sidecar next to each source"
+        )]
+        sidecar: bool,
+        #[arg(
+            long = "tag",
+            value_name = "TAG",
+            help = "Tag every imported clip (repeatable)"
+        )]
+        tag: Vec<String>,
+        #[arg(
+            long = "truncate",
+            help = "Shorten names that exceed the maximum filename length instead of failing"
+        )]
+        truncate: bool,
+    },
+    Ingest {
+        #[arg(help = "Directory an external recorder writes clips into")]
+        dir: PathBuf,
+        #[arg(
+            long = "settle",
+            default_value_t = 2,
+            help = "Seconds a new file must go without changes before it is imported"
+        )]
+        settle: u64,
+    },
+    Verify,
+    Doctor {
+        #[arg(long = "json", help = "Print the report as JSON")]
+        json: bool,
+        #[arg(
+            long = "player",
+            help = "Check this player command instead of default_player from cli-settings.json"
+        )]
+        player: Option<String>,
+    },
+    CompareRender {
+        #[arg(help = "Clip shown on the left (or top)")]
+        a: String,
+        #[arg(help = "Clip shown on the right (or bottom)")]
+        b: String,
+        #[arg(help = "Path of the comparison video to write")]
+        output: PathBuf,
+        #[arg(long = "vertical", help = "Stack the clips vertically instead")]
+        vertical: bool,
+    },
+    Info {
+        #[arg(help = "Name of the local clip to inspect")]
+        name: String,
+        #[arg(
+            long = "keyframes",
+            help = "List keyframe timestamps (the cut points for fast stream-copy trims)"
+        )]
+        keyframes: bool,
+    },
+    Reveal {
+        #[arg(help = "Name of the local clip to show in the file manager")]
+        name: String,
+    },
+    Download {
+        #[arg(
+            required_unless_present = "all",
+            help = "Name of the hosted clip to download"
+        )]
+        name: Option<String>,
+        #[arg(short = 'a', long = "all", conflicts_with = "name")]
+        all: bool,
+        #[arg(
+            short = 'f',
+            long = "force",
+            help = "Overwrite or re-download existing local files"
+        )]
+        force: bool,
+        #[arg(
+            short = 'j',
+            long = "jobs",
+            default_value_t = 3,
+            value_parser = clap::builder::RangedU64ValueParser::<usize>::new().range(1..)
+        )]
+        jobs: usize,
+        #[arg(
+            long = "resume",
+            requires = "all",
+            help = "Skip clips finished by a previous interrupted run"
+        )]
+        resume: bool,
+        #[arg(
+            long = "notify",
+            help = "Send a desktop notification when the operation finishes"
+        )]
+        notify: bool,
+    },
+    Sidecar {
+        #[arg(
+            required_unless_present = "all",
+            help = "Name of the clip to write a <name>.json sidecar (liked status, tags, hosted ID) for"
+        )]
+        name: Option<String>,
+        #[arg(short = 'a', long = "all", conflicts_with = "name")]
+        all: bool,
+    },
+    Analytics {
+        #[arg(help = "Name of the hosted clip to show analytics for")]
+        name: String,
+    },
+    SetThumbnail {
+        #[arg(help = "Name of the hosted clip to set the thumbnail for")]
+        name: String,
+        #[arg(help = "Time of the frame to use (e.g., 5.5 or 00:00:05)")]
+        at: String,
+    },
+    SetPassword {
+        #[arg(help = "Name of the hosted clip to protect")]
+        name: String,
+        #[arg(
+            required_unless_present = "remove_password",
+            help = "Password viewers must enter to watch the clip"
+        )]
+        password: Option<String>,
+        #[arg(
+            long = "remove-password",
+            conflicts_with = "password",
+            help = "Remove the password so anyone with the link can watch"
+        )]
+        remove_password: bool,
+    },
+    Encrypt {
+        #[arg(help = "Name of the local clip to encrypt")]
+        name: String,
+    },
+    Decrypt {
+        #[arg(help = "Name of the encrypted clip to decrypt")]
+        name: String,
+    },
+    Thumbnail {
+        #[arg(help = "Names of the local clips to generate thumbnails for")]
+        names: Vec<String>,
+        #[arg(short = 'a', long = "all", conflicts_with = "names")]
+        all: bool,
+        #[arg(short = 'o', long = "output-dir")]
+        output_dir: Option<PathBuf>,
+        #[arg(
+            long = "output",
+            conflicts_with_all = ["all", "output_dir"],
+            help = "Image file to write (.png or .jpg) when generating a single thumbnail"
+        )]
+        output: Option<PathBuf>,
+        #[arg(
+            long = "at",
+            help = "Time of the frame to extract (defaults to the middle of the clip)"
+        )]
+        at: Option<String>,
+        #[arg(short = 'j', long = "jobs", default_value_t = 4)]
+        jobs: usize,
+    },
+    Merge {
+        #[arg(
+            num_args = 2..,
+            required = true,
+            help = "Names of the local clips to join, in order"
+        )]
+        names: Vec<String>,
+        #[arg(
+            short = 'o',
+            long = "output",
+            help = "Name for the merged clip (without extension)"
+        )]
+        output: Option<String>,
+        #[arg(
+            long = "reencode",
+            help = "Re-encode clips whose codecs, resolution or frame rate differ"
+        )]
+        reencode: bool,
+    },
+    Gif {
+        #[arg(help = "Name of the local clip to export as a GIF")]
+        name: String,
+        #[arg(long = "start", help = "Start time (e.g., 5.5 or 00:00:05)")]
+        start: Option<String>,
+        #[arg(long = "end", help = "End time (e.g., 10 or 00:00:10)")]
+        end: Option<String>,
+        #[arg(
+            long = "fps",
+            default_value_t = 15,
+            value_parser = clap::value_parser!(u32).range(1..)
+        )]
+        fps: u32,
+        #[arg(
+            long = "width",
+            default_value_t = 480,
+            value_parser = clap::value_parser!(u32).range(1..),
+            help = "Output width in pixels"
+        )]
+        width: u32,
+        #[arg(short = 'f', long = "force", help = "Overwrite an existing GIF")]
+        force: bool,
+    },
+    Batch {
+        #[arg(help = "JSON file containing an array of operations")]
+        file: PathBuf,
+        #[arg(
+            long = "continue-on-error",
+            help = "Keep running the remaining operations after a failure"
+        )]
+        continue_on_error: bool,
+    },
+    AddAudio {
+        #[arg(help = "Name of the local clip to add audio to")]
+        name: String,
+        #[arg(help = "Audio file to mux onto the clip")]
+        audio: PathBuf,
+        #[arg(
+            short = 'k',
+            long = "keep-original",
+            help = "Mix the new audio with the clip's original audio instead of replacing it"
+        )]
+        keep_original: bool,
+        #[arg(long = "original-volume", default_value_t = 1.0)]
+        original_volume: f32,
+        #[arg(long = "audio-volume", default_value_t = 1.0)]
+        audio_volume: f32,
+    },
     Audio,
 }
 
+impl Commands {
+    pub fn uses_data_file(&self) -> bool {
+        !matches!(
+            self,
+            Commands::Login { .. }
+                | Commands::Logout
+                | Commands::Me { .. }
+                | Commands::TwoFactorAuth { .. }
+                | Commands::Completions { .. }
+                | Commands::Doctor { .. }
+                | Commands::Daemon { .. }
+                | Commands::Audio
+        )
+    }
+
+    pub fn prints_json(&self) -> bool {
+        matches!(
+            self,
+            Commands::List { json: true, .. }
+                | Commands::Me { json: true, .. }
+                | Commands::Search { json: true, .. }
+                | Commands::Doctor { json: true, .. }
+        )
+    }
+}
+
+#[derive(
+    ValueEnum, serde::Serialize, serde::Deserialize, Clone, Copy, Debug, Default, PartialEq,
+)]
+#[serde(rename_all = "kebab-case")]
+pub enum ManageSort {
+    #[default]
+    Date,
+    Name,
+    Liked,
+    Hosted,
+    Tag,
+}
+
+impl fmt::Display for ManageSort {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let label = match self {
+            ManageSort::Date => "Date (Newest First)",
+            ManageSort::Name => "Name (A-Z)",
+            ManageSort::Liked => "Liked First",
+            ManageSort::Hosted => "Hosted First",
+            ManageSort::Tag => "Tag (A-Z)",
+        };
+        write!(f, "{label}")
+    }
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq)]
+pub enum ListFormat {
+    Table,
+    Compact,
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq)]
+pub enum EncodePreset {
+    Ultrafast,
+    Superfast,
+    Veryfast,
+    Faster,
+    Fast,
+    Medium,
+    Slow,
+    Slower,
+    Veryslow,
+}
+
+impl fmt::Display for EncodePreset {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            EncodePreset::Ultrafast => "ultrafast",
+            EncodePreset::Superfast => "superfast",
+            EncodePreset::Veryfast => "veryfast",
+            EncodePreset::Faster => "faster",
+            EncodePreset::Fast => "fast",
+            EncodePreset::Medium => "medium",
+            EncodePreset::Slow => "slow",
+            EncodePreset::Slower => "slower",
+            EncodePreset::Veryslow => "veryslow",
+        };
+        write!(f, "{name}")
+    }
+}
+
+impl EncodePreset {
+    pub fn vpx_cpu_used(self) -> u8 {
+        match self {
+            EncodePreset::Ultrafast => 8,
+            EncodePreset::Superfast => 7,
+            EncodePreset::Veryfast => 6,
+            EncodePreset::Faster => 5,
+            EncodePreset::Fast => 4,
+            EncodePreset::Medium => 3,
+            EncodePreset::Slow => 2,
+            EncodePreset::Slower => 1,
+            EncodePreset::Veryslow => 0,
+        }
+    }
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq)]
+pub enum ShareVisibility {
+    Public,
+    Unlisted,
+    Private,
+}
+
+impl fmt::Display for ShareVisibility {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ShareVisibility::Public => write!(f, "public"),
+            ShareVisibility::Unlisted => write!(f, "unlisted"),
+            ShareVisibility::Private => write!(f, "private"),
+        }
+    }
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq)]
+pub enum InitSystem {
+    Systemd,
+    Openrc,
+    Runit,
+    Xdg,
+}
+
 #[derive(Subcommand)]
 pub enum AutostartAction {
-    On,
-    Off,
+    On {
+        #[arg(
+            long = "init",
+            value_enum,
+            help = "Autostart backend to use (defaults to systemd when present, otherwise xdg)"
+        )]
+        init: Option<InitSystem>,
+    },
+    Off {
+        #[arg(long = "init", value_enum, help = "Autostart backend to remove")]
+        init: Option<InitSystem>,
+    },
+    Status {
+        #[arg(long = "init", value_enum, help = "Autostart backend to check")]
+        init: Option<InitSystem>,
+    },
+}
+
+impl fmt::Display for InitSystem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            InitSystem::Systemd => "systemd",
+            InitSystem::Openrc => "openrc",
+            InitSystem::Runit => "runit",
+            InitSystem::Xdg => "xdg",
+        };
+        write!(f, "{name}")
+    }
 }
 
 #[derive(Subcommand)]
@@ -110,6 +943,7 @@ pub enum DaemonCommand {
     Start,
     Stop,
     Restart,
+    Reload,
     Status,
     Logs,
     Autostart {
@@ -118,10 +952,89 @@ pub enum DaemonCommand {
     },
 }
 
+#[derive(Subcommand)]
+pub enum AliasCommand {
+    Add {
+        #[arg(help = "Short name to refer to the clip by")]
+        alias: String,
+        #[arg(help = "Name of the clip the alias points to")]
+        target: String,
+    },
+    List,
+    Remove {
+        alias: String,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum TrashCommand {
+    List,
+    Restore {
+        #[arg(help = "Name of the trashed clip to put back")]
+        name: String,
+    },
+    Empty {
+        #[arg(short = 'y', long = "yes", help = "Empty the trash without asking")]
+        yes: bool,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum TagCommand {
+    Add {
+        #[arg(help = "Name of the local clip to tag")]
+        name: String,
+        #[arg(required = true, help = "Tags to add")]
+        tags: Vec<String>,
+    },
+    Remove {
+        #[arg(help = "Name of the local clip to untag")]
+        name: String,
+        #[arg(required = true, help = "Tags to remove")]
+        tags: Vec<String>,
+    },
+    List {
+        #[arg(help = "Name of the clip to show tags for")]
+        name: String,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum ConfigCommand {
+    Backup {
+        #[arg(help = "Archive to write (a .tar.gz file)")]
+        file: PathBuf,
+        #[arg(
+            long = "include-token",
+            help = "Also include the login token so the new machine stays signed in"
+        )]
+        include_token: bool,
+    },
+    Restore {
+        #[arg(help = "Archive created by `wayclip config backup`")]
+        file: PathBuf,
+        #[arg(
+            short = 'y',
+            long = "yes",
+            help = "Overwrite existing files without asking"
+        )]
+        yes: bool,
+    },
+}
+
 #[derive(Subcommand)]
 pub enum TwoFactorCommand {
     Setup,
     Status,
+    RecoveryCodes {
+        #[arg(
+            short = 'o',
+            long = "output",
+            value_name = "PATH",
+            help = "Also write the new codes to this file (readable only by you)"
+        )]
+        output: Option<PathBuf>,
+    },
 }
 
 pub const LOCAL_PORT: u16 = 54321;
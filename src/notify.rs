@@ -0,0 +1,20 @@
+use anyhow::Result;
+use tokio::process::Command;
+
+pub async fn send_notification(summary: &str, body: &str) {
+    let _ = Command::new("notify-send")
+        .arg("--app-name=Wayclip")
+        .arg(summary)
+        .arg(body)
+        .output()
+        .await;
+}
+
+pub async fn notify_completion<T>(operation: &str, subject: &str, result: &Result<T>) {
+    match result {
+        Ok(_) => send_notification(&format!("✔ {operation} finished"), subject).await,
+        Err(e) => {
+            send_notification(&format!("✗ {operation} failed"), &format!("{subject}: {e}")).await
+        }
+    }
+}
@@ -0,0 +1,240 @@
+use anyhow::{Context, Result, bail};
+use serde::Deserialize;
+use std::path::Path;
+use tokio::process::Command;
+
+#[derive(Deserialize, Debug, Default)]
+struct FfprobeOutput {
+    #[serde(default)]
+    streams: Vec<FfprobeStream>,
+    format: Option<FfprobeFormat>,
+}
+
+#[derive(Deserialize, Debug)]
+struct FfprobeStream {
+    codec_type: Option<String>,
+    codec_name: Option<String>,
+    avg_frame_rate: Option<String>,
+    width: Option<u32>,
+    height: Option<u32>,
+    color_primaries: Option<String>,
+    color_transfer: Option<String>,
+    color_space: Option<String>,
+    sample_rate: Option<String>,
+}
+
+impl FfprobeStream {
+    fn color_args(&self) -> Vec<String> {
+        [
+            ("-color_primaries", &self.color_primaries),
+            ("-color_trc", &self.color_transfer),
+            ("-colorspace", &self.color_space),
+        ]
+        .into_iter()
+        .filter_map(|(flag, value)| match value.as_deref() {
+            Some(value) if value != "unknown" => Some([flag.to_string(), value.to_string()]),
+            _ => None,
+        })
+        .flatten()
+        .collect()
+    }
+
+    fn frame_rate(&self) -> Option<f64> {
+        let (numerator, denominator) = self.avg_frame_rate.as_deref()?.split_once('/')?;
+        let rate = numerator.parse::<f64>().ok()? / denominator.parse::<f64>().ok()?;
+        (rate.is_finite() && rate > 0.0).then_some(rate)
+    }
+
+    fn is_hdr(&self) -> bool {
+        matches!(
+            self.color_transfer.as_deref(),
+            Some("smpte2084" | "arib-std-b67")
+        ) || self.color_primaries.as_deref() == Some("bt2020")
+    }
+}
+
+#[derive(Deserialize, Debug)]
+struct FfprobeFormat {
+    duration: Option<String>,
+    bit_rate: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ProbeInfo {
+    pub duration: f64,
+    pub has_audio: bool,
+    pub audio_streams: usize,
+    pub hdr: bool,
+    pub color_args: Vec<String>,
+    pub sample_rate: Option<u32>,
+    pub video_codec: Option<String>,
+    pub frame_rate: Option<f64>,
+    pub width: Option<u32>,
+    pub height: Option<u32>,
+    pub bit_rate: Option<u64>,
+}
+
+async fn run_ffprobe(path: &Path) -> Result<FfprobeOutput> {
+    let metadata = tokio::fs::metadata(path)
+        .await
+        .context(format!("Cannot read {}", path.display()))?;
+    if metadata.len() == 0 {
+        bail!("file is empty");
+    }
+
+    let output = Command::new("ffprobe")
+        .arg("-v")
+        .arg("error")
+        .arg("-show_entries")
+        .arg("format=duration,bit_rate:stream=codec_type,codec_name,avg_frame_rate,width,height,color_primaries,color_transfer,color_space,sample_rate")
+        .arg("-of")
+        .arg("json")
+        .arg(path)
+        .output()
+        .await
+        .context("Failed to execute ffprobe. Is it installed and in your PATH?")?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        bail!("ffprobe could not read the file: {}", stderr.trim());
+    }
+
+    serde_json::from_slice(&output.stdout).context("Failed to parse ffprobe output")
+}
+
+impl FfprobeOutput {
+    fn has_stream(&self, codec_type: &str) -> bool {
+        self.streams
+            .iter()
+            .any(|s| s.codec_type.as_deref() == Some(codec_type))
+    }
+
+    fn duration(&self) -> Result<f64> {
+        self.format
+            .as_ref()
+            .and_then(|f| f.duration.as_deref())
+            .and_then(|d| d.parse::<f64>().ok())
+            .filter(|d| *d > 0.0)
+            .context("duration is missing or zero")
+    }
+}
+
+pub async fn probe_clip(path: &Path) -> Result<ProbeInfo> {
+    let parsed = run_ffprobe(path).await?;
+    let stream = |codec_type: &str| {
+        parsed
+            .streams
+            .iter()
+            .find(|s| s.codec_type.as_deref() == Some(codec_type))
+    };
+    let video = stream("video").context("no video stream found")?;
+    Ok(ProbeInfo {
+        duration: parsed.duration()?,
+        has_audio: parsed.has_stream("audio"),
+        audio_streams: parsed
+            .streams
+            .iter()
+            .filter(|s| s.codec_type.as_deref() == Some("audio"))
+            .count(),
+        hdr: video.is_hdr(),
+        color_args: video.color_args(),
+        sample_rate: stream("audio")
+            .and_then(|s| s.sample_rate.as_deref())
+            .and_then(|rate| rate.parse().ok()),
+        video_codec: video.codec_name.clone(),
+        frame_rate: video.frame_rate(),
+        width: video.width,
+        height: video.height,
+        bit_rate: parsed
+            .format
+            .as_ref()
+            .and_then(|f| f.bit_rate.as_deref())
+            .and_then(|rate| rate.parse().ok()),
+    })
+}
+
+pub async fn probe_audio_file(path: &Path) -> Result<f64> {
+    let parsed = run_ffprobe(path).await?;
+    if !parsed.has_stream("audio") {
+        bail!("no audio stream found");
+    }
+    parsed.duration()
+}
+
+#[derive(Deserialize, Debug)]
+struct FfprobeChapters {
+    #[serde(default)]
+    chapters: Vec<Chapter>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct Chapter {
+    pub start_time: String,
+    pub end_time: String,
+}
+
+pub async fn probe_chapters(path: &Path) -> Result<Vec<Chapter>> {
+    let output = Command::new("ffprobe")
+        .arg("-v")
+        .arg("error")
+        .arg("-show_chapters")
+        .arg("-of")
+        .arg("json")
+        .arg(path)
+        .output()
+        .await
+        .context("Failed to execute ffprobe. Is it installed and in your PATH?")?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        bail!("ffprobe could not read the file: {}", stderr.trim());
+    }
+
+    let parsed: FfprobeChapters =
+        serde_json::from_slice(&output.stdout).context("Failed to parse ffprobe output")?;
+    Ok(parsed.chapters)
+}
+
+#[derive(Deserialize, Debug)]
+struct FfprobePackets {
+    #[serde(default)]
+    packets: Vec<FfprobePacket>,
+}
+
+#[derive(Deserialize, Debug)]
+struct FfprobePacket {
+    pts_time: Option<String>,
+    flags: Option<String>,
+}
+
+pub async fn probe_keyframes(path: &Path) -> Result<Vec<f64>> {
+    let output = Command::new("ffprobe")
+        .arg("-v")
+        .arg("error")
+        .arg("-select_streams")
+        .arg("v:0")
+        .arg("-show_entries")
+        .arg("packet=pts_time,flags")
+        .arg("-of")
+        .arg("json")
+        .arg(path)
+        .output()
+        .await
+        .context("Failed to execute ffprobe. Is it installed and in your PATH?")?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        bail!("ffprobe could not read the file: {}", stderr.trim());
+    }
+
+    let parsed: FfprobePackets =
+        serde_json::from_slice(&output.stdout).context("Failed to parse ffprobe output")?;
+    let mut keyframes: Vec<f64> = parsed
+        .packets
+        .iter()
+        .filter(|p| p.flags.as_deref().is_some_and(|f| f.starts_with('K')))
+        .filter_map(|p| p.pts_time.as_deref()?.parse().ok())
+        .collect();
+    keyframes.sort_by(f64::total_cmp);
+    Ok(keyframes)
+}
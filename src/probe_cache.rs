@@ -0,0 +1,95 @@
+use crate::data_file::write_atomic;
+use crate::probe::probe_clip;
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+use wayclip_core::settings::Settings;
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct CachedProbe {
+    size: u64,
+    modified: u64,
+    pub duration: f64,
+    pub width: Option<u32>,
+    pub height: Option<u32>,
+    pub video_codec: Option<String>,
+    pub bit_rate: Option<u64>,
+}
+
+impl CachedProbe {
+    pub fn resolution(&self) -> Option<String> {
+        Some(format!("{}x{}", self.width?, self.height?))
+    }
+}
+
+#[derive(Serialize, Deserialize, Default, Debug)]
+#[serde(default)]
+pub struct ProbeCache {
+    entries: BTreeMap<PathBuf, CachedProbe>,
+    #[serde(skip)]
+    dirty: bool,
+}
+
+impl ProbeCache {
+    fn path() -> PathBuf {
+        Settings::config_path()
+            .join("wayclip")
+            .join("probe-cache.json")
+    }
+
+    pub async fn load() -> Self {
+        let Ok(contents) = tokio::fs::read(Self::path()).await else {
+            return Self::default();
+        };
+        serde_json::from_slice(&contents).unwrap_or_default()
+    }
+
+    pub async fn save(&mut self) -> Result<()> {
+        if !self.dirty {
+            return Ok(());
+        }
+        self.entries.retain(|path, _| path.exists());
+        let path = Self::path();
+        if let Some(parent) = path.parent() {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+        write_atomic(&path, &serde_json::to_vec_pretty(self)?)
+            .await
+            .context("Failed to save the probe cache")?;
+        self.dirty = false;
+        Ok(())
+    }
+
+    pub async fn probe(&mut self, clip_path: &Path) -> Option<CachedProbe> {
+        let metadata = tokio::fs::metadata(clip_path).await.ok()?;
+        let size = metadata.len();
+        let modified = metadata
+            .modified()
+            .ok()?
+            .duration_since(UNIX_EPOCH)
+            .ok()?
+            .as_secs();
+        if let Some(cached) = self.entries.get(clip_path)
+            && cached.size == size
+            && cached.modified == modified
+        {
+            return Some(cached.clone());
+        }
+
+        let info = probe_clip(clip_path).await.ok()?;
+        let cached = CachedProbe {
+            size,
+            modified,
+            duration: info.duration,
+            width: info.width,
+            height: info.height,
+            video_codec: info.video_codec,
+            bit_rate: info.bit_rate,
+        };
+        self.entries.insert(clip_path.to_path_buf(), cached.clone());
+        self.dirty = true;
+        Some(cached)
+    }
+}
@@ -0,0 +1,66 @@
+use crate::api_client;
+use crate::daemon::runtime_dir;
+use anyhow::{Result, bail};
+use serde_json::Value;
+use std::path::PathBuf;
+use std::time::Duration;
+use tokio::io::AsyncWriteExt;
+use wayclip_core::api;
+use wayclip_core::models::UserProfile;
+
+const PROFILE_CACHE_TTL: Duration = Duration::from_secs(60);
+
+fn cache_path() -> PathBuf {
+    runtime_dir().join("wayclip-profile.json")
+}
+
+async fn read_cache() -> Option<Value> {
+    let path = cache_path();
+    let modified = tokio::fs::metadata(&path).await.ok()?.modified().ok()?;
+    if modified.elapsed().ok()? > PROFILE_CACHE_TTL {
+        return None;
+    }
+    let contents = tokio::fs::read(&path).await.ok()?;
+    serde_json::from_slice(&contents).ok()
+}
+
+async fn write_cache(profile: &Value) -> std::io::Result<()> {
+    let path = cache_path();
+    let temp_path = path.with_extension("tmp");
+    let contents = serde_json::to_vec(profile)?;
+    let mut file = tokio::fs::OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(true)
+        .mode(0o600)
+        .open(&temp_path)
+        .await?;
+    file.write_all(&contents).await?;
+    drop(file);
+    tokio::fs::rename(&temp_path, &path).await
+}
+
+async fn profile_json(fresh: bool) -> Result<Value, api::ApiClientError> {
+    if !fresh && let Some(profile) = read_cache().await {
+        return Ok(profile);
+    }
+    let profile = api_client::current_user_json().await?;
+    let _ = write_cache(&profile).await;
+    Ok(profile)
+}
+
+pub async fn current_user(fresh: bool) -> Result<UserProfile, api::ApiClientError> {
+    Ok(serde_json::from_value(profile_json(fresh).await?)?)
+}
+
+pub async fn max_clip_duration() -> Result<Option<f64>> {
+    let profile = profile_json(false).await?;
+    let Some(limit) = profile["tier_limits"].get("max_clip_duration") else {
+        bail!("the server did not report tier limits");
+    };
+    Ok(limit.as_f64().filter(|max| *max > 0.0))
+}
+
+pub async fn invalidate() {
+    let _ = tokio::fs::remove_file(cache_path()).await;
+}
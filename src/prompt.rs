@@ -0,0 +1,17 @@
+use inquire::InquireError;
+use std::process::ExitCode;
+
+pub const CANCELLED_EXIT_CODE: u8 = 130;
+
+pub fn is_cancellation(error: &anyhow::Error) -> bool {
+    error.chain().any(|cause| {
+        matches!(
+            cause.downcast_ref::<InquireError>(),
+            Some(InquireError::OperationCanceled | InquireError::OperationInterrupted)
+        )
+    })
+}
+
+pub fn cancelled_exit() -> ExitCode {
+    ExitCode::from(CANCELLED_EXIT_CODE)
+}
@@ -0,0 +1,127 @@
+use crate::theme::Themed;
+use crate::trash::move_to_trash;
+use crate::unified_clip::{ClipLookup, find_unified_clips_matching, gather_clips};
+use anyhow::{Result, anyhow, bail};
+use chrono::{TimeDelta, Utc};
+use colored::*;
+use inquire::Confirm;
+use std::path::Path;
+use wayclip_core::delete_file;
+use wayclip_core::models::UnifiedClipData;
+
+pub fn parse_age(input: &str) -> Result<TimeDelta, String> {
+    let input = input.trim();
+    let split = input
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(input.len());
+    let (amount, unit) = input.split_at(split);
+    let amount: i64 = amount
+        .parse()
+        .map_err(|_| format!("'{input}' must start with a number (e.g. 30d, 12h)"))?;
+    let age = match unit {
+        "min" => TimeDelta::try_minutes(amount),
+        "h" => TimeDelta::try_hours(amount),
+        "d" => TimeDelta::try_days(amount),
+        "w" => TimeDelta::try_weeks(amount),
+        "" => return Err(format!("'{input}' needs a unit (min, h, d or w)")),
+        _ => return Err(format!("Unknown unit '{unit}' (use min, h, d or w)")),
+    };
+    age.ok_or_else(|| format!("'{input}' is too large"))
+}
+
+fn describe_age(clip: &UnifiedClipData) -> String {
+    let age = Utc::now().signed_duration_since(clip.created_at.with_timezone(&Utc));
+    if age.num_days() > 0 {
+        format!("{}d old", age.num_days())
+    } else {
+        format!("{}h old", age.num_hours())
+    }
+}
+
+pub async fn handle_prune(
+    pattern: Option<&str>,
+    older_than: Option<TimeDelta>,
+    dry_run: bool,
+    yes: bool,
+    permanent: bool,
+    lookup: ClipLookup,
+) -> Result<()> {
+    if pattern.is_none() && older_than.is_none() {
+        bail!("Specify a name pattern, --older-than, or both.");
+    }
+
+    let clips = match pattern {
+        Some(pattern) => find_unified_clips_matching(pattern, lookup).await?,
+        None => gather_clips(lookup).await?,
+    };
+    let cutoff = older_than.map(|age| Utc::now() - age);
+    let mut matches: Vec<UnifiedClipData> = clips
+        .into_iter()
+        .filter(|clip| clip.local_path.is_some())
+        .filter(|clip| cutoff.is_none_or(|cutoff| clip.created_at.with_timezone(&Utc) < cutoff))
+        .collect();
+    matches.sort_by_key(|a| a.created_at);
+
+    if matches.is_empty() {
+        println!("{}", "○ No local clips match.".warning());
+        return Ok(());
+    }
+
+    println!("○ {} local clips match:", matches.len());
+    for clip in &matches {
+        println!("  {} ({})", clip.name.accent(), describe_age(clip));
+    }
+    if dry_run {
+        println!("{}", "○ Dry run: nothing was deleted.".warning());
+        return Ok(());
+    }
+
+    if !yes {
+        let prompt = if permanent {
+            format!(
+                "Delete these {} local files? This cannot be undone.",
+                matches.len()
+            )
+        } else {
+            format!("Move these {} local files to the trash?", matches.len())
+        };
+        let confirmed = Confirm::new(&prompt).with_default(false).prompt()?;
+        if !confirmed {
+            println!("{}", "○ Prune cancelled.".warning());
+            return Ok(());
+        }
+    }
+
+    let mut failed = 0;
+    for clip in &matches {
+        let Some(local_path) = &clip.local_path else {
+            continue;
+        };
+        let result = if permanent {
+            delete_file(local_path).await.map_err(|e| anyhow!(e))
+        } else {
+            move_to_trash(Path::new(local_path)).await
+        };
+        match result {
+            Ok(_) => println!("{} {}", "✔".success(), clip.name),
+            Err(e) => {
+                failed += 1;
+                println!("{} {}: {e:#}", "✗".error(), clip.name);
+            }
+        }
+    }
+
+    if failed > 0 {
+        bail!("{failed} of {} clips could not be deleted.", matches.len());
+    }
+    let summary = if permanent {
+        format!("✔ Deleted {} local clips.", matches.len())
+    } else {
+        format!(
+            "✔ Moved {} local clips to the trash. See them with `wayclip trash list`.",
+            matches.len()
+        )
+    };
+    println!("{}", summary.success().bold());
+    Ok(())
+}
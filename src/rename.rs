@@ -1,39 +1,62 @@
-use crate::unified_clip::find_unified_clip;
-use crate::validate::sanitize_and_validate_filename_stem;
+use crate::sidecar::move_sidecar;
+use crate::theme::Themed;
+use crate::unified_clip::{ClipLookup, find_unified_clip};
+use crate::validate::{FilenameRules, renamed_file_name, sanitize_and_validate_filename_stem};
 use anyhow::{Context, Result, bail};
-use colored::*;
 use inquire::Text;
 use std::path::PathBuf;
+use wayclip_core::models::UnifiedClipData;
 use wayclip_core::rename_all_entries;
 
-pub async fn handle_rename(name: &str) -> Result<()> {
-    let clip_to_rename = find_unified_clip(name).await?;
-
-    let clip_path_str = clip_to_rename
+pub async fn rename_clip(
+    clip: &UnifiedClipData,
+    new_name_stem: &str,
+    filename_rules: &FilenameRules,
+) -> Result<String> {
+    let clip_path_str = clip
         .local_path
+        .as_ref()
         .context("Cannot rename a clip that does not exist locally.")?;
-    let clip_path = PathBuf::from(&clip_path_str);
+    let new_file_name =
+        renamed_file_name(&PathBuf::from(clip_path_str), new_name_stem, filename_rules)?;
+    let new_full_name = new_file_name
+        .to_str()
+        .context(format!(
+            "'{}' has a non-UTF-8 extension, which the clip library cannot record.",
+            new_file_name.display()
+        ))?
+        .to_string();
+    if let Err(e) = rename_all_entries(clip_path_str, &new_full_name).await {
+        bail!("Failed to rename: {e}");
+    }
+    let old_path = PathBuf::from(clip_path_str);
+    move_sidecar(&old_path, &old_path.with_file_name(&new_full_name)).await;
+    Ok(new_full_name)
+}
+
+pub async fn handle_rename(
+    name: &str,
+    filename_rules: &FilenameRules,
+    lookup: ClipLookup,
+) -> Result<()> {
+    let clip_to_rename = find_unified_clip(name, lookup).await?;
+
+    if clip_to_rename.local_path.is_none() {
+        bail!("Cannot rename a clip that does not exist locally.");
+    }
 
     let new_name_input = Text::new("› Enter new name (without extension):")
         .with_initial_value(&clip_to_rename.name)
         .prompt()?;
 
-    let new_name_stem = sanitize_and_validate_filename_stem(&new_name_input)?;
+    let new_name_stem = sanitize_and_validate_filename_stem(&new_name_input, filename_rules)?;
 
     if new_name_stem == clip_to_rename.name {
-        println!("{}", "○ Rename cancelled (name is the same).".yellow());
+        println!("{}", "○ Rename cancelled (name is the same).".warning());
         return Ok(());
     }
 
-    let extension = clip_path
-        .extension()
-        .and_then(|s| s.to_str())
-        .unwrap_or("mp4");
-    let new_full_name = format!("{new_name_stem}.{extension}");
-
-    match rename_all_entries(&clip_path_str, &new_full_name).await {
-        Ok(_) => println!("{}", format!("✔ Renamed to '{new_full_name}'").green()),
-        Err(e) => bail!("Failed to rename: {e}"),
-    }
+    let new_full_name = rename_clip(&clip_to_rename, &new_name_stem, filename_rules).await?;
+    println!("{}", format!("✔ Renamed to '{new_full_name}'").success());
     Ok(())
 }
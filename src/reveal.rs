@@ -0,0 +1,54 @@
+use crate::theme::Themed;
+use crate::unified_clip::{ClipLookup, find_unified_clip};
+use anyhow::{Context, Result, bail};
+use std::path::Path;
+use tokio::process::Command;
+
+fn file_uri(path: &Path) -> String {
+    let encoded: Vec<String> = path
+        .to_string_lossy()
+        .split('/')
+        .map(|segment| urlencoding::encode(segment).into_owned())
+        .collect();
+    format!("file://{}", encoded.join("/"))
+}
+
+async fn show_item_in_file_manager(path: &Path) -> bool {
+    Command::new("dbus-send")
+        .arg("--session")
+        .arg("--dest=org.freedesktop.FileManager1")
+        .arg("--type=method_call")
+        .arg("/org/freedesktop/FileManager1")
+        .arg("org.freedesktop.FileManager1.ShowItems")
+        .arg(format!("array:string:{}", file_uri(path)))
+        .arg("string:")
+        .output()
+        .await
+        .is_ok_and(|output| output.status.success())
+}
+
+pub async fn handle_reveal(name: &str, lookup: ClipLookup) -> Result<()> {
+    let clip = find_unified_clip(name, lookup).await?;
+    let clip_path_str = clip.local_path.context(format!(
+        "Clip '{}' is not stored locally, so there is nothing to reveal.",
+        clip.name
+    ))?;
+    let clip_path = Path::new(&clip_path_str);
+
+    if show_item_in_file_manager(clip_path).await {
+        println!("○ Revealing {} in your file manager.", clip_path.display());
+        return Ok(());
+    }
+
+    let folder = clip_path
+        .parent()
+        .context("Could not determine the clip's folder.")?;
+    if opener::open(folder).is_err() {
+        bail!("Could not open a file manager. The clip is located at:\n{clip_path_str}");
+    }
+    println!(
+        "○ Opening folder: {}",
+        folder.display().to_string().accent()
+    );
+    Ok(())
+}
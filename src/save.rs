@@ -1,25 +1,238 @@
+use crate::data_file::add_clip_tags;
+use crate::import::normalize_tags;
+use crate::rename::rename_clip;
+use crate::theme::Themed;
+use crate::unified_clip::{ClipLookup, clips_directory, find_unified_clip, gather_clips};
+use crate::validate::{FilenameRules, sanitize_and_validate_filename_stem};
 use anyhow::{Context, Result, bail};
-use colored::*;
+use regex::Regex;
+use serde_json::Value;
+use std::ffi::OsStr;
+use std::path::{Path, PathBuf};
+use std::process::ExitStatus;
+use std::time::{Duration, SystemTime};
 use tokio::process::Command;
 use wayclip_core::control::DaemonManager;
 use wayclip_core::settings::Settings;
 
-pub async fn handle_save() -> Result<()> {
+#[derive(Clone, Debug, Default)]
+pub struct CaptureScope {
+    pub monitor: Option<String>,
+    pub region: Option<String>,
+}
+
+impl CaptureScope {
+    fn is_empty(&self) -> bool {
+        self.monitor.is_none() && self.region.is_none()
+    }
+
+    fn trigger_args(&self) -> Vec<String> {
+        let mut args = Vec::new();
+        if let Some(monitor) = &self.monitor {
+            args.push("--monitor".to_string());
+            args.push(monitor.clone());
+        }
+        if let Some(region) = &self.region {
+            args.push("--region".to_string());
+            args.push(region.clone());
+        }
+        args
+    }
+}
+
+const NEW_CLIP_TIMEOUT: Duration = Duration::from_secs(10);
+const NEW_CLIP_POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+const OUTPUT_QUERIES: [(&str, &[&str]); 3] = [
+    ("wlr-randr", &["--json"]),
+    ("swaymsg", &["-t", "get_outputs", "-r"]),
+    ("hyprctl", &["monitors", "-j"]),
+];
+
+async fn list_outputs() -> Option<Vec<String>> {
+    for (program, args) in OUTPUT_QUERIES {
+        let Ok(output) = Command::new(program).args(args).output().await else {
+            continue;
+        };
+        if !output.status.success() {
+            continue;
+        }
+        let Ok(Value::Array(outputs)) = serde_json::from_slice::<Value>(&output.stdout) else {
+            continue;
+        };
+        return Some(
+            outputs
+                .iter()
+                .filter_map(|o| o["name"].as_str().map(String::from))
+                .collect(),
+        );
+    }
+    None
+}
+
+fn validate_region(region: &str) -> Result<String> {
+    let pattern = Regex::new(r"^(\d+)x(\d+)\+(\d+)\+(\d+)$")?;
+    let trimmed = region.trim();
+    let Some(captures) = pattern.captures(trimmed) else {
+        bail!("Invalid region '{region}'. Use WxH+X+Y, e.g. 1920x1080+0+0.");
+    };
+    if &captures[1] == "0" || &captures[2] == "0" {
+        bail!("Region width and height must be greater than zero.");
+    }
+    Ok(trimmed.to_string())
+}
+
+async fn validate_scope(monitor: Option<&str>, region: Option<&str>) -> Result<CaptureScope> {
+    let region = region.map(validate_region).transpose()?;
+    if let Some(monitor) = monitor {
+        match list_outputs().await {
+            Some(outputs) if !outputs.iter().any(|o| o == monitor) => bail!(
+                "Monitor '{monitor}' not found. Available outputs: {}",
+                outputs.join(", ")
+            ),
+            Some(_) => {}
+            None => println!(
+                "{}",
+                "○ Could not query the compositor for outputs; passing the monitor name through unchecked."
+                    .warning()
+            ),
+        }
+    }
+    Ok(CaptureScope {
+        monitor: monitor.map(String::from),
+        region,
+    })
+}
+
+async fn run_trigger<P: AsRef<OsStr>>(trigger_path: P, scope: &CaptureScope) -> Result<ExitStatus> {
+    Command::new(trigger_path)
+        .args(scope.trigger_args())
+        .status()
+        .await
+        .context("Failed to execute the trigger process.")
+}
+
+async fn newest_clip_since(clips_dir: &Path, since: SystemTime) -> Result<Option<PathBuf>> {
+    let mut newest: Option<(SystemTime, PathBuf)> = None;
+    let mut entries = tokio::fs::read_dir(clips_dir)
+        .await
+        .context(format!("Failed to read {}", clips_dir.display()))?;
+    while let Some(entry) = entries.next_entry().await? {
+        let path = entry.path();
+        let is_hidden = path
+            .file_name()
+            .and_then(|s| s.to_str())
+            .is_none_or(|s| s.starts_with('.'));
+        let is_partial = path
+            .extension()
+            .and_then(|e| e.to_str())
+            .is_none_or(|e| e == "tmp" || e == "part");
+        if is_hidden || is_partial {
+            continue;
+        }
+        let metadata = entry.metadata().await?;
+        if !metadata.is_file() {
+            continue;
+        }
+        let modified = metadata.modified()?;
+        if modified >= since && newest.as_ref().is_none_or(|(time, _)| modified > *time) {
+            newest = Some((modified, path));
+        }
+    }
+    Ok(newest.map(|(_, path)| path))
+}
+
+async fn wait_for_new_clip(clips_dir: &Path, since: SystemTime) -> Result<PathBuf> {
+    let deadline = tokio::time::Instant::now() + NEW_CLIP_TIMEOUT;
+    loop {
+        if let Some(path) = newest_clip_since(clips_dir, since).await? {
+            return Ok(path);
+        }
+        if tokio::time::Instant::now() >= deadline {
+            bail!(
+                "The clip was saved, but no new file appeared in {} to rename or tag.",
+                clips_dir.display()
+            );
+        }
+        tokio::time::sleep(NEW_CLIP_POLL_INTERVAL).await;
+    }
+}
+
+async fn label_saved_clip(
+    clip_path: &Path,
+    name: Option<&str>,
+    tags: &[String],
+    filename_rules: &FilenameRules,
+    lookup: ClipLookup,
+) -> Result<()> {
+    let stem = clip_path
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .context("Invalid filename")?
+        .to_string();
+    gather_clips(lookup).await?;
+    let clip = find_unified_clip(&stem, lookup).await?;
+
+    let full_filename = match name {
+        Some(name) if name != clip.name => {
+            let full_filename = rename_clip(&clip, name, filename_rules).await?;
+            println!("{}", format!("✔ Saved clip as '{full_filename}'").success());
+            full_filename
+        }
+        _ => clip.full_filename.clone(),
+    };
+
+    if !tags.is_empty() {
+        if !add_clip_tags(&full_filename, tags).await? {
+            bail!("Could not tag '{full_filename}': it has no local data entry yet.");
+        }
+        println!("○ Tagged with {}.", tags.join(", ").accent());
+    }
+    Ok(())
+}
+
+pub async fn handle_save(filename_rules: &FilenameRules, lookup: ClipLookup) -> Result<()> {
+    handle_save_scoped(None, None, None, &[], filename_rules, lookup).await
+}
+
+pub async fn handle_save_scoped(
+    monitor: Option<&str>,
+    region: Option<&str>,
+    name: Option<&str>,
+    tags: &[String],
+    filename_rules: &FilenameRules,
+    lookup: ClipLookup,
+) -> Result<()> {
     let manager = DaemonManager::new();
     if !manager.is_running().await {
         bail!("Daemon is not running.  Start it with: wayclip daemon start");
     }
 
+    let name = name
+        .map(|name| sanitize_and_validate_filename_stem(name, filename_rules))
+        .transpose()?;
+    let tags = normalize_tags(tags)?;
+    let scope = validate_scope(monitor, region).await?;
     let settings = Settings::load().await?;
-    let mut trigger_command = Command::new(settings.trigger_path);
-    let status = trigger_command
-        .status()
-        .await
-        .context("Failed to execute the trigger process.")?;
+    let started = SystemTime::now();
+    let mut status = run_trigger(&settings.trigger_path, &scope).await?;
+    if !status.success() && !scope.is_empty() {
+        println!(
+            "{}",
+            "⚠ The daemon did not accept the capture scope; saving the full capture instead."
+                .warning()
+        );
+        status = run_trigger(&settings.trigger_path, &CaptureScope::default()).await?;
+    }
     if status.success() {
-        println!("{}", "✔ Trigger process finished successfully.".green());
+        println!("{}", "✔ Trigger process finished successfully.".success());
     } else {
         bail!("Trigger process failed with status: {status}");
     }
+
+    if name.is_some() || !tags.is_empty() {
+        let clip_path = wait_for_new_clip(&clips_directory(&settings), started).await?;
+        label_saved_clip(&clip_path, name.as_deref(), &tags, filename_rules, lookup).await?;
+    }
     Ok(())
 }
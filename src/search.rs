@@ -0,0 +1,77 @@
+use crate::clip_state::ClipState;
+use crate::manage::generate_display_string;
+use crate::theme::Themed;
+use crate::unified_clip::{ClipLookup, gather_clips};
+use anyhow::{Result, bail};
+use fuzzy_matcher::FuzzyMatcher;
+use fuzzy_matcher::skim::SkimMatcherV2;
+use serde_json::json;
+use wayclip_core::models::UnifiedClipData;
+
+pub fn rank_clips(clips: Vec<UnifiedClipData>, query: &str) -> Vec<(i64, UnifiedClipData)> {
+    let matcher = SkimMatcherV2::default().ignore_case();
+    let mut ranked: Vec<(i64, UnifiedClipData)> = clips
+        .into_iter()
+        .filter_map(|clip| {
+            matcher
+                .fuzzy_match(&clip.name, query)
+                .map(|score| (score, clip))
+        })
+        .collect();
+    ranked.sort_by(|(a_score, a), (b_score, b)| {
+        b_score
+            .cmp(a_score)
+            .then_with(|| a.name.to_lowercase().cmp(&b.name.to_lowercase()))
+    });
+    ranked
+}
+
+pub async fn handle_search(
+    query: &str,
+    limit: usize,
+    json: bool,
+    lookup: ClipLookup,
+) -> Result<()> {
+    let query = query.trim();
+    if query.is_empty() {
+        bail!("Search query cannot be empty.");
+    }
+
+    let mut ranked = rank_clips(gather_clips(lookup).await?, query);
+    ranked.truncate(limit);
+
+    if json {
+        let results: Vec<_> = ranked
+            .iter()
+            .map(|(score, clip)| {
+                json!({
+                    "name": clip.name,
+                    "full_filename": clip.full_filename,
+                    "score": score,
+                    "local": clip.local_path.is_some(),
+                    "hosted": clip.is_hosted,
+                    "hosted_id": clip.hosted_id.map(|id| id.to_string()),
+                    "liked": clip.local_data.as_ref().is_some_and(|d| d.liked),
+                    "created_at": clip.created_at.to_rfc3339(),
+                })
+            })
+            .collect();
+        println!("{}", serde_json::to_string_pretty(&results)?);
+        return Ok(());
+    }
+
+    if ranked.is_empty() {
+        println!("{}", format!("○ No clips match '{query}'.").warning());
+        return Ok(());
+    }
+
+    let clip_state = ClipState::load().await?;
+    println!("Found {} matching clips:", ranked.len());
+    for (_, clip) in &ranked {
+        println!(
+            "  {}",
+            generate_display_string(clip, &clip_state.unplayable)
+        );
+    }
+    Ok(())
+}
@@ -0,0 +1,136 @@
+use crate::data_file::{new_tag, restore_clip_entry};
+use crate::theme::Themed;
+use crate::unified_clip::{ClipLookup, find_unified_clip, gather_clips};
+use anyhow::{Context, Result, bail};
+use colored::*;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use uuid::Uuid;
+use wayclip_core::ClipJsonData;
+use wayclip_core::models::UnifiedClipData;
+
+#[derive(Serialize, Deserialize, Default, Debug, Clone)]
+#[serde(default)]
+pub struct Sidecar {
+    pub liked: bool,
+    pub tags: Vec<String>,
+    pub hosted_id: Option<String>,
+}
+
+impl Sidecar {
+    fn from_clip(clip: &UnifiedClipData) -> Self {
+        Self {
+            liked: clip.local_data.as_ref().is_some_and(|d| d.liked),
+            tags: clip
+                .local_data
+                .as_ref()
+                .map(|d| d.tags.iter().map(ToString::to_string).collect())
+                .unwrap_or_default(),
+            hosted_id: clip.hosted_id.map(|id| id.to_string()),
+        }
+    }
+
+    pub fn hosted_id(&self) -> Option<Uuid> {
+        self.hosted_id
+            .as_deref()
+            .and_then(|id| Uuid::parse_str(id).ok())
+    }
+}
+
+pub fn sidecar_path(clip_path: &Path) -> PathBuf {
+    clip_path.with_extension("json")
+}
+
+pub async fn read_sidecar(clip_path: &Path) -> Option<Sidecar> {
+    let contents = tokio::fs::read(sidecar_path(clip_path)).await.ok()?;
+    serde_json::from_slice(&contents).ok()
+}
+
+async fn write_sidecar(clip_path: &Path, sidecar: &Sidecar) -> Result<PathBuf> {
+    let path = sidecar_path(clip_path);
+    tokio::fs::write(&path, serde_json::to_vec_pretty(sidecar)?)
+        .await
+        .context(format!("Failed to write {}", path.display()))?;
+    Ok(path)
+}
+
+pub async fn move_sidecar(old_clip_path: &Path, new_clip_path: &Path) {
+    let old_path = sidecar_path(old_clip_path);
+    if old_path.exists() {
+        let _ = tokio::fs::rename(&old_path, sidecar_path(new_clip_path)).await;
+    }
+}
+
+pub fn fill_from_sidecars(clips: &mut [UnifiedClipData]) {
+    for clip in clips.iter_mut() {
+        if clip.local_data.is_some() {
+            continue;
+        }
+        let Some(local_path) = &clip.local_path else {
+            continue;
+        };
+        let Ok(contents) = std::fs::read(sidecar_path(Path::new(local_path))) else {
+            continue;
+        };
+        if let Ok(sidecar) = serde_json::from_slice::<Sidecar>(&contents) {
+            clip.hosted_id = clip.hosted_id.or_else(|| sidecar.hosted_id());
+            clip.local_data = Some(ClipJsonData {
+                tags: sidecar.tags.iter().map(|tag| new_tag(tag)).collect(),
+                liked: sidecar.liked,
+                hosted_id: clip.hosted_id,
+            });
+        }
+    }
+}
+
+pub async fn import_sidecar(source: &Path, destination: &Path) -> Result<bool> {
+    let Some(sidecar) = read_sidecar(source).await else {
+        return Ok(false);
+    };
+    write_sidecar(destination, &sidecar).await?;
+
+    let full_filename = destination
+        .file_name()
+        .and_then(|s| s.to_str())
+        .context("Invalid filename")?;
+    restore_clip_entry(
+        full_filename,
+        sidecar.liked,
+        &sidecar.tags,
+        sidecar.hosted_id(),
+    )
+    .await
+    .context("Failed to restore sidecar metadata")?;
+    Ok(true)
+}
+
+pub async fn handle_sidecar(name: Option<&str>, all: bool, lookup: ClipLookup) -> Result<()> {
+    let clips = match name {
+        Some(name) if !all => vec![find_unified_clip(name, lookup).await?],
+        _ => gather_clips(lookup).await?,
+    };
+
+    let mut written = 0;
+    for clip in &clips {
+        let Some(local_path) = &clip.local_path else {
+            if !all {
+                bail!("Clip '{}' not found locally.", clip.name);
+            }
+            continue;
+        };
+        let path = write_sidecar(Path::new(local_path), &Sidecar::from_clip(clip)).await?;
+        written += 1;
+        if !all {
+            println!("{}", format!("✔ Wrote {}", path.display()).success());
+        }
+    }
+    if all {
+        println!(
+            "{}",
+            format!("✔ Wrote sidecars for {written} clips.")
+                .success()
+                .bold()
+        );
+    }
+    Ok(())
+}
@@ -1,14 +1,73 @@
+use crate::api_client::{anonymous_client, api_client};
+use crate::cli_settings::CliSettings;
+use crate::clip_password::{set_clip_password, validate_clip_password};
 use crate::clipboard::copy_to_clipboard;
-use crate::unified_clip::find_unified_clip;
+use crate::exit::SilentExit;
+use crate::probe::probe_clip;
+use crate::profile_cache;
+use crate::theme::Themed;
+use crate::thumbnail::{upload_poster, validate_poster_time};
+use crate::unified_clip::{ClipLookup, find_unified_clip};
+use crate::upload::{
+    DEFAULT_UPLOAD_RETRIES, ShareMetadata, progress_part, replace_upload, share_upload,
+    upload_progress_bar,
+};
+use crate::uploader::mime_for;
+use crate::url::{clip_public_url, format_url};
 use anyhow::{Context, Result, bail};
 use colored::*;
 use inquire::Confirm;
+use serde_json::{Value, json};
 use std::path::Path;
 use uuid::Uuid;
 use wayclip_core::api;
+use wayclip_core::models::UserProfile;
+use wayclip_core::settings::Settings;
 
-pub async fn handle_me() -> Result<()> {
-    match api::get_current_user().await {
+fn profile_json(profile: &UserProfile) -> Value {
+    json!({
+        "username": profile.user.username,
+        "email": profile.user.email,
+        "email_verified": profile.user.email_verified_at.is_some(),
+        "tier": format!("{:?}", profile.user.tier),
+        "two_factor_enabled": profile.user.two_factor_enabled,
+        "clip_count": profile.clip_count,
+        "storage_used": profile.storage_used,
+        "storage_limit": profile.storage_limit,
+        "last_login_at": profile.user.last_login_at.map(|time| time.to_rfc3339()),
+        "last_login_ip": profile.user.last_login_ip,
+    })
+}
+
+fn print_json_error(code: &str, message: &str) {
+    println!("{}", json!({ "error": code, "message": message }));
+}
+
+pub async fn handle_me(porcelain: bool, fresh: bool, json: bool) -> Result<()> {
+    match profile_cache::current_user(fresh).await {
+        Ok(profile) if json => {
+            println!("{}", serde_json::to_string_pretty(&profile_json(&profile))?);
+        }
+        Err(api::ApiClientError::Unauthorized) if json => {
+            print_json_error("unauthorized", "You are not logged in.");
+            return Err(SilentExit(1).into());
+        }
+        Err(e) if json => {
+            print_json_error("request_failed", &format!("Failed to fetch profile: {e}"));
+            return Err(SilentExit(2).into());
+        }
+        Ok(profile) if porcelain => {
+            println!(
+                "{}\t{}\t{:?}\t{}\t{}\t{}\t{}",
+                profile.user.username,
+                profile.user.email.as_deref().unwrap_or_default(),
+                profile.user.tier,
+                u8::from(profile.user.two_factor_enabled),
+                profile.clip_count,
+                profile.storage_used,
+                profile.storage_limit
+            );
+        }
         Ok(profile) => {
             let usage_gb = profile.storage_used as f64 / 1_073_741_824.0;
             let limit_gb = profile.storage_limit as f64 / 1_073_741_824.0;
@@ -19,33 +78,33 @@ pub async fn handle_me() -> Result<()> {
             };
 
             println!("{}", "┌─ Your Profile & Status ─────────".bold());
-            println!("│ {} {}", "Username:".cyan(), profile.user.username);
+            println!("│ {} {}", "Username:".accent(), profile.user.username);
             if let Some(email) = &profile.user.email {
                 let verified = if profile.user.email_verified_at.is_some() {
-                    "✔ Verified".green()
+                    "✔ Verified".success()
                 } else {
-                    "⚠ Not verified".yellow()
+                    "⚠ Not verified".warning()
                 };
-                println!("│ {} {} ({})", "Email:".cyan(), email, verified);
+                println!("│ {} {} ({})", "Email:".accent(), email, verified);
             }
             println!(
                 "│ {} {}",
-                "Tier:".cyan(),
-                format!("{:?}", profile.user.tier).green()
+                "Tier:".accent(),
+                format!("{:?}", profile.user.tier).success()
             );
 
             let two_fa_status = if profile.user.two_factor_enabled {
-                "Enabled ✔".green()
+                "Enabled ✔".success()
             } else {
-                "Disabled".yellow()
+                "Disabled".warning()
             };
-            println!("│ {} {}", "2FA:".cyan(), two_fa_status);
+            println!("│ {} {}", "2FA:".accent(), two_fa_status);
 
             println!("├─ Usage ───────────────────────");
-            println!("│ {} {}", "Hosted Clips:".cyan(), profile.clip_count);
+            println!("│ {} {}", "Hosted Clips:".accent(), profile.clip_count);
             println!(
                 "│ {} {:.2} GB / {} GB ({:.1}%)",
-                "Storage:".cyan(),
+                "Storage:".accent(),
                 usage_gb,
                 limit_gb,
                 percentage
@@ -57,12 +116,12 @@ pub async fn handle_me() -> Result<()> {
             {
                 println!(
                     "│ {} {}",
-                    "Last Login:".cyan(),
+                    "Last Login:".accent(),
                     time.format("%Y-%m-%d %H:%M:%S UTC")
                 );
-                println!("│ {} {}", "From IP:".cyan(), ip);
+                println!("│ {} {}", "From IP:".accent(), ip);
             } else {
-                println!("│ {}", "No login activity recorded.".cyan());
+                println!("│ {}", "No login activity recorded.".accent());
             }
 
             println!("└─────────────────────────────────");
@@ -77,56 +136,367 @@ pub async fn handle_me() -> Result<()> {
     Ok(())
 }
 
-pub async fn handle_share(clip_name: &str) -> Result<()> {
-    let _ = api::get_current_user()
+pub fn format_bytes(bytes: f64) -> String {
+    if bytes >= 1_073_741_824.0 {
+        format!("{:.1} GB", bytes / 1_073_741_824.0)
+    } else {
+        format!("{:.0} MB", bytes / 1_048_576.0)
+    }
+}
+
+fn format_duration(seconds: f64) -> String {
+    let total = seconds.round() as u64;
+    match (total / 60, total % 60) {
+        (0, secs) => format!("{secs}s"),
+        (mins, 0) => format!("{mins}m"),
+        (mins, secs) => format!("{mins}m {secs}s"),
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct ShareOptions<'a> {
+    pub thumbnail_at: Option<&'a str>,
+    pub replace: Option<&'a str>,
+    pub password: Option<&'a str>,
+    pub retries: u32,
+    pub markdown: bool,
+    pub yes: bool,
+}
+
+impl Default for ShareOptions<'_> {
+    fn default() -> Self {
+        Self {
+            thumbnail_at: None,
+            replace: None,
+            password: None,
+            retries: DEFAULT_UPLOAD_RETRIES,
+            markdown: false,
+            yes: false,
+        }
+    }
+}
+
+pub async fn handle_share(
+    clip_name: &str,
+    options: &ShareOptions<'_>,
+    metadata: &ShareMetadata,
+    lookup: ClipLookup,
+) -> Result<Option<String>> {
+    let password = options.password.map(validate_clip_password).transpose()?;
+    let profile = profile_cache::current_user(false)
         .await
         .context("You must be logged in to share clips.")?;
 
-    let clip = find_unified_clip(clip_name).await?;
+    let clip = find_unified_clip(clip_name, lookup).await?;
     let clip_path_str = clip
         .local_path
         .context(format!("Clip '{}' not found locally.", clip.name))?;
     let clip_path = Path::new(&clip_path_str);
 
-    let confirmed = Confirm::new("Are you sure you want to share this clip?")
-        .with_default(true)
-        .prompt()?;
+    let replace_target = match options.replace {
+        Some(target) => {
+            let target = find_unified_clip(target, lookup).await?;
+            let hosted_id = target.hosted_id.context(format!(
+                "'{}' is not hosted, so there is nothing to replace.",
+                target.name
+            ))?;
+            Some((target.name, hosted_id))
+        }
+        None => None,
+    };
 
-    if !confirmed {
-        println!("{}", "○ Share cancelled.".yellow());
-        return Ok(());
+    let thumbnail_at = match options.thumbnail_at {
+        Some(at) => Some(validate_poster_time(clip_path, at).await?),
+        None => None,
+    };
+
+    let max_duration = match profile_cache::max_clip_duration().await {
+        Ok(max_duration) => max_duration,
+        Err(e) => {
+            println!(
+                "{}",
+                format!(
+                    "○ Could not determine your tier's clip length limit ({e:#}); the server will check it during upload."
+                )
+                .warning()
+            );
+            None
+        }
+    };
+    if let Some(max_duration) = max_duration {
+        let duration = probe_clip(clip_path)
+            .await
+            .context(format!("Clip '{}' is not playable", clip.name))?
+            .duration;
+        if duration > max_duration {
+            bail!(
+                "This clip is {} but your {:?} tier allows {}. Trim it with `wayclip edit` before sharing.",
+                format_duration(duration),
+                profile.user.tier,
+                format_duration(max_duration)
+            );
+        }
+    }
+
+    let clip_size = tokio::fs::metadata(clip_path)
+        .await
+        .context(format!("Failed to read {}", clip_path.display()))?
+        .len() as f64;
+    let remaining = (profile.storage_limit as f64 - profile.storage_used as f64).max(0.0);
+    if profile.storage_limit <= 0 {
+        println!(
+            "○ This clip is {}; your storage is unlimited.",
+            format_bytes(clip_size)
+        );
+    } else if clip_size > remaining {
+        println!(
+            "{}",
+            format!(
+                "⚠ This clip is {}, which exceeds your remaining {} of storage.",
+                format_bytes(clip_size),
+                format_bytes(remaining)
+            )
+            .warning()
+        );
+    } else if remaining > 0.0 {
+        println!(
+            "○ This clip is {} and will use {:.0}% of your remaining {}.",
+            format_bytes(clip_size),
+            (clip_size / remaining * 100.0).max(1.0),
+            format_bytes(remaining)
+        );
     }
 
-    println!("{}", "◌ Initializing upload...".yellow());
-    let client = api::get_api_client().await?;
-    match api::share_clip(&client, clip_path).await {
-        Ok(url) => {
-            println!("{}", "✔ Clip shared successfully!".green().bold());
-            println!("  Public URL: {}", url.underline());
+    if !options.yes {
+        let prompt = match &replace_target {
+            Some((target_name, _)) => format!(
+                "Replace the hosted content of '{target_name}' with '{}'? The public URL stays the same.",
+                clip.name
+            ),
+            None => "Are you sure you want to share this clip?".to_string(),
+        };
+        let confirmed = Confirm::new(&prompt).with_default(true).prompt()?;
 
+        if !confirmed {
+            println!("{}", "○ Share cancelled.".warning());
+            return Ok(None);
+        }
+    }
+
+    println!("{}", "◌ Initializing upload...".warning());
+    let progress = upload_progress_bar(&clip.name)?;
+    let (url, clip_id) = match &replace_target {
+        Some((_, hosted_id)) => {
+            let uploaded = replace_upload(clip_path, *hosted_id, &progress, options.retries).await;
+            progress.finish_and_clear();
+            uploaded?;
+            let settings = Settings::load().await?;
+            let cli_settings = CliSettings::load().await?;
+            (
+                clip_public_url(&settings, &cli_settings, hosted_id),
+                *hosted_id,
+            )
+        }
+        None => {
+            let uploaded = share_upload(clip_path, metadata, &progress, options.retries).await;
+            progress.finish_and_clear();
+            let url = uploaded?;
             let clip_id_str = url
                 .split('/')
                 .next_back()
                 .context("Could not parse clip ID from URL")?;
             let clip_id = Uuid::parse_str(clip_id_str)?;
+            (url, clip_id)
+        }
+    };
 
-            let full_filename = clip_path
-                .file_name()
-                .and_then(|s| s.to_str())
-                .context("Invalid filename")?;
-            wayclip_core::update_hosted_id(full_filename, clip_id)
-                .await
-                .context("Failed to save hosted ID to local data file")?;
-
-            match copy_to_clipboard(&url).await {
-                Ok(_) => println!("{}", "✔ URL automatically copied to clipboard!".green()),
-                Err(e) => println!(
-                    "{}",
-                    format!("✗ Could not copy URL to clipboard: {e:#}").yellow()
-                ),
-            }
+    match &replace_target {
+        Some((target_name, _)) => println!(
+            "{}",
+            format!("✔ Hosted clip '{target_name}' updated; its URL is unchanged.")
+                .success()
+                .bold()
+        ),
+        None => println!("{}", "✔ Clip shared successfully!".success().bold()),
+    }
+    let link = format_url(&clip.name, &url, options.markdown);
+    println!("  Public URL: {}", link.underline());
+    profile_cache::invalidate().await;
+
+    let full_filename = clip_path
+        .file_name()
+        .and_then(|s| s.to_str())
+        .context("Invalid filename")?;
+    wayclip_core::update_hosted_id(full_filename, clip_id)
+        .await
+        .context("Failed to save hosted ID to local data file")?;
+
+    if let Some(password) = password {
+        match set_clip_password(clip_id, Some(password)).await {
+            Ok(_) => println!(
+                "{}",
+                "✔ The clip is password-protected; viewers must enter the password to watch."
+                    .success()
+            ),
+            Err(e) => println!(
+                "{}",
+                format!(
+                    "✗ Could not set the password, so the clip is public: {e:#}\nRun `wayclip set-password {}` to retry.",
+                    clip.name
+                )
+                .error()
+            ),
         }
-        Err(e) => bail!("Failed to share clip: {e}"),
     }
+
+    if let Some(at) = &thumbnail_at {
+        match upload_poster(clip_path, clip_id, at).await {
+            Ok(_) => println!(
+                "{}",
+                format!("✔ Thumbnail set to the frame at {at}.").success()
+            ),
+            Err(e) => println!("{}", format!("✗ Could not set thumbnail: {e:#}").warning()),
+        }
+    }
+
+    match copy_to_clipboard(&link).await {
+        Ok(_) => println!("{}", "✔ URL automatically copied to clipboard!".success()),
+        Err(e) => println!(
+            "{}",
+            format!("✗ Could not copy URL to clipboard: {e:#}").warning()
+        ),
+    }
+    Ok(Some(url))
+}
+
+pub async fn handle_anonymous_share(
+    clip_name: &str,
+    markdown: bool,
+    lookup: ClipLookup,
+) -> Result<()> {
+    let clip = find_unified_clip(clip_name, lookup).await?;
+    let clip_path_str = clip
+        .local_path
+        .context(format!("Clip '{}' not found locally.", clip.name))?;
+    let clip_path = Path::new(&clip_path_str);
+
+    println!(
+        "{}",
+        "○ Anonymous shares are not linked to your account and expire automatically.".warning()
+    );
+    let confirmed = Confirm::new("Upload this clip anonymously?")
+        .with_default(true)
+        .prompt()?;
+    if !confirmed {
+        println!("{}", "○ Share cancelled.".warning());
+        return Ok(());
+    }
+
+    let settings = Settings::load().await?;
+    let progress = upload_progress_bar(&clip.name)?;
+    let part = progress_part(clip_path, mime_for(clip_path), &progress).await?;
+    let form = reqwest::multipart::Form::new().part("file", part);
+
+    let response = anonymous_client()
+        .await?
+        .post(format!("{}/api/share/anonymous", settings.api_url))
+        .multipart(form)
+        .send()
+        .await;
+    progress.finish_and_clear();
+    let response = response?;
+
+    if !response.status().is_success() {
+        let error_body: Value = response.json().await.unwrap_or_default();
+        let error_msg = error_body["message"]
+            .as_str()
+            .unwrap_or("The server rejected the upload.");
+        bail!("Anonymous share failed: {error_msg}");
+    }
+
+    let body: Value = response.json().await?;
+    let url = body["url"]
+        .as_str()
+        .context("No URL received from the server. The API response may have changed.")?;
+
+    let link = format_url(&clip.name, url, markdown);
+    println!("{}", "✔ Clip shared anonymously!".success().bold());
+    println!("  Public URL: {}", link.underline());
+    match body["expires_at"]
+        .as_str()
+        .and_then(|s| chrono::DateTime::parse_from_rfc3339(s).ok())
+    {
+        Some(expires_at) => println!(
+            "  {} {}",
+            "Expires:".warning().bold(),
+            expires_at
+                .with_timezone(&chrono::Local)
+                .format("%Y-%m-%d %H:%M")
+                .to_string()
+                .warning()
+                .bold()
+        ),
+        None => println!(
+            "  {}",
+            "Expires: soon (no expiry time reported)".warning().bold()
+        ),
+    }
+
+    match copy_to_clipboard(&link).await {
+        Ok(_) => println!("{}", "✔ URL automatically copied to clipboard!".success()),
+        Err(e) => println!(
+            "{}",
+            format!("✗ Could not copy URL to clipboard: {e:#}").warning()
+        ),
+    }
+    Ok(())
+}
+
+pub async fn handle_analytics(clip_name: &str, lookup: ClipLookup) -> Result<()> {
+    let clip = find_unified_clip(clip_name, lookup).await?;
+    let Some(hosted_id) = clip.hosted_id else {
+        bail!(
+            "'{}' is not a hosted clip, so there are no analytics to show.",
+            clip.name
+        );
+    };
+
+    let settings = Settings::load().await?;
+    let client = api_client().await?;
+    let response = client
+        .get(format!(
+            "{}/api/clips/{}/analytics",
+            settings.api_url, hosted_id
+        ))
+        .send()
+        .await?;
+
+    let status = response.status();
+    if status == reqwest::StatusCode::UNAUTHORIZED {
+        bail!("You are not logged in. Please run `wayclip login` first.");
+    }
+    if !status.is_success() {
+        let error_body: Value = response.json().await.unwrap_or_default();
+        let error_msg = error_body["message"]
+            .as_str()
+            .unwrap_or("Unexpected server response.");
+        bail!("Failed to fetch analytics: {error_msg}");
+    }
+
+    let analytics: Value = response.json().await?;
+    let count = |key: &str| analytics[key].as_u64().unwrap_or(0);
+
+    println!("{}", "┌─ Clip Analytics ────────────────".bold());
+    println!("│ {} {}", "Clip:".accent(), clip.name);
+    println!("├─ Engagement ──────────────────");
+    println!("│ {} {}", "Views:".accent(), count("views"));
+    println!(
+        "│ {} {}",
+        "Unique Viewers:".accent(),
+        count("unique_viewers")
+    );
+    println!("│ {} {}", "Downloads:".accent(), count("downloads"));
+    println!("└─────────────────────────────────");
+
     Ok(())
 }
@@ -0,0 +1,76 @@
+use crate::data_file::{add_clip_tags, remove_clip_tags};
+use crate::import::normalize_tags;
+use crate::theme::Themed;
+use crate::unified_clip::{ClipLookup, find_unified_clip};
+use anyhow::{Result, bail};
+use wayclip_core::models::UnifiedClipData;
+
+pub fn clip_tags(clip: &UnifiedClipData) -> Vec<String> {
+    clip.local_data
+        .as_ref()
+        .map(|d| d.tags.iter().map(ToString::to_string).collect())
+        .unwrap_or_default()
+}
+
+pub fn has_tag(clip: &UnifiedClipData, tag: &str) -> bool {
+    clip_tags(clip).iter().any(|t| t.eq_ignore_ascii_case(tag))
+}
+
+async fn find_local_clip(name: &str, lookup: ClipLookup) -> Result<UnifiedClipData> {
+    let clip = find_unified_clip(name, lookup).await?;
+    if clip.local_data.is_none() || clip.local_path.is_none() {
+        bail!(
+            "Clip '{}' does not exist locally and cannot be tagged.",
+            clip.name
+        );
+    }
+    Ok(clip)
+}
+
+pub async fn handle_tag_add(name: &str, tags: &[String], lookup: ClipLookup) -> Result<()> {
+    let tags = normalize_tags(tags)?;
+    let clip = find_local_clip(name, lookup).await?;
+    if !add_clip_tags(&clip.full_filename, &tags).await? {
+        bail!("Could not tag '{}': it has no local data entry.", clip.name);
+    }
+    println!(
+        "✔ Tagged '{}' with {}.",
+        clip.name.accent(),
+        tags.join(", ").success()
+    );
+    Ok(())
+}
+
+pub async fn handle_tag_remove(name: &str, tags: &[String], lookup: ClipLookup) -> Result<()> {
+    let tags = normalize_tags(tags)?;
+    let clip = find_local_clip(name, lookup).await?;
+    let existing = clip_tags(&clip);
+    if !tags.iter().any(|t| existing.contains(t)) {
+        println!(
+            "{}",
+            format!("○ '{}' has none of those tags.", clip.name).warning()
+        );
+        return Ok(());
+    }
+    if !remove_clip_tags(&clip.full_filename, &tags).await? {
+        bail!(
+            "Could not untag '{}': it has no local data entry.",
+            clip.name
+        );
+    }
+    println!("✔ Removed tags from '{}'.", clip.name.accent());
+    Ok(())
+}
+
+pub async fn handle_tag_list(name: &str, lookup: ClipLookup) -> Result<()> {
+    let clip = find_unified_clip(name, lookup).await?;
+    let tags = clip_tags(&clip);
+    if tags.is_empty() {
+        println!("{}", format!("○ '{}' has no tags.", clip.name).warning());
+        return Ok(());
+    }
+    for tag in tags {
+        println!("{tag}");
+    }
+    Ok(())
+}
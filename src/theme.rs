@@ -0,0 +1,94 @@
+use crate::cli_settings::CliSettings;
+use anyhow::{Result, bail};
+use colored::{Color, ColoredString, Colorize};
+use serde::{Deserialize, Serialize};
+use std::sync::OnceLock;
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq)]
+#[serde(rename_all = "kebab-case")]
+pub enum ThemeName {
+    #[default]
+    Default,
+    Light,
+    Mono,
+}
+
+#[derive(Clone, Copy, Debug)]
+struct Palette {
+    success: Color,
+    warning: Color,
+    error: Color,
+    accent: Color,
+}
+
+const DEFAULT_PALETTE: Palette = Palette {
+    success: Color::Green,
+    warning: Color::Yellow,
+    error: Color::Red,
+    accent: Color::Cyan,
+};
+
+const LIGHT_PALETTE: Palette = Palette {
+    success: Color::Green,
+    warning: Color::Magenta,
+    error: Color::Red,
+    accent: Color::Blue,
+};
+
+static PALETTE: OnceLock<Palette> = OnceLock::new();
+
+fn parse_color(role: &str, value: &str) -> Result<Color> {
+    match value.parse::<Color>() {
+        Ok(color) => Ok(color),
+        Err(_) => bail!("Invalid color '{value}' for '{role}' in theme_colors"),
+    }
+}
+
+pub fn init(settings: &CliSettings) -> Result<()> {
+    let mut palette = match settings.theme {
+        ThemeName::Default => DEFAULT_PALETTE,
+        ThemeName::Light => LIGHT_PALETTE,
+        ThemeName::Mono => {
+            colored::control::set_override(false);
+            DEFAULT_PALETTE
+        }
+    };
+    for (role, value) in &settings.theme_colors {
+        let color = parse_color(role, value)?;
+        match role.as_str() {
+            "success" => palette.success = color,
+            "warning" => palette.warning = color,
+            "error" => palette.error = color,
+            "accent" => palette.accent = color,
+            _ => bail!(
+                "Unknown theme role '{role}' in theme_colors (expected success, warning, error or accent)"
+            ),
+        }
+    }
+    let _ = PALETTE.set(palette);
+    Ok(())
+}
+
+fn palette() -> &'static Palette {
+    PALETTE.get().unwrap_or(&DEFAULT_PALETTE)
+}
+
+pub trait Themed: Colorize + Sized {
+    fn success(self) -> ColoredString {
+        self.color(palette().success)
+    }
+
+    fn warning(self) -> ColoredString {
+        self.color(palette().warning)
+    }
+
+    fn error(self) -> ColoredString {
+        self.color(palette().error)
+    }
+
+    fn accent(self) -> ColoredString {
+        self.color(palette().accent)
+    }
+}
+
+impl<T: Colorize> Themed for T {}
@@ -0,0 +1,272 @@
+use crate::api_client::api_client;
+use crate::encrypt::private_temp_path;
+use crate::probe::probe_clip;
+use crate::theme::Themed;
+use crate::unified_clip::{ClipLookup, find_unified_clip, gather_clips};
+use crate::validate::{ffmpeg_time_to_seconds, validate_ffmpeg_time};
+use anyhow::{Context, Result, bail};
+use colored::*;
+use indicatif::{ProgressBar, ProgressStyle};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tokio::process::Command;
+use tokio::sync::Semaphore;
+use tokio::task::JoinSet;
+use uuid::Uuid;
+use wayclip_core::settings::Settings;
+
+struct ThumbnailJob {
+    name: String,
+    input: PathBuf,
+    output: PathBuf,
+}
+
+async fn extract_thumbnail(input: &Path, output: &Path, at: &str) -> Result<()> {
+    let output_result = Command::new("ffmpeg")
+        .arg("-y")
+        .arg("-ss")
+        .arg(at)
+        .arg("-i")
+        .arg(input)
+        .arg("-frames:v")
+        .arg("1")
+        .arg("-q:v")
+        .arg("2")
+        .arg(output)
+        .output()
+        .await
+        .context("Failed to execute ffmpeg. Is it installed and in your PATH?")?;
+
+    if !output_result.status.success() {
+        let stderr = String::from_utf8_lossy(&output_result.stderr);
+        bail!(
+            "ffmpeg failed with status: {}\n{}",
+            output_result.status,
+            stderr
+        );
+    }
+    Ok(())
+}
+
+pub async fn validate_poster_time(clip_path: &Path, at: &str) -> Result<String> {
+    let at = validate_ffmpeg_time(at)?;
+    let seconds = ffmpeg_time_to_seconds(&at)?;
+    let duration = probe_clip(clip_path).await?.duration;
+    if seconds >= duration {
+        bail!("Thumbnail time {at} is past the end of the clip ({duration:.2}s).");
+    }
+    Ok(at)
+}
+
+pub async fn upload_poster(clip_path: &Path, hosted_id: Uuid, at: &str) -> Result<()> {
+    let frame_path = private_temp_path("jpg");
+    extract_thumbnail(clip_path, &frame_path, at).await?;
+    let bytes = tokio::fs::read(&frame_path).await;
+    let _ = tokio::fs::remove_file(&frame_path).await;
+    let part = reqwest::multipart::Part::bytes(bytes.context("Failed to read extracted frame")?)
+        .file_name("thumbnail.jpg")
+        .mime_str("image/jpeg")?;
+    let form = reqwest::multipart::Form::new().part("thumbnail", part);
+
+    let settings = Settings::load().await?;
+    let client = api_client().await?;
+    let response = client
+        .post(format!(
+            "{}/api/clips/{}/thumbnail",
+            settings.api_url, hosted_id
+        ))
+        .multipart(form)
+        .send()
+        .await?;
+
+    let status = response.status();
+    if status == reqwest::StatusCode::UNAUTHORIZED {
+        bail!("You are not logged in. Please run `wayclip login` first.");
+    }
+    if !status.is_success() {
+        let error_body: serde_json::Value = response.json().await.unwrap_or_default();
+        let error_msg = error_body["message"]
+            .as_str()
+            .unwrap_or("The server rejected the thumbnail.");
+        bail!("Failed to upload thumbnail: {error_msg}");
+    }
+    Ok(())
+}
+
+pub async fn handle_set_thumbnail(name: &str, at: &str, lookup: ClipLookup) -> Result<()> {
+    let clip = find_unified_clip(name, lookup).await?;
+    let hosted_id = clip
+        .hosted_id
+        .context(format!("'{}' is not a hosted clip.", clip.name))?;
+    let clip_path_str = clip.local_path.context(format!(
+        "Clip '{}' must exist locally to extract a thumbnail frame.",
+        clip.name
+    ))?;
+    let clip_path = Path::new(&clip_path_str);
+
+    let at = validate_poster_time(clip_path, at).await?;
+    println!("{}", "◌ Uploading thumbnail...".warning());
+    upload_poster(clip_path, hosted_id, &at).await?;
+    println!(
+        "{}",
+        format!("✔ Thumbnail for '{}' set to the frame at {at}.", clip.name).success()
+    );
+    Ok(())
+}
+
+fn thumbnail_path(
+    name: &str,
+    clip_path: &Path,
+    output_dir: Option<&Path>,
+    output: Option<&Path>,
+) -> PathBuf {
+    if let Some(output) = output {
+        return output.to_path_buf();
+    }
+    let file_name = format!("{name}.jpg");
+    match output_dir {
+        Some(dir) => dir.join(file_name),
+        None => clip_path.with_file_name(file_name),
+    }
+}
+
+fn validate_image_output(output: &Path) -> Result<()> {
+    let ext = output
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(|e| e.to_lowercase());
+    match ext.as_deref() {
+        Some("png" | "jpg" | "jpeg") => Ok(()),
+        _ => bail!(
+            "Output '{}' must end in .png, .jpg or .jpeg.",
+            output.display()
+        ),
+    }
+}
+
+async fn frame_time(input: &Path, at: Option<&str>) -> Result<String> {
+    match at {
+        Some(at) => Ok(at.to_string()),
+        None => {
+            let duration = probe_clip(input).await?.duration;
+            Ok(format!("{:.3}", duration / 2.0))
+        }
+    }
+}
+
+pub async fn handle_thumbnail(
+    names: &[String],
+    all: bool,
+    output_dir: Option<&Path>,
+    output: Option<&Path>,
+    at: Option<&str>,
+    jobs: usize,
+    lookup: ClipLookup,
+) -> Result<()> {
+    let at = at.map(validate_ffmpeg_time).transpose()?;
+
+    if names.is_empty() && !all {
+        bail!("Specify one or more clip names, or use --all.");
+    }
+    if let Some(output) = output {
+        if names.len() != 1 {
+            bail!("--output can only be used with a single clip name.");
+        }
+        validate_image_output(output)?;
+    }
+
+    if let Some(dir) = output_dir {
+        tokio::fs::create_dir_all(dir).await.context(format!(
+            "Failed to create output directory {}",
+            dir.display()
+        ))?;
+    }
+
+    let clips = if all {
+        gather_clips(lookup).await?
+    } else {
+        let mut clips = Vec::new();
+        for name in names {
+            clips.push(find_unified_clip(name, lookup).await?);
+        }
+        clips
+    };
+
+    let mut thumbnail_jobs = Vec::new();
+    for clip in clips {
+        match clip.local_path {
+            Some(local_path) => {
+                let input = PathBuf::from(local_path);
+                let output = thumbnail_path(&clip.name, &input, output_dir, output);
+                thumbnail_jobs.push(ThumbnailJob {
+                    name: clip.name,
+                    input,
+                    output,
+                });
+            }
+            None => println!(
+                "{}",
+                format!("○ Skipping '{}' (no local file).", clip.name).warning()
+            ),
+        }
+    }
+
+    if thumbnail_jobs.is_empty() {
+        println!(
+            "{}",
+            "○ No local clips to generate thumbnails for.".warning()
+        );
+        return Ok(());
+    }
+
+    let progress = ProgressBar::new(thumbnail_jobs.len() as u64);
+    progress.set_style(
+        ProgressStyle::with_template("{spinner:.cyan} [{bar:30.cyan/blue}] {pos}/{len} {msg}")?
+            .progress_chars("█▓░"),
+    );
+
+    let semaphore = Arc::new(Semaphore::new(jobs.max(1)));
+    let mut tasks = JoinSet::new();
+    for job in thumbnail_jobs {
+        let semaphore = semaphore.clone();
+        let at = at.clone();
+        tasks.spawn(async move {
+            let _permit = semaphore.acquire_owned().await;
+            let result = match frame_time(&job.input, at.as_deref()).await {
+                Ok(at) => extract_thumbnail(&job.input, &job.output, &at).await,
+                Err(e) => Err(e),
+            };
+            (job, result)
+        });
+    }
+
+    let mut succeeded = 0;
+    let mut failed = 0;
+    while let Some(joined) = tasks.join_next().await {
+        let (job, result) = joined?;
+        match result {
+            Ok(_) => {
+                succeeded += 1;
+                progress.println(format!("✔ {} → {}", job.name, job.output.display()));
+            }
+            Err(e) => {
+                failed += 1;
+                progress.println(format!("{} {}: {e:#}", "✗".error(), job.name));
+            }
+        }
+        progress.set_message(job.name);
+        progress.inc(1);
+    }
+    progress.finish_and_clear();
+
+    if failed > 0 {
+        bail!("Generated {succeeded} thumbnails, {failed} failed.");
+    }
+    println!(
+        "{}",
+        format!("✔ Generated {succeeded} thumbnails.")
+            .success()
+            .bold()
+    );
+    Ok(())
+}
@@ -0,0 +1,78 @@
+use crate::cli_settings::CliSettings;
+use crate::theme::Themed;
+use anyhow::{Context, Result};
+use keyring::Entry;
+use serde::{Deserialize, Serialize};
+use wayclip_core::api;
+use wayclip_core::settings::Settings;
+
+const KEYRING_SERVICE: &str = "wayclip";
+const KEYRING_USER: &str = "session-token";
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq)]
+#[serde(rename_all = "kebab-case")]
+pub enum TokenStorage {
+    #[default]
+    File,
+    Keyring,
+}
+
+fn keyring_entry() -> Result<Entry> {
+    Entry::new(KEYRING_SERVICE, KEYRING_USER).context("Failed to access the system keyring")
+}
+
+fn read_keyring_token() -> Option<String> {
+    keyring_entry().ok()?.get_password().ok()
+}
+
+pub async fn store_token(token: String) -> Result<()> {
+    let storage = CliSettings::load().await?.token_storage;
+    if storage == TokenStorage::Keyring {
+        let saved = keyring_entry().and_then(|entry| {
+            entry
+                .set_password(&token)
+                .context("Failed to save the token to the system keyring")
+        });
+        match saved {
+            Ok(()) => return clear_file_token().await,
+            Err(e) => println!(
+                "{}",
+                format!("⚠ {e:#}; falling back to file-based token storage.").warning()
+            ),
+        }
+    }
+    api::login(token).await
+}
+
+async fn clear_file_token() -> Result<()> {
+    let mut settings = Settings::load().await?;
+    if settings.auth_token.take().is_some() {
+        settings.save().await?;
+    }
+    Ok(())
+}
+
+pub async fn session_token() -> Result<Option<String>> {
+    let storage = CliSettings::load().await?.token_storage;
+    match (storage == TokenStorage::Keyring)
+        .then(read_keyring_token)
+        .flatten()
+    {
+        Some(token) => Ok(Some(token)),
+        None => Ok(Settings::load().await?.auth_token),
+    }
+}
+
+pub async fn clear_tokens() -> Result<()> {
+    if let Ok(entry) = keyring_entry() {
+        match entry.delete_credential() {
+            Ok(_) | Err(keyring::Error::NoEntry) => {}
+            Err(e) => println!(
+                "{}",
+                format!("⚠ Could not remove the token from the system keyring: {e}").warning()
+            ),
+        }
+    }
+    api::logout().await?;
+    Ok(())
+}
@@ -0,0 +1,208 @@
+use crate::data_file::write_atomic;
+use crate::theme::Themed;
+use crate::unified_clip::clips_directory;
+use anyhow::{Context, Result, bail};
+use chrono::{DateTime, Local, Utc};
+use colored::*;
+use inquire::Confirm;
+use serde::{Deserialize, Serialize};
+use std::cmp::Reverse;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use wayclip_core::settings::Settings;
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+struct TrashEntry {
+    original_path: PathBuf,
+    deleted_at: i64,
+}
+
+#[derive(Serialize, Deserialize, Default, Debug)]
+#[serde(default)]
+struct TrashIndex {
+    entries: BTreeMap<String, TrashEntry>,
+}
+
+fn trash_directory(settings: &Settings) -> PathBuf {
+    clips_directory(settings).join(".wayclip-trash")
+}
+
+fn index_path(trash_dir: &Path) -> PathBuf {
+    trash_dir.join("index.json")
+}
+
+async fn load_index(trash_dir: &Path) -> Result<TrashIndex> {
+    let path = index_path(trash_dir);
+    if !path.exists() {
+        return Ok(TrashIndex::default());
+    }
+    let contents = tokio::fs::read(&path)
+        .await
+        .context(format!("Failed to read {}", path.display()))?;
+    serde_json::from_slice(&contents).context(format!("Failed to parse {}", path.display()))
+}
+
+async fn save_index(trash_dir: &Path, index: &TrashIndex) -> Result<()> {
+    write_atomic(&index_path(trash_dir), &serde_json::to_vec_pretty(index)?).await
+}
+
+fn clip_name(entry: &TrashEntry) -> String {
+    entry
+        .original_path
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().to_string())
+        .unwrap_or_default()
+}
+
+fn format_deleted_at(millis: i64) -> String {
+    DateTime::from_timestamp_millis(millis)
+        .map(|time| {
+            time.with_timezone(&Local)
+                .format("%Y-%m-%d %H:%M")
+                .to_string()
+        })
+        .unwrap_or_default()
+}
+
+pub async fn move_to_trash(local_path: &Path) -> Result<()> {
+    let settings = Settings::load().await?;
+    let trash_dir = trash_directory(&settings);
+    tokio::fs::create_dir_all(&trash_dir)
+        .await
+        .context(format!("Failed to create {}", trash_dir.display()))?;
+
+    let file_name = local_path
+        .file_name()
+        .and_then(|s| s.to_str())
+        .context("Invalid filename")?;
+    let deleted_at = Utc::now().timestamp_millis();
+    let trash_name = format!("{deleted_at}-{file_name}");
+    tokio::fs::rename(local_path, trash_dir.join(&trash_name))
+        .await
+        .context(format!(
+            "Failed to move {} to the trash",
+            local_path.display()
+        ))?;
+
+    let mut index = load_index(&trash_dir).await?;
+    index.entries.insert(
+        trash_name,
+        TrashEntry {
+            original_path: local_path.to_path_buf(),
+            deleted_at,
+        },
+    );
+    save_index(&trash_dir, &index).await
+}
+
+pub async fn handle_trash_list() -> Result<()> {
+    let settings = Settings::load().await?;
+    let index = load_index(&trash_directory(&settings)).await?;
+    if index.entries.is_empty() {
+        println!("{}", "○ The trash is empty.".warning());
+        return Ok(());
+    }
+
+    let mut entries: Vec<&TrashEntry> = index.entries.values().collect();
+    entries.sort_by_key(|e| Reverse(e.deleted_at));
+    println!("{} clips in the trash:", entries.len());
+    for entry in entries {
+        println!(
+            "  {} {}",
+            clip_name(entry).accent(),
+            format!("(deleted {})", format_deleted_at(entry.deleted_at)).dimmed()
+        );
+    }
+    Ok(())
+}
+
+pub async fn handle_trash_restore(name: &str) -> Result<()> {
+    let settings = Settings::load().await?;
+    let trash_dir = trash_directory(&settings);
+    let mut index = load_index(&trash_dir).await?;
+
+    let name = name.trim();
+    let name = name.strip_suffix(".mp4").unwrap_or(name);
+    let (trash_name, entry) = index
+        .entries
+        .iter()
+        .filter(|(_, entry)| clip_name(entry).eq_ignore_ascii_case(name))
+        .max_by_key(|(_, entry)| entry.deleted_at)
+        .map(|(trash_name, entry)| (trash_name.clone(), entry.clone()))
+        .context(format!("'{name}' is not in the trash."))?;
+
+    if entry.original_path.exists() {
+        bail!(
+            "Cannot restore '{name}': {} already exists. Rename or delete it first.",
+            entry.original_path.display()
+        );
+    }
+    if let Some(parent) = entry.original_path.parent() {
+        tokio::fs::create_dir_all(parent).await?;
+    }
+    tokio::fs::rename(trash_dir.join(&trash_name), &entry.original_path)
+        .await
+        .context(format!("Failed to restore '{name}'"))?;
+    index.entries.remove(&trash_name);
+    save_index(&trash_dir, &index).await?;
+
+    println!(
+        "{}",
+        format!("✔ Restored '{name}' to {}", entry.original_path.display()).success()
+    );
+    Ok(())
+}
+
+pub async fn handle_trash_empty(yes: bool) -> Result<()> {
+    let settings = Settings::load().await?;
+    let trash_dir = trash_directory(&settings);
+    let mut index = load_index(&trash_dir).await?;
+    if index.entries.is_empty() {
+        println!("{}", "○ The trash is already empty.".warning());
+        return Ok(());
+    }
+
+    if !yes {
+        let confirmed = Confirm::new(&format!(
+            "Permanently delete {} clips in the trash? This cannot be undone.",
+            index.entries.len()
+        ))
+        .with_default(false)
+        .prompt()?;
+        if !confirmed {
+            println!("{}", "○ Operation cancelled.".warning());
+            return Ok(());
+        }
+    }
+
+    let total = index.entries.len();
+    let mut failed = 0;
+    let trash_names: Vec<String> = index.entries.keys().cloned().collect();
+    for trash_name in trash_names {
+        let path = trash_dir.join(&trash_name);
+        match tokio::fs::remove_file(&path).await {
+            Ok(_) => {
+                index.entries.remove(&trash_name);
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                index.entries.remove(&trash_name);
+            }
+            Err(e) => {
+                failed += 1;
+                println!("{} {}: {e}", "✗".error(), path.display());
+            }
+        }
+    }
+    save_index(&trash_dir, &index).await?;
+
+    if failed > 0 {
+        bail!("{failed} of {total} trashed clips could not be deleted.");
+    }
+    println!(
+        "{}",
+        format!("✔ Permanently deleted {total} clips.")
+            .success()
+            .bold()
+    );
+    Ok(())
+}
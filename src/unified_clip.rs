@@ -1,8 +1,210 @@
+use crate::alias::resolve_alias;
+use crate::api_client::hosted_clips_index;
+use crate::cli_settings::CliSettings;
+use crate::data_file::{read_clip_entries, register_clip_entries};
+use crate::sidecar::fill_from_sidecars;
+use crate::theme::Themed;
 use anyhow::{Context, Result, bail};
-use wayclip_core::gather_unified_clips;
+use chrono::{DateTime, Local, Utc};
+use inquire::Select;
+use regex::RegexBuilder;
+use std::collections::{HashMap, HashSet};
+use std::env;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
 use wayclip_core::models::UnifiedClipData;
+use wayclip_core::settings::Settings;
 
-pub async fn find_unified_clip(name_input: &str) -> Result<UnifiedClipData> {
+const DEFAULT_SERVER_TIMEOUT_SECS: u64 = 10;
+const CLIP_EXTENSIONS: [&str; 5] = ["mp4", "mkv", "mov", "webm", "flv"];
+
+static OFFLINE_WARNED: AtomicBool = AtomicBool::new(false);
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct ClipLookup {
+    pub fuzzy: bool,
+    pub offline: bool,
+}
+
+pub fn clips_directory(settings: &Settings) -> PathBuf {
+    env::var_os("HOME")
+        .map(PathBuf::from)
+        .unwrap_or_default()
+        .join(&settings.save_path_from_home_string)
+}
+
+async fn gather_local_clips() -> Result<Vec<UnifiedClipData>> {
+    let settings = Settings::load().await?;
+    let clips_dir = clips_directory(&settings);
+    let mut local_entries = read_clip_entries().await;
+    let mut unregistered = HashSet::new();
+    let mut clips = Vec::new();
+    let Ok(mut entries) = tokio::fs::read_dir(&clips_dir).await else {
+        return Ok(clips);
+    };
+    while let Some(entry) = entries.next_entry().await? {
+        let path = entry.path();
+        let is_clip = path
+            .extension()
+            .and_then(|e| e.to_str())
+            .is_some_and(|e| CLIP_EXTENSIONS.contains(&e.to_lowercase().as_str()));
+        if !path.is_file() || !is_clip {
+            continue;
+        }
+        let (Some(name), Some(full_filename)) = (
+            path.file_stem().and_then(|s| s.to_str()),
+            path.file_name().and_then(|s| s.to_str()),
+        ) else {
+            continue;
+        };
+        let created_at: DateTime<Utc> = entry
+            .metadata()
+            .await
+            .and_then(|m| m.created().or_else(|_| m.modified()))
+            .map(DateTime::from)
+            .unwrap_or_else(|_| Utc::now());
+        let local_data = local_entries.remove(full_filename);
+        if local_data.is_none() {
+            unregistered.insert(full_filename.to_string());
+        }
+        clips.push(UnifiedClipData {
+            name: name.to_string(),
+            full_filename: full_filename.to_string(),
+            local_path: Some(path.display().to_string()),
+            hosted_id: local_data.as_ref().and_then(|d| d.hosted_id),
+            is_hosted: false,
+            local_data,
+            created_at: created_at.into(),
+        });
+    }
+    fill_from_sidecars(&mut clips);
+    let new_entries: Vec<_> = clips
+        .iter()
+        .filter(|clip| unregistered.contains(&clip.full_filename))
+        .map(|clip| {
+            (
+                clip.full_filename.clone(),
+                clip.local_data.clone().unwrap_or_default(),
+            )
+        })
+        .collect();
+    if !new_entries.is_empty() {
+        register_clip_entries(new_entries).await?;
+    }
+    Ok(clips)
+}
+
+pub async fn gather_unified_clips() -> Result<Vec<UnifiedClipData>> {
+    let mut hosted: HashMap<_, _> = hosted_clips_index()
+        .await?
+        .into_iter()
+        .map(|clip| (clip.id, clip))
+        .collect();
+    let mut clips = gather_local_clips().await?;
+    for clip in &mut clips {
+        clip.is_hosted = clip
+            .hosted_id
+            .is_some_and(|id| hosted.remove(&id).is_some());
+    }
+    for (id, hosted_clip) in hosted {
+        let name = Path::new(&hosted_clip.file_name)
+            .file_stem()
+            .and_then(|s| s.to_str())
+            .unwrap_or(&hosted_clip.file_name)
+            .to_string();
+        clips.push(UnifiedClipData {
+            name,
+            full_filename: hosted_clip.file_name,
+            local_path: None,
+            local_data: None,
+            created_at: hosted_clip.created_at.with_timezone(&Local),
+            is_hosted: true,
+            hosted_id: Some(id),
+        });
+    }
+    Ok(clips)
+}
+
+pub async fn gather_clips(lookup: ClipLookup) -> Result<Vec<UnifiedClipData>> {
+    if lookup.offline {
+        return gather_local_clips().await;
+    }
+
+    let cli_settings = CliSettings::load().await?;
+    let timeout = Duration::from_secs(
+        cli_settings
+            .server_timeout_secs
+            .unwrap_or(DEFAULT_SERVER_TIMEOUT_SECS)
+            .max(1),
+    );
+
+    let mut last_error = None;
+    for _ in 0..=cli_settings.server_retries {
+        match tokio::time::timeout(timeout, gather_unified_clips()).await {
+            Ok(Ok(clips)) => return Ok(clips),
+            Ok(Err(e)) => last_error = Some(format!("{e:#}")),
+            Err(_) => last_error = Some(format!("timed out after {}s", timeout.as_secs())),
+        }
+    }
+
+    if !OFFLINE_WARNED.swap(true, Ordering::SeqCst) {
+        eprintln!(
+            "{}",
+            format!(
+                "⚠ Server unreachable ({}), showing local clips only.",
+                last_error.unwrap_or_default()
+            )
+            .warning()
+        );
+    }
+    gather_local_clips().await
+}
+
+const MAX_SUGGESTIONS: usize = 3;
+
+fn levenshtein(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, a_char) in a.chars().enumerate() {
+        let mut current = vec![i + 1; b.len() + 1];
+        for (j, b_char) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(a_char != *b_char);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
+
+fn did_you_mean(clips: &[UnifiedClipData], name: &str) -> String {
+    let needle = name.to_lowercase();
+    let max_distance = (needle.chars().count() / 3).max(2);
+    let mut scored: Vec<(usize, &str)> = clips
+        .iter()
+        .filter_map(|clip| {
+            let candidate = clip.name.to_lowercase();
+            let distance = if candidate.contains(&needle) {
+                0
+            } else {
+                levenshtein(&needle, &candidate)
+            };
+            (distance <= max_distance).then_some((distance, clip.name.as_str()))
+        })
+        .collect();
+    if scored.is_empty() {
+        return String::new();
+    }
+    scored.sort();
+    let names: Vec<&str> = scored
+        .into_iter()
+        .take(MAX_SUGGESTIONS)
+        .map(|(_, name)| name)
+        .collect();
+    format!(" Did you mean: {}?", names.join(", "))
+}
+
+pub async fn find_unified_clip(name_input: &str, lookup: ClipLookup) -> Result<UnifiedClipData> {
     let trimmed_name = name_input.trim();
 
     let name_stem = if trimmed_name.to_lowercase().ends_with(".mp4") {
@@ -15,9 +217,89 @@ pub async fn find_unified_clip(name_input: &str) -> Result<UnifiedClipData> {
         bail!("Clip name cannot be empty.");
     }
 
-    let all_clips = gather_unified_clips().await?;
-    all_clips
+    let cli_settings = CliSettings::load().await?;
+    let name_stem = resolve_alias(&cli_settings, name_stem).unwrap_or(name_stem);
+
+    let mut all_clips = gather_clips(lookup).await?;
+    if let Some(index) = all_clips
+        .iter()
+        .position(|clip| clip.name.eq_ignore_ascii_case(name_stem))
+    {
+        return Ok(all_clips.swap_remove(index));
+    }
+    if !lookup.fuzzy {
+        bail!(
+            "Clip '{name_stem}' not found.{}",
+            did_you_mean(&all_clips, name_stem)
+        );
+    }
+
+    let suggestions = did_you_mean(&all_clips, name_stem);
+    let needle = name_stem.to_lowercase();
+    let mut candidates: Vec<UnifiedClipData> = all_clips
+        .into_iter()
+        .filter(|clip| clip.name.to_lowercase().contains(&needle))
+        .collect();
+    match candidates.len() {
+        0 => bail!("No clip name contains '{name_stem}'.{suggestions}"),
+        1 => Ok(candidates.remove(0)),
+        _ => {
+            candidates.sort_by(|a, b| a.name.cmp(&b.name));
+            let names: Vec<String> = candidates.iter().map(|c| c.name.clone()).collect();
+            let choice = Select::new(
+                &format!("{} clips match '{name_stem}'. Which one?", names.len()),
+                names,
+            )
+            .prompt()?;
+            Ok(candidates
+                .into_iter()
+                .find(|clip| clip.name == choice)
+                .unwrap())
+        }
+    }
+}
+
+pub async fn handle_resolve(name: &str, stem: bool, lookup: ClipLookup) -> Result<()> {
+    let clip = find_unified_clip(name, lookup).await?;
+    if stem {
+        println!("{}", clip.name);
+    } else {
+        println!("{}", clip.full_filename);
+    }
+    Ok(())
+}
+
+pub fn is_glob_pattern(input: &str) -> bool {
+    input.contains(['*', '?'])
+}
+
+pub async fn find_unified_clips_matching(
+    pattern: &str,
+    lookup: ClipLookup,
+) -> Result<Vec<UnifiedClipData>> {
+    let trimmed = pattern.trim();
+    let stem = if trimmed.to_lowercase().ends_with(".mp4") {
+        &trimmed[..trimmed.len() - 4]
+    } else {
+        trimmed
+    };
+    if stem.is_empty() {
+        bail!("Clip pattern cannot be empty.");
+    }
+
+    let regex_source = regex::escape(stem).replace(r"\*", ".*").replace(r"\?", ".");
+    let matcher = RegexBuilder::new(&format!("^{regex_source}$"))
+        .case_insensitive(true)
+        .build()
+        .context(format!("Invalid clip pattern '{stem}'"))?;
+
+    let matches: Vec<UnifiedClipData> = gather_clips(lookup)
+        .await?
         .into_iter()
-        .find(|clip| clip.name.eq_ignore_ascii_case(name_stem))
-        .context(format!("Clip '{name_stem}' not found."))
+        .filter(|clip| matcher.is_match(&clip.name))
+        .collect();
+    if matches.is_empty() {
+        bail!("No clips match '{stem}'.");
+    }
+    Ok(matches)
 }
@@ -0,0 +1,209 @@
+use crate::api_client::api_client;
+use crate::download::bytes_style;
+use crate::model::ShareVisibility;
+use crate::theme::Themed;
+use anyhow::{Context, Result, anyhow};
+use futures_util::{TryStreamExt, stream};
+use indicatif::ProgressBar;
+use reqwest::multipart::{Form, Part};
+use serde_json::Value;
+use std::future::Future;
+use std::path::Path;
+use std::time::Duration;
+use tokio::io::AsyncReadExt;
+use uuid::Uuid;
+use wayclip_core::settings::Settings;
+
+const UPLOAD_CHUNK_SIZE: usize = 256 * 1024;
+const INITIAL_RETRY_DELAY: Duration = Duration::from_secs(1);
+const MAX_RETRY_DELAY: Duration = Duration::from_secs(30);
+pub const DEFAULT_UPLOAD_RETRIES: u32 = 3;
+
+#[derive(Debug, Default)]
+pub struct ShareMetadata {
+    pub title: Option<String>,
+    pub description: Option<String>,
+    pub visibility: Option<ShareVisibility>,
+}
+
+impl ShareMetadata {
+    fn apply(&self, mut form: Form) -> Form {
+        if let Some(title) = &self.title {
+            form = form.text("title", title.clone());
+        }
+        if let Some(description) = &self.description {
+            form = form.text("description", description.clone());
+        }
+        if let Some(visibility) = self.visibility {
+            form = form.text("visibility", visibility.to_string());
+        }
+        form
+    }
+}
+
+enum UploadFailure {
+    Retryable(anyhow::Error),
+    Permanent(anyhow::Error),
+}
+
+impl From<anyhow::Error> for UploadFailure {
+    fn from(error: anyhow::Error) -> Self {
+        UploadFailure::Permanent(error)
+    }
+}
+
+pub async fn progress_part(path: &Path, mime: &str, progress: &ProgressBar) -> Result<Part> {
+    let file_name = path
+        .file_name()
+        .and_then(|s| s.to_str())
+        .context("Invalid filename")?
+        .to_string();
+    let file = tokio::fs::File::open(path)
+        .await
+        .context(format!("Failed to read {}", path.display()))?;
+    let length = file.metadata().await?.len();
+    progress.set_length(length);
+    progress.set_position(0);
+
+    let progress = progress.clone();
+    let chunks = stream::try_unfold(file, |mut file| async move {
+        let mut buffer = vec![0; UPLOAD_CHUNK_SIZE];
+        let read = file.read(&mut buffer).await?;
+        if read == 0 {
+            return Ok::<_, std::io::Error>(None);
+        }
+        buffer.truncate(read);
+        Ok(Some((buffer, file)))
+    })
+    .inspect_ok(move |chunk| progress.inc(chunk.len() as u64));
+
+    Ok(
+        Part::stream_with_length(reqwest::Body::wrap_stream(chunks), length)
+            .file_name(file_name)
+            .mime_str(mime)?,
+    )
+}
+
+pub fn upload_progress_bar(label: &str) -> Result<ProgressBar> {
+    let progress = ProgressBar::new(0);
+    progress.set_style(bytes_style()?);
+    progress.set_message(label.to_string());
+    Ok(progress)
+}
+
+async fn send_upload(
+    request: reqwest::RequestBuilder,
+    action: &str,
+) -> Result<reqwest::Response, UploadFailure> {
+    let response = match request.send().await {
+        Ok(response) => response,
+        Err(e) if e.is_timeout() || e.is_connect() || e.is_request() || e.is_body() => {
+            return Err(UploadFailure::Retryable(anyhow!(e)));
+        }
+        Err(e) => return Err(UploadFailure::Permanent(anyhow!(e))),
+    };
+
+    let status = response.status();
+    if status == reqwest::StatusCode::UNAUTHORIZED {
+        return Err(UploadFailure::Permanent(anyhow!(
+            "You are not logged in. Please run `wayclip login` first."
+        )));
+    }
+    if status.is_success() {
+        return Ok(response);
+    }
+    let error_body: Value = response.json().await.unwrap_or_default();
+    let error_msg = error_body["message"]
+        .as_str()
+        .unwrap_or("The server rejected the upload.");
+    let error = anyhow!("{action}: {error_msg}");
+    if status.is_server_error() || status == reqwest::StatusCode::TOO_MANY_REQUESTS {
+        Err(UploadFailure::Retryable(error))
+    } else {
+        Err(UploadFailure::Permanent(error))
+    }
+}
+
+async fn with_retries<T, F, Fut>(retries: u32, progress: &ProgressBar, mut attempt: F) -> Result<T>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, UploadFailure>>,
+{
+    let mut delay = INITIAL_RETRY_DELAY;
+    let mut failures = 0;
+    loop {
+        match attempt().await {
+            Ok(value) => return Ok(value),
+            Err(UploadFailure::Permanent(e)) => return Err(e),
+            Err(UploadFailure::Retryable(e)) if failures >= retries => {
+                return Err(e.context(format!("Upload failed after {} attempts", failures + 1)));
+            }
+            Err(UploadFailure::Retryable(e)) => {
+                failures += 1;
+                progress.suspend(|| {
+                    println!(
+                        "{}",
+                        format!(
+                            "⚠ Upload interrupted ({e:#}); retrying in {}s ({failures}/{retries})...",
+                            delay.as_secs()
+                        )
+                        .warning()
+                    )
+                });
+                tokio::time::sleep(delay).await;
+                delay = (delay * 2).min(MAX_RETRY_DELAY);
+            }
+        }
+    }
+}
+
+pub async fn share_upload(
+    clip_path: &Path,
+    metadata: &ShareMetadata,
+    progress: &ProgressBar,
+    retries: u32,
+) -> Result<String> {
+    let settings = Settings::load().await?;
+    let client = api_client().await?;
+    let url = format!("{}/api/share", settings.api_url);
+    let (client, url) = (&client, url.as_str());
+
+    with_retries(retries, progress, move || async move {
+        let form = metadata.apply(Form::new()).part(
+            "file",
+            progress_part(clip_path, "video/mp4", progress).await?,
+        );
+        let request = client.post(url).multipart(form);
+        let response = send_upload(request, "Failed to share clip").await?;
+
+        let body: Value = response.json().await.map_err(|e| anyhow!(e))?;
+        let public_url = body["url"]
+            .as_str()
+            .context("No URL received from the server. The API response may have changed.")?;
+        Ok::<_, UploadFailure>(public_url.to_string())
+    })
+    .await
+}
+
+pub async fn replace_upload(
+    clip_path: &Path,
+    hosted_id: Uuid,
+    progress: &ProgressBar,
+    retries: u32,
+) -> Result<()> {
+    let settings = Settings::load().await?;
+    let client = api_client().await?;
+    let url = format!("{}/api/clips/{}/file", settings.api_url, hosted_id);
+    let (client, url) = (&client, url.as_str());
+
+    with_retries(retries, progress, move || async move {
+        let form = Form::new().part(
+            "file",
+            progress_part(clip_path, "video/mp4", progress).await?,
+        );
+        let request = client.put(url).multipart(form);
+        send_upload(request, "Failed to replace hosted clip").await?;
+        Ok::<_, UploadFailure>(())
+    })
+    .await
+}
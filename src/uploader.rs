@@ -0,0 +1,163 @@
+use crate::cli_settings::CliSettings;
+use crate::clipboard::copy_to_clipboard;
+use crate::theme::Themed;
+use crate::unified_clip::{ClipLookup, find_unified_clip};
+use crate::upload::{progress_part, upload_progress_bar};
+use crate::url::format_url;
+use anyhow::{Context, Result, bail};
+use colored::*;
+use inquire::Confirm;
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::path::Path;
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct UploaderConfig {
+    pub url: String,
+    #[serde(default = "default_file_field")]
+    pub file_field: String,
+    #[serde(default)]
+    pub fields: BTreeMap<String, String>,
+    #[serde(default)]
+    pub headers: BTreeMap<String, String>,
+    #[serde(default)]
+    pub url_path: Option<String>,
+}
+
+fn default_file_field() -> String {
+    "file".to_string()
+}
+
+fn expand_template(template: &str, name: &str, file_name: &str) -> String {
+    template
+        .replace("{name}", name)
+        .replace("{filename}", file_name)
+}
+
+fn header_map(headers: &BTreeMap<String, String>) -> Result<HeaderMap> {
+    let mut map = HeaderMap::new();
+    for (name, value) in headers {
+        let header_name = HeaderName::from_bytes(name.as_bytes())
+            .context(format!("Invalid header name '{name}' in uploader"))?;
+        let header_value = HeaderValue::from_str(value)
+            .context(format!("Invalid value for header '{name}' in uploader"))?;
+        map.insert(header_name, header_value);
+    }
+    Ok(map)
+}
+
+fn extract_url(body: &str, url_path: Option<&str>) -> Result<String> {
+    let Some(path) = url_path else {
+        let url = body.trim();
+        if url.is_empty() {
+            bail!("The uploader returned an empty response.");
+        }
+        return Ok(url.to_string());
+    };
+
+    let json: Value =
+        serde_json::from_str(body).context("The uploader did not return valid JSON")?;
+    let pointer = format!("/{}", path.trim_start_matches('.').replace('.', "/"));
+    json.pointer(&pointer)
+        .and_then(Value::as_str)
+        .map(String::from)
+        .context(format!("No URL found at '{path}' in the uploader response"))
+}
+
+pub fn mime_for(path: &Path) -> &'static str {
+    match path
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(str::to_lowercase)
+        .as_deref()
+    {
+        Some("mp4") => "video/mp4",
+        Some("mkv") => "video/x-matroska",
+        Some("webm") => "video/webm",
+        Some("mov") => "video/quicktime",
+        _ => "application/octet-stream",
+    }
+}
+
+pub async fn handle_uploader_share(
+    clip_name: &str,
+    uploader_name: &str,
+    markdown: bool,
+    lookup: ClipLookup,
+) -> Result<()> {
+    let cli_settings = CliSettings::load().await?;
+    let Some(uploader) = cli_settings.uploaders.get(uploader_name) else {
+        let known: Vec<&str> = cli_settings.uploaders.keys().map(String::as_str).collect();
+        if known.is_empty() {
+            bail!(
+                "Uploader '{uploader_name}' is not configured. Add it under \"uploaders\" in {}.",
+                CliSettings::path().display()
+            );
+        }
+        bail!(
+            "Uploader '{uploader_name}' is not configured. Available uploaders: {}",
+            known.join(", ")
+        );
+    };
+
+    let clip = find_unified_clip(clip_name, lookup).await?;
+    let clip_path_str = clip
+        .local_path
+        .context(format!("Clip '{}' not found locally.", clip.name))?;
+    let clip_path = Path::new(&clip_path_str);
+    let file_name = clip_path
+        .file_name()
+        .and_then(|s| s.to_str())
+        .context("Invalid filename")?
+        .to_string();
+
+    let confirmed = Confirm::new(&format!("Upload '{}' to '{uploader_name}'?", clip.name))
+        .with_help_message("This clip will not be linked to your Wayclip account.")
+        .with_default(true)
+        .prompt()?;
+    if !confirmed {
+        println!("{}", "○ Share cancelled.".warning());
+        return Ok(());
+    }
+
+    let progress = upload_progress_bar(&clip.name)?;
+    let part = progress_part(clip_path, mime_for(clip_path), &progress).await?;
+    let mut form = reqwest::multipart::Form::new().part(uploader.file_field.clone(), part);
+    for (key, value) in &uploader.fields {
+        form = form.text(key.clone(), expand_template(value, &clip.name, &file_name));
+    }
+
+    println!("{}", format!("◌ Uploading to {uploader_name}...").warning());
+    let response = reqwest::Client::new()
+        .post(expand_template(&uploader.url, &clip.name, &file_name))
+        .headers(header_map(&uploader.headers)?)
+        .multipart(form)
+        .send()
+        .await;
+    progress.finish_and_clear();
+    let response = response.context(format!("Failed to reach uploader '{uploader_name}'"))?;
+
+    let status = response.status();
+    let body = response.text().await?;
+    if !status.is_success() {
+        bail!(
+            "Uploader '{uploader_name}' responded with {status}: {}",
+            body.trim()
+        );
+    }
+    let url = extract_url(&body, uploader.url_path.as_deref())?;
+
+    println!("{}", "✔ Clip uploaded successfully!".success().bold());
+    let link = format_url(&clip.name, &url, markdown);
+    println!("  Public URL: {}", link.underline());
+    match copy_to_clipboard(&link).await {
+        Ok(_) => println!("{}", "✔ URL automatically copied to clipboard!".success()),
+        Err(e) => println!(
+            "{}",
+            format!("✗ Could not copy URL to clipboard: {e:#}").warning()
+        ),
+    }
+    Ok(())
+}
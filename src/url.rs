@@ -1,21 +1,55 @@
+use crate::cli_settings::CliSettings;
 use crate::clipboard::copy_to_clipboard;
-use crate::unified_clip::find_unified_clip;
-use anyhow::{Context, Result, bail};
+use crate::theme::Themed;
+use crate::unified_clip::{ClipLookup, find_unified_clip};
+use anyhow::{Result, bail};
 use colored::*;
+use std::fmt::Display;
 use wayclip_core::settings::Settings;
 
-pub async fn handle_url(name: &str) -> Result<()> {
-    let clip = find_unified_clip(name).await?;
+pub fn markdown_link(name: &str, url: &str) -> String {
+    let label = name.replace('[', "\\[").replace(']', "\\]");
+    format!("[{label}]({url})")
+}
+
+pub fn format_url(name: &str, url: &str, markdown: bool) -> String {
+    if markdown {
+        markdown_link(name, url)
+    } else {
+        url.to_string()
+    }
+}
+
+pub fn clip_public_url(
+    settings: &Settings,
+    cli_settings: &CliSettings,
+    id: impl Display,
+) -> String {
+    let base = cli_settings
+        .web_url
+        .as_deref()
+        .unwrap_or(&settings.api_url)
+        .trim_end_matches('/');
+    format!("{base}/clip/{id}")
+}
+
+pub async fn handle_url(name: &str, markdown: bool, lookup: ClipLookup) -> Result<()> {
+    let clip = find_unified_clip(name, lookup).await?;
     let settings = Settings::load().await?;
+    let cli_settings = CliSettings::load().await?;
 
     if let Some(id) = clip.hosted_id {
-        let public_url = format!("{}/clip/{}", settings.api_url, id);
+        let public_url = format_url(
+            &clip.name,
+            &clip_public_url(&settings, &cli_settings, id),
+            markdown,
+        );
         println!("  {}", public_url.underline());
         match copy_to_clipboard(&public_url).await {
-            Ok(_) => println!("{}", "✔ Public URL copied to clipboard!".green()),
+            Ok(_) => println!("{}", "✔ Public URL copied to clipboard!".success()),
             Err(e) => println!(
                 "{}",
-                format!("✗ Could not copy URL to clipboard: {e:#}").yellow()
+                format!("✗ Could not copy URL to clipboard: {e:#}").warning()
             ),
         }
     } else {
@@ -27,14 +61,29 @@ pub async fn handle_url(name: &str) -> Result<()> {
     Ok(())
 }
 
-pub async fn handle_open(name: &str) -> Result<()> {
-    let clip = find_unified_clip(name).await?;
+pub async fn open_url_or_print(url: &str) {
+    if opener::open(url).is_ok() {
+        println!("○ Opening URL in browser: {}", url.accent());
+        return;
+    }
+    println!(
+        "{}",
+        "⚠ Could not open a browser automatically. Open this URL manually:".warning()
+    );
+    println!("  {}", url.underline());
+    if copy_to_clipboard(url).await.is_ok() {
+        println!("{}", "✔ URL copied to clipboard.".success());
+    }
+}
+
+pub async fn handle_open(name: &str, lookup: ClipLookup) -> Result<()> {
+    let clip = find_unified_clip(name, lookup).await?;
     let settings = Settings::load().await?;
+    let cli_settings = CliSettings::load().await?;
 
     if let Some(id) = clip.hosted_id {
-        let public_url = format!("{}/clip/{}", settings.api_url, id);
-        println!("○ Opening URL in browser: {}", public_url.cyan());
-        opener::open(&public_url).context("Failed to open URL in browser.")?;
+        let public_url = clip_public_url(&settings, &cli_settings, id);
+        open_url_or_print(&public_url).await;
     } else {
         bail!(
             "'{}' is not a hosted clip and does not have a public URL.",
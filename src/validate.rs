@@ -1,18 +1,164 @@
+use crate::cli_settings::CliSettings;
 use anyhow::{Result, bail};
+use serde::{Deserialize, Serialize};
+use std::ffi::{OsStr, OsString};
+use std::path::Path;
 
-pub fn sanitize_and_validate_filename_stem(new_name_input: &str) -> Result<String> {
+pub const DEFAULT_MAX_FILENAME_LENGTH: usize = 255;
+
+fn truncate_at_word_boundary(stem: &str, max_bytes: usize) -> String {
+    let mut end = max_bytes.min(stem.len());
+    while !stem.is_char_boundary(end) {
+        end -= 1;
+    }
+    let cut = &stem[..end];
+    let at_boundary = end == stem.len() || stem[end..].starts_with(['_', '-', '.']);
+    let truncated = match cut.rfind(['_', '-', '.']) {
+        Some(index) if !at_boundary && index >= end / 2 => &cut[..index],
+        _ => cut,
+    };
+    truncated.trim_end_matches(['_', '-', '.']).to_string()
+}
+
+pub fn fit_filename_stem(
+    stem: &str,
+    ext: impl AsRef<OsStr>,
+    rules: &FilenameRules,
+) -> Result<String> {
+    let ext = ext.as_ref();
+    let max_length = rules.max_length;
+    let max_stem = max_length.saturating_sub(ext.len() + 1);
+    if stem.len() <= max_stem {
+        return Ok(stem.to_string());
+    }
+    if !rules.truncate {
+        bail!(
+            "Name is {} bytes long but at most {max_stem} are allowed with a .{} extension. Use --truncate to shorten it automatically.",
+            stem.len(),
+            ext.display()
+        );
+    }
+    let truncated = truncate_at_word_boundary(stem, max_stem);
+    if truncated.is_empty() {
+        bail!("Name cannot be shortened to fit within {max_length} bytes.");
+    }
+    Ok(truncated)
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq)]
+#[serde(rename_all = "kebab-case")]
+pub enum NameCase {
+    #[default]
+    Keep,
+    Lower,
+    Upper,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq)]
+#[serde(rename_all = "kebab-case")]
+pub enum CharacterPolicy {
+    #[default]
+    Permissive,
+    Portable,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(default)]
+pub struct SanitizeRules {
+    pub space_replacement: String,
+    pub case: NameCase,
+    pub allowed_characters: CharacterPolicy,
+}
+
+impl Default for SanitizeRules {
+    fn default() -> Self {
+        Self {
+            space_replacement: "_".to_string(),
+            case: NameCase::Keep,
+            allowed_characters: CharacterPolicy::Permissive,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct FilenameRules {
+    pub sanitize: SanitizeRules,
+    pub max_length: usize,
+    pub truncate: bool,
+}
+
+impl Default for FilenameRules {
+    fn default() -> Self {
+        Self {
+            sanitize: SanitizeRules::default(),
+            max_length: DEFAULT_MAX_FILENAME_LENGTH,
+            truncate: false,
+        }
+    }
+}
+
+impl FilenameRules {
+    pub fn from_settings(settings: &CliSettings) -> Result<Self> {
+        let sanitize = settings.sanitize.clone();
+        if sanitize
+            .space_replacement
+            .chars()
+            .any(|c| is_forbidden_char(c) || c.is_control())
+        {
+            bail!(
+                "Invalid space_replacement '{}' in sanitize settings.",
+                sanitize.space_replacement
+            );
+        }
+        Ok(Self {
+            sanitize,
+            max_length: settings
+                .max_filename_length
+                .unwrap_or(DEFAULT_MAX_FILENAME_LENGTH),
+            truncate: false,
+        })
+    }
+
+    pub fn with_truncate(&self, truncate: bool) -> Self {
+        Self {
+            truncate,
+            ..self.clone()
+        }
+    }
+}
+
+fn is_forbidden_char(c: char) -> bool {
+    matches!(c, '<' | '>' | ':' | '"' | '/' | '\\' | '|' | '?' | '*')
+}
+
+pub fn sanitize_and_validate_filename_stem(
+    new_name_input: &str,
+    rules: &FilenameRules,
+) -> Result<String> {
+    let rules = &rules.sanitize;
     let trimmed = new_name_input.trim();
     if trimmed.is_empty() {
         bail!("New name cannot be empty.");
     }
-    let sanitized = trimmed.replace(' ', "_");
+    let replaced = trimmed.replace(' ', &rules.space_replacement);
+    let sanitized = match rules.case {
+        NameCase::Keep => replaced,
+        NameCase::Lower => replaced.to_lowercase(),
+        NameCase::Upper => replaced.to_uppercase(),
+    };
 
-    if sanitized
-        .chars()
-        .any(|c| matches!(c, '<' | '>' | ':' | '"' | '/' | '\\' | '|' | '?' | '*'))
-    {
+    if sanitized.chars().any(is_forbidden_char) {
         bail!("New name contains invalid characters (< > : \" / \\ | ? *).");
     }
+    if rules.allowed_characters == CharacterPolicy::Portable
+        && sanitized.chars().any(|c| {
+            !(c.is_ascii_alphanumeric()
+                || matches!(c, '-' | '_' | '.')
+                || rules.space_replacement.contains(c))
+        })
+    {
+        bail!("New name may only contain ASCII letters, digits, '-', '_' and '.'.");
+    }
     Ok(sanitized)
 }
 
@@ -34,3 +180,60 @@ pub fn validate_ffmpeg_time(time_str: &str) -> Result<String> {
         bail!("Invalid time format '{time_str}'. Use seconds (e.g., 5.5) or HH:MM:SS format.",);
     }
 }
+
+pub fn ffmpeg_time_to_seconds(time_str: &str) -> Result<f64> {
+    let validated = validate_ffmpeg_time(time_str)?;
+    Ok(validated
+        .split(':')
+        .map(|part| part.parse::<f64>().unwrap_or(0.0))
+        .fold(0.0, |total, part| total * 60.0 + part))
+}
+
+pub fn renamed_file_name(
+    clip_path: &Path,
+    new_stem: &str,
+    rules: &FilenameRules,
+) -> Result<OsString> {
+    let ext = match clip_path.extension() {
+        Some(ext) if !ext.is_empty() => ext.to_os_string(),
+        _ => OsString::from("mp4"),
+    };
+    let mut file_name = OsString::from(fit_filename_stem(new_stem, &ext, rules)?);
+    file_name.push(".");
+    file_name.push(&ext);
+    Ok(file_name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn renamed_file_name_keeps_uppercase_extension() {
+        let name = renamed_file_name(
+            Path::new("/clips/old.MKV"),
+            "new",
+            &FilenameRules::default(),
+        )
+        .unwrap();
+        assert_eq!(name, OsString::from("new.MKV"));
+    }
+
+    #[test]
+    fn renamed_file_name_keeps_non_mp4_extension() {
+        let name = renamed_file_name(
+            Path::new("/clips/old.webm"),
+            "new",
+            &FilenameRules::default(),
+        )
+        .unwrap();
+        assert_eq!(name, OsString::from("new.webm"));
+    }
+
+    #[test]
+    fn renamed_file_name_defaults_to_mp4_without_extension() {
+        let name =
+            renamed_file_name(Path::new("/clips/old"), "new", &FilenameRules::default()).unwrap();
+        assert_eq!(name, OsString::from("new.mp4"));
+    }
+}
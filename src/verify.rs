@@ -0,0 +1,94 @@
+use crate::clip_state::ClipState;
+use crate::probe::probe_clip;
+use crate::theme::Themed;
+use crate::unified_clip::{ClipLookup, gather_clips};
+use anyhow::{Result, bail};
+use colored::*;
+use comfy_table::presets::UTF8_FULL;
+use comfy_table::{Cell, ContentArrangement, Table};
+use indicatif::{ProgressBar, ProgressStyle};
+use std::path::PathBuf;
+use std::sync::Arc;
+use tokio::sync::Semaphore;
+use tokio::task::JoinSet;
+
+const PROBE_CONCURRENCY: usize = 4;
+
+pub async fn handle_verify(lookup: ClipLookup) -> Result<()> {
+    println!("{}", "◌ Loading clips...".warning());
+    let local_clips: Vec<(String, PathBuf)> = gather_clips(lookup)
+        .await?
+        .into_iter()
+        .filter_map(|clip| clip.local_path.map(|path| (clip.name, PathBuf::from(path))))
+        .collect();
+
+    if local_clips.is_empty() {
+        println!("{}", "○ No local clips to verify.".warning());
+        return Ok(());
+    }
+
+    let progress = ProgressBar::new(local_clips.len() as u64);
+    progress.set_style(
+        ProgressStyle::with_template("{spinner:.cyan} [{bar:30.cyan/blue}] {pos}/{len} {msg}")?
+            .progress_chars("█▓░"),
+    );
+
+    let semaphore = Arc::new(Semaphore::new(PROBE_CONCURRENCY));
+    let mut tasks = JoinSet::new();
+    for (name, path) in local_clips {
+        let semaphore = semaphore.clone();
+        tasks.spawn(async move {
+            let _permit = semaphore.acquire_owned().await;
+            let result = probe_clip(&path).await;
+            (name, result)
+        });
+    }
+
+    let mut bad_clips = Vec::new();
+    let mut checked = 0;
+    let mut total_duration = 0.0;
+    while let Some(joined) = tasks.join_next().await {
+        let (name, result) = joined?;
+        match result {
+            Ok(info) => total_duration += info.duration,
+            Err(e) => bad_clips.push((name.clone(), format!("{e:#}"))),
+        }
+        checked += 1;
+        progress.set_message(name);
+        progress.inc(1);
+    }
+    progress.finish_and_clear();
+
+    let mut state = ClipState::load().await?;
+    state.unplayable = bad_clips.iter().map(|(name, _)| name.clone()).collect();
+    state.save().await?;
+
+    if bad_clips.is_empty() {
+        println!(
+            "{}",
+            format!("✔ All {checked} local clips are playable ({total_duration:.0}s of footage).")
+                .success()
+                .bold()
+        );
+        return Ok(());
+    }
+
+    bad_clips.sort_by_key(|a| a.0.to_lowercase());
+    let mut table = Table::new();
+    table
+        .load_preset(UTF8_FULL)
+        .set_content_arrangement(ContentArrangement::Dynamic)
+        .set_header(vec![
+            Cell::new("Clip Name").add_attribute(comfy_table::Attribute::Bold),
+            Cell::new("Problem").add_attribute(comfy_table::Attribute::Bold),
+        ]);
+    for (name, reason) in &bad_clips {
+        table.add_row(vec![Cell::new(name), Cell::new(reason)]);
+    }
+    println!("{table}");
+
+    bail!(
+        "{} of {checked} local clips appear corrupt or unplayable.",
+        bad_clips.len()
+    );
+}
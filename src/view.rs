@@ -1,23 +1,195 @@
-use crate::unified_clip::find_unified_clip;
+use crate::cli_settings::CliSettings;
+use crate::encrypt::decrypt_for_access;
+use crate::theme::Themed;
+use crate::unified_clip::{ClipLookup, find_unified_clip};
+use crate::validate::{ffmpeg_time_to_seconds, validate_ffmpeg_time};
 use anyhow::{Context, Result, bail};
 use std::path::Path;
 use std::process::Stdio;
 use tokio::process::Command;
 
-pub async fn handle_view(name: &str, player: Option<&str>) -> Result<()> {
-    let clip = find_unified_clip(name).await?;
+const DEFAULT_PLAYER: &str = "mpv";
+
+pub fn player_binary(player_cmd: &str) -> &str {
+    Path::new(player_cmd)
+        .file_name()
+        .and_then(|s| s.to_str())
+        .unwrap_or(player_cmd)
+}
+
+fn mute_args(player_cmd: &str) -> Option<&'static [&'static str]> {
+    match player_binary(player_cmd) {
+        "mpv" => Some(&["--mute=yes"]),
+        "celluloid" => Some(&["--mpv-mute=yes"]),
+        "vlc" | "cvlc" => Some(&["--no-audio"]),
+        "mplayer" => Some(&["-nosound"]),
+        "ffplay" => Some(&["-an"]),
+        _ => None,
+    }
+}
+
+#[derive(Clone, Debug)]
+struct PlayTime {
+    time: String,
+    secs: f64,
+}
+
+impl PlayTime {
+    fn new(time: &str) -> Result<Self> {
+        let time = validate_ffmpeg_time(time)?;
+        let secs = ffmpeg_time_to_seconds(&time)?;
+        Ok(Self { time, secs })
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct Playback {
+    mute: bool,
+    start: Option<PlayTime>,
+    end: Option<PlayTime>,
+    repeat: bool,
+}
+
+impl Playback {
+    pub fn new(mute: bool, start: Option<&str>, end: Option<&str>, repeat: bool) -> Result<Self> {
+        let start = start.map(PlayTime::new).transpose()?;
+        let end = end.map(PlayTime::new).transpose()?;
+        if let Some(end) = &end {
+            let start_secs = start.as_ref().map_or(0.0, |start| start.secs);
+            if end.secs <= start_secs {
+                bail!(
+                    "--loop-end ({}) must be after --loop-start ({}).",
+                    end.time,
+                    start.as_ref().map_or("0", |start| start.time.as_str())
+                );
+            }
+        }
+        Ok(Self {
+            mute,
+            start,
+            end,
+            repeat,
+        })
+    }
+
+    fn timing_args(&self, player_cmd: &str) -> Option<Vec<String>> {
+        let start_secs = self.start.as_ref().map_or(0.0, |start| start.secs);
+        let mut args = Vec::new();
+        match player_binary(player_cmd) {
+            binary @ ("mpv" | "celluloid") => {
+                let prefix = if binary == "mpv" { "--" } else { "--mpv-" };
+                if let Some(start) = &self.start {
+                    args.push(format!("{prefix}start={}", start.time));
+                }
+                match (&self.end, self.repeat) {
+                    (Some(end), true) => {
+                        let start = self.start.as_ref().map_or("0", |start| start.time.as_str());
+                        args.push(format!("{prefix}ab-loop-a={start}"));
+                        args.push(format!("{prefix}ab-loop-b={}", end.time));
+                    }
+                    (Some(end), false) => args.push(format!("{prefix}end={}", end.time)),
+                    (None, true) => args.push(format!("{prefix}loop-file=inf")),
+                    (None, false) => {}
+                }
+            }
+            "vlc" | "cvlc" => {
+                if self.start.is_some() {
+                    args.push(format!("--start-time={start_secs}"));
+                }
+                if let Some(end) = &self.end {
+                    args.push(format!("--stop-time={}", end.secs));
+                }
+                if self.repeat {
+                    args.push("--repeat".to_string());
+                }
+            }
+            binary @ ("mplayer" | "ffplay") => {
+                if self.start.is_some() {
+                    args.push("-ss".to_string());
+                    args.push(start_secs.to_string());
+                }
+                if let Some(end) = &self.end {
+                    let length_flag = if binary == "mplayer" { "-endpos" } else { "-t" };
+                    args.push(length_flag.to_string());
+                    args.push((end.secs - start_secs).to_string());
+                }
+                if self.repeat {
+                    args.push("-loop".to_string());
+                    args.push("0".to_string());
+                }
+            }
+            _ => return None,
+        }
+        Some(args)
+    }
+
+    fn player_args(&self, player_cmd: &str) -> Vec<String> {
+        let mut args = Vec::new();
+        if self.mute {
+            match mute_args(player_cmd) {
+                Some(mute) => args.extend(mute.iter().map(|arg| arg.to_string())),
+                None => println!(
+                    "{}",
+                    format!("⚠ Don't know how to mute '{player_cmd}'; playing with sound.")
+                        .warning()
+                ),
+            }
+        }
+        if self.start.is_none() && self.end.is_none() && !self.repeat {
+            return args;
+        }
+        match self.timing_args(player_cmd) {
+            Some(timing) => args.extend(timing),
+            None => println!(
+                "{}",
+                format!(
+                    "⚠ Don't know how to seek or loop in '{player_cmd}'; playing the whole clip once."
+                )
+                .warning()
+            ),
+        }
+        args
+    }
+}
+
+pub async fn handle_view(
+    name: &str,
+    player: Option<&str>,
+    playback: &Playback,
+    lookup: ClipLookup,
+) -> Result<()> {
+    if let Some(decrypted) = decrypt_for_access(name).await? {
+        let result = play_file(&decrypted.path, player, playback).await;
+        decrypted.remove().await?;
+        return result;
+    }
+
+    let clip = find_unified_clip(name, lookup).await?;
     let clip_file_str = clip
         .local_path
         .context(format!("Clip '{}' not found locally.", clip.name))?;
-    let clip_file = Path::new(&clip_file_str);
+    play_file(Path::new(&clip_file_str), player, playback).await
+}
 
-    let player_name = player.unwrap_or("mpv").to_string();
+pub async fn resolve_player(player: Option<&str>) -> Result<String> {
+    let default_player = CliSettings::load().await?.default_player;
+    let player_name = player.unwrap_or(&default_player).trim();
+    Ok(if player_name.is_empty() {
+        DEFAULT_PLAYER.to_string()
+    } else {
+        player_name.to_string()
+    })
+}
+
+pub async fn play_file(clip_file: &Path, player: Option<&str>, playback: &Playback) -> Result<()> {
+    let player_name = resolve_player(player).await?;
     let mut parts = player_name.split_whitespace();
-    let player_cmd = parts.next().unwrap_or("mpv");
+    let player_cmd = parts.next().unwrap_or(DEFAULT_PLAYER);
     let player_args = parts;
 
     let mut command = Command::new(player_cmd);
     command.args(player_args);
+    command.args(playback.player_args(player_cmd));
     command.arg(clip_file);
     command
         .stdin(Stdio::null())
@@ -33,10 +205,10 @@ pub async fn handle_view(name: &str, player: Option<&str>) -> Result<()> {
         return Ok(());
     }
 
-    if let Some(code) = status.code() {
-        if code == 3 || code == 4 {
-            return Ok(());
-        }
+    if let Some(code) = status.code()
+        && (code == 3 || code == 4)
+    {
+        return Ok(());
     }
 
     bail!("Media player exited with an unexpected error status: {status}",);
@@ -0,0 +1,50 @@
+use crate::save::handle_save;
+use crate::theme::Themed;
+use crate::unified_clip::ClipLookup;
+use crate::validate::FilenameRules;
+use anyhow::Result;
+use chrono::Local;
+use colored::*;
+use tokio::io::{AsyncBufReadExt, BufReader};
+
+pub async fn handle_watch(filename_rules: &FilenameRules, lookup: ClipLookup) -> Result<()> {
+    println!("{}", "┌─ Wayclip Watch ─────".bold());
+    println!("│ Press {} to save a clip.", "Enter".accent());
+    println!(
+        "│ Type {} and press Enter (or Ctrl-C) to stop.",
+        "q".accent()
+    );
+    println!("└─────────────────────");
+
+    let mut lines = BufReader::new(tokio::io::stdin()).lines();
+    let mut saved = 0;
+    let mut failed = 0;
+    loop {
+        let line = tokio::select! {
+            line = lines.next_line() => line?,
+            _ = tokio::signal::ctrl_c() => None,
+        };
+        let Some(line) = line else {
+            break;
+        };
+        if matches!(line.trim(), "q" | "quit" | "exit") {
+            break;
+        }
+
+        let timestamp = Local::now().format("%H:%M:%S");
+        print!("[{timestamp}] ");
+        match handle_save(filename_rules, lookup).await {
+            Ok(_) => saved += 1,
+            Err(e) => {
+                failed += 1;
+                println!("{} {e:#}", "✗ Save failed:".error());
+            }
+        }
+    }
+
+    println!(
+        "{}",
+        format!("○ Stopped watching ({saved} saved, {failed} failed).").warning()
+    );
+    Ok(())
+}